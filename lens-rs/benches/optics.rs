@@ -0,0 +1,73 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lens_rs::*;
+
+type Nested = (u64, (u64, (u64, Option<Box<(u64, u64)>>)));
+
+fn nested() -> Nested {
+    (1, (2, (3, Some(Box::new((4, 5))))))
+}
+
+fn lens(c: &mut Criterion) {
+    let mut x = nested();
+    let mut group = c.benchmark_group("lens");
+    group.bench_function("direct", |b| b.iter(|| black_box(&x).1 .1 .0));
+    group.bench_function("view_ref", |b| {
+        b.iter(|| *optics!(_1._1._0).view_ref(black_box(&x)))
+    });
+    group.bench_function("direct_mut", |b| b.iter(|| black_box(&mut x).1 .1 .0 += 1));
+    group.bench_function("view_mut", |b| {
+        b.iter(|| *optics!(_1._1._0).view_mut(black_box(&mut x)) += 1)
+    });
+    group.finish();
+}
+
+fn prism(c: &mut Criterion) {
+    let x = nested();
+    let mut group = c.benchmark_group("prism");
+    group.bench_function("direct", |b| {
+        b.iter(|| black_box(&x).1 .1 .1.as_ref().map(|boxed| boxed.1))
+    });
+    group.bench_function("pm_ref", |b| {
+        b.iter(|| {
+            optics!(_1._1._1.Some._box._1)
+                .pm_ref(black_box(&x))
+                .copied()
+        })
+    });
+    group.bench_function("preview", |b| {
+        b.iter(|| optics!(_1._1._1.Some._box._1).preview(black_box(&x)))
+    });
+    group.finish();
+}
+
+fn traversal(c: &mut Criterion) {
+    let x: Vec<Option<(u64, u64)>> = (0..1024)
+        .map(|i| if i % 3 == 0 { None } else { Some((i, i)) })
+        .collect();
+    let mut group = c.benchmark_group("traversal");
+    group.bench_function("direct", |b| {
+        b.iter(|| black_box(&x).iter().flatten().map(|p| p.1).sum::<u64>())
+    });
+    group.bench_function("traverse_ref", |b| {
+        b.iter(|| {
+            optics!(_mapped.Some._1)
+                .traverse_ref(black_box(&x))
+                .into_iter()
+                .sum::<u64>()
+        })
+    });
+    group.bench_function("visit_ref", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            optics!(_mapped.Some._1).visit_ref(black_box(&x), &mut |i| {
+                sum += i;
+                true
+            });
+            sum
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lens, prism, traversal);
+criterion_main!(benches);
@@ -1,19 +1,70 @@
 use inwelling::*;
 
-use std::{env, path::PathBuf};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+// only the crates with a `[package.metadata.inwelling.lens-rs]` section are scanned,
+// the sources of other dependencies are never walked.
+fn scan_rs_paths(dir: &Path, rs_paths: &mut Vec<PathBuf>) {
+    if let Ok(entries) = dir.read_dir() {
+        entries.flatten().for_each(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                scan_rs_paths(&path, rs_paths);
+            } else if path.extension() == Some("rs".as_ref()) {
+                rs_paths.push(path);
+            }
+        });
+    }
+}
+
+// watching is on with the `watch` feature, `LENS_RS_WATCH=0` or `LENS_RS_WATCH=1` overrides it.
+fn watch_rs_files() -> bool {
+    println!("cargo:rerun-if-env-changed=LENS_RS_WATCH");
+    match env::var("LENS_RS_WATCH") {
+        Ok(watch) => watch != "0" && watch != "false",
+        Err(_) => env::var_os("CARGO_FEATURE_WATCH").is_some(),
+    }
+}
 
 fn main() {
-    let mut output = inwelling(Opts {
+    // any `rerun-if-changed` turns off cargo's default of rerunning on every change of this package.
+    println!("cargo:rerun-if-changed=build.rs");
+    let watch = watch_rs_files();
+
+    let mut sections = inwelling(Opts {
         watch_manifest: true,
-        watch_rs_files: true,
-        dump_rs_paths: true,
+        watch_rs_files: false,
+        dump_rs_paths: false,
     })
-    .sections
-    .into_iter()
-    .fold(
+    .sections;
+    // the same crates and files in the same order every time, so the generated file diffs cleanly.
+    sections.sort_by(|a, b| (&a.pkg, &a.manifest).cmp(&(&b.pkg, &b.manifest)));
+
+    let mut output = sections.into_iter().fold(
         String::from("lens_rs_derive::scan_optics_from_source_files!{\n"),
         |mut output, section| {
-            section.rs_paths.unwrap().iter().for_each(|rs_path| {
+            let crate_dir = section.manifest.parent().unwrap();
+            output.push_str(&format!(
+                "    // from crate {} ({})\n",
+                section.pkg,
+                section.manifest.display()
+            ));
+            let mut rs_paths = vec![];
+            ["src", "examples", "tests"].iter().for_each(|dir| {
+                let dir = crate_dir.join(dir);
+                // a directory is watched as a whole, so new files are picked up as well.
+                if watch && dir.is_dir() {
+                    println!("cargo:rerun-if-changed={}", dir.to_str().unwrap());
+                }
+                scan_rs_paths(&dir, &mut rs_paths)
+            });
+            rs_paths.sort();
+
+            rs_paths.iter().for_each(|rs_path| {
                 let rs_path = rs_path.to_str().unwrap();
 
                 if cfg!(windows) {
@@ -22,11 +73,85 @@ fn main() {
                     output.push_str(&format!("    \"{}\",\n", rs_path));
                 }
             });
+
+            // `optics = ["name", ..]` declares the optics of code no file has, e.g. generated by prost.
+            let mut declared = section
+                .metadata
+                .get("optics")
+                .and_then(|optics| optics.as_array())
+                .into_iter()
+                .flatten()
+                .map(|optic| match optic.as_str() {
+                    Some(name) => declared_optic(name, &section.pkg),
+                    None => panic!(
+                        "`optics` of [package.metadata.inwelling.lens-rs] in crate {} should list strings, found `{}`",
+                        section.pkg, optic
+                    ),
+                })
+                .collect::<Vec<_>>();
+            declared.sort_unstable();
+            declared
+                .into_iter()
+                .for_each(|optic| output.push_str(&format!("    {},\n", optic)));
             output
         },
     );
     output.push('}');
 
-    let out_path = PathBuf::from(env::var("OUT_DIR").expect("$OUT_DIR should exist."));
-    std::fs::write(out_path.join("optics.rs"), output).expect("optics.rs should be generated.");
+    let out_path = optics_path();
+    write_if_changed(&out_path, &output);
+    println!("cargo:rustc-env=LENS_RS_OPTICS_FILE={}", out_path.display());
+}
+
+// an entry of `optics = [..]` goes into the generated file as is, so it must be an identifier.
+fn declared_optic<'a>(name: &'a str, pkg: &str) -> &'a str {
+    let mut chars = name.chars();
+    let is_ident = chars.next().is_some_and(|c| c == '_' || c.is_alphabetic())
+        && chars.all(|c| c == '_' || c.is_alphanumeric());
+    if !is_ident || name == "_" {
+        panic!(
+            "`{}` in `optics` of [package.metadata.inwelling.lens-rs] in crate {} should be the name of a field or variant",
+            name, pkg
+        );
+    }
+    name
+}
+
+// `LENS_RS_OPTICS_PATH` puts the generated file elsewhere than `$OUT_DIR`, e.g. out of a target dir shared by CI jobs.
+fn optics_path() -> PathBuf {
+    println!("cargo:rerun-if-env-changed=LENS_RS_OPTICS_PATH");
+    match env::var_os("LENS_RS_OPTICS_PATH") {
+        Some(path) => {
+            // relative to the package, `include!` would resolve it relative to `src/optics.rs`.
+            let path = env::current_dir()
+                .expect("the package dir should exist.")
+                .join(path);
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)
+                    .expect("the directory of $LENS_RS_OPTICS_PATH should be created.");
+            }
+            path
+        }
+        None => {
+            PathBuf::from(env::var("OUT_DIR").expect("$OUT_DIR should exist.")).join("optics.rs")
+        }
+    }
+}
+
+// concurrent builds sharing the file each write their own temporary file and rename it in place,
+// so a reader sees the old or the new file and never a truncated one.
+// An unchanged file isn't touched, so its modification time doesn't rebuild lens-rs.
+fn write_if_changed(path: &Path, output: &str) {
+    if fs::read_to_string(path).ok().as_deref() == Some(output) {
+        return;
+    }
+    let tmp_path = path.with_extension(format!("rs.{}.tmp", process::id()));
+    fs::write(&tmp_path, output).expect("optics.rs should be generated.");
+    if let Err(err) = fs::rename(&tmp_path, path) {
+        let _ = fs::remove_file(&tmp_path);
+        // another build may have renamed the same contents in place first, e.g. on Windows.
+        if fs::read_to_string(path).ok().as_deref() != Some(output) {
+            panic!("optics.rs should be generated: {}", err);
+        }
+    }
 }
@@ -0,0 +1,50 @@
+use crate::*;
+
+/**
+* Construct a value from its default by assigning foci through optics.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let config: (String, (u16, Option<bool>)) = Builder::default()
+*     .with(optics!(_0), "localhost".to_string())
+*     .with(optics!(_1._0), 8080)
+*     .with(optics!(_1._1._or_default), true)
+*     .build();
+* assert_eq!(config, ("localhost".to_string(), (8080, Option::Some(true))));
+* ```
+*/
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Builder<S> {
+    value: S,
+}
+
+impl<S> Builder<S> {
+    /// start from `value` instead of `S::default()`.
+    pub fn from_value(value: S) -> Self {
+        Self { value }
+    }
+
+    /// assign `value` to the focus, the later assignments win.
+    pub fn with<St>(mut self, optic: St, value: St::To) -> Self
+    where
+        St: Setter<S>,
+    {
+        optic.set(&mut self.value, value);
+        self
+    }
+
+    /// like `with`, but modify the focus in place.
+    pub fn with_over<Ov, F>(mut self, optic: Ov, mut f: F) -> Self
+    where
+        Ov: Over<S>,
+        F: FnMut(&mut Ov::To),
+    {
+        optic.over(&mut self.value, &mut f);
+        self
+    }
+
+    pub fn build(self) -> S {
+        self.value
+    }
+}
@@ -0,0 +1,240 @@
+/*!
+* Optics over the buffers of the `bytes` crate, for the headers of binary protocols:
+* `index` focuses a byte, `slice` a range of bytes and e.g. `u16_be_at` an integer at a fixed offset.
+* They have no focus when the buffer is too short.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::bytes_buf::{slice, u16_be_at};
+* use lens_rs::value::index;
+* use ::bytes::BytesMut;
+*
+* let mut packet = (BytesMut::from(&[4, 0x00, 0x50, b'p', b'i', b'n', b'g'][..]), "udp");
+* let port = optics::_0(u16_be_at(1));
+* assert_eq!(port.preview(&packet), Option::Some(80));
+* port.set(&mut packet, 8080);
+* assert_eq!(packet.0[..3], [4, 0x1f, 0x90]);
+*
+* *index(0, __).pm_mut(&mut packet.0).unwrap() += 1;
+* assert_eq!(slice(3..7).preview(&packet.0.freeze()).unwrap(), &b"ping"[..]);
+* assert_eq!(u16_be_at(6).preview(&vec![0u8; 7]), Option::None);
+* ```
+*/
+use crate::*;
+use ::bytes::{Bytes, BytesMut};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+macro_rules! impl_index {
+    ($data:ty) => {
+        impl<Tr> TraversalRef<$data> for optics::_index<Tr>
+        where
+            Tr: TraversalRef<u8>,
+        {
+            type To = Tr::To;
+
+            #[inline]
+            fn traverse_ref<'a>(&self, source: &'a $data) -> Vec<&'a Self::To> {
+                match source.get(self.0) {
+                    Option::Some(x) => self.1.traverse_ref(x),
+                    Option::None => vec![],
+                }
+            }
+        }
+
+        impl<Pm> PrismRef<$data> for optics::_index<Pm>
+        where
+            Pm: PrismRef<u8>,
+        {
+            #[inline]
+            fn pm_ref<'a>(&self, source: &'a $data) -> Option<&'a Self::To> {
+                source.get(self.0).and_then(|x| self.1.pm_ref(x))
+            }
+        }
+
+        impl<Pv> Preview<$data> for optics::_index<Pv>
+        where
+            Pv: Preview<u8>,
+        {
+            type To = Pv::To;
+
+            #[inline]
+            fn preview(&self, source: &$data) -> Option<Self::To> {
+                source.get(self.0).and_then(|x| self.1.preview(x))
+            }
+        }
+    };
+}
+
+impl_index!(Bytes);
+impl_index!(BytesMut);
+
+// `Bytes` is shared and immutable, only a `BytesMut` can be written through.
+impl<Tr> TraversalMut<BytesMut> for optics::_index<Tr>
+where
+    Tr: TraversalMut<u8>,
+{
+    #[inline]
+    fn traverse_mut<'a>(&self, source: &'a mut BytesMut) -> Vec<&'a mut Self::To> {
+        match source.get_mut(self.0) {
+            Option::Some(x) => self.1.traverse_mut(x),
+            Option::None => vec![],
+        }
+    }
+}
+
+impl<Pm> PrismMut<BytesMut> for optics::_index<Pm>
+where
+    Pm: PrismMut<u8>,
+{
+    #[inline]
+    fn pm_mut<'a>(&self, source: &'a mut BytesMut) -> Option<&'a mut Self::To> {
+        source.get_mut(self.0).and_then(|x| self.1.pm_mut(x))
+    }
+}
+
+impl<St> Setter<BytesMut> for optics::_index<St>
+where
+    St: Setter<u8>,
+{
+    type To = St::To;
+
+    #[inline]
+    fn set(&self, source: &mut BytesMut, value: Self::To) {
+        if let Option::Some(x) = source.get_mut(self.0) {
+            self.1.set(x, value)
+        }
+    }
+}
+
+/// focus on the bytes in `range`, see `slice`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Slice(Range<usize>);
+
+/// focus on the bytes in `range`, a `Bytes` is sliced without copying.
+/// Setting writes the bytes in place and is a no-op unless the value has the length of the range.
+pub const fn slice(range: Range<usize>) -> Slice {
+    Slice(range)
+}
+
+impl Slice {
+    fn within(&self, len: usize) -> bool {
+        self.0.start <= self.0.end && self.0.end <= len
+    }
+}
+
+impl Preview<Bytes> for Slice {
+    type To = Bytes;
+
+    #[inline]
+    fn preview(&self, source: &Bytes) -> Option<Self::To> {
+        self.within(source.len())
+            .then(|| source.slice(self.0.clone()))
+    }
+}
+
+impl Preview<BytesMut> for Slice {
+    type To = Bytes;
+
+    #[inline]
+    fn preview(&self, source: &BytesMut) -> Option<Self::To> {
+        source.get(self.0.clone()).map(Bytes::copy_from_slice)
+    }
+}
+
+impl Setter<BytesMut> for Slice {
+    type To = Bytes;
+
+    #[inline]
+    fn set(&self, source: &mut BytesMut, value: Self::To) {
+        match source.get_mut(self.0.clone()) {
+            Option::Some(bytes) if bytes.len() == value.len() => bytes.copy_from_slice(&value),
+            _ => (),
+        }
+    }
+}
+
+/// an integer at a fixed offset of a buffer, see e.g. `u16_be_at`.
+#[derive(Debug)]
+pub struct IntAt<I> {
+    offset: usize,
+    big_endian: bool,
+    int: PhantomData<fn() -> I>,
+}
+
+impl<I> Clone for IntAt<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I> Copy for IntAt<I> {}
+
+impl<I> PartialEq for IntAt<I> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.offset, self.big_endian) == (other.offset, other.big_endian)
+    }
+}
+
+impl<I> Eq for IntAt<I> {}
+
+macro_rules! impl_int_at {
+    ($int:ty, $be:ident, $le:ident) => {
+        #[doc = concat!("a big-endian `", stringify!($int), "` starting at `offset`.")]
+        pub const fn $be(offset: usize) -> IntAt<$int> {
+            IntAt {
+                offset,
+                big_endian: true,
+                int: PhantomData,
+            }
+        }
+
+        #[doc = concat!("a little-endian `", stringify!($int), "` starting at `offset`.")]
+        pub const fn $le(offset: usize) -> IntAt<$int> {
+            IntAt {
+                offset,
+                big_endian: false,
+                int: PhantomData,
+            }
+        }
+
+        impl<T: AsRef<[u8]>> Preview<T> for IntAt<$int> {
+            type To = $int;
+
+            #[inline]
+            fn preview(&self, source: &T) -> Option<Self::To> {
+                let end = self.offset.checked_add(std::mem::size_of::<$int>())?;
+                let bytes = source.as_ref().get(self.offset..end)?.try_into().ok()?;
+                Option::Some(if self.big_endian {
+                    <$int>::from_be_bytes(bytes)
+                } else {
+                    <$int>::from_le_bytes(bytes)
+                })
+            }
+        }
+
+        impl<T: AsMut<[u8]>> Setter<T> for IntAt<$int> {
+            type To = $int;
+
+            #[inline]
+            fn set(&self, source: &mut T, value: Self::To) {
+                let end = self.offset.saturating_add(std::mem::size_of::<$int>());
+                if let Option::Some(bytes) = source.as_mut().get_mut(self.offset..end) {
+                    bytes.copy_from_slice(&if self.big_endian {
+                        value.to_be_bytes()
+                    } else {
+                        value.to_le_bytes()
+                    })
+                }
+            }
+        }
+    };
+}
+
+impl_int_at!(u16, u16_be_at, u16_le_at);
+impl_int_at!(u32, u32_be_at, u32_le_at);
+impl_int_at!(u64, u64_be_at, u64_le_at);
+impl_int_at!(i16, i16_be_at, i16_le_at);
+impl_int_at!(i32, i32_be_at, i32_le_at);
+impl_int_at!(i64, i64_be_at, i64_le_at);
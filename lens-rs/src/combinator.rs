@@ -0,0 +1,1384 @@
+use crate::*;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::ControlFlow;
+
+/**
+* Turn an affine optic into a `Getter` which falls back to the value when there is no focus.
+* ## Example
+* ```
+* use lens_rs::*;
+* let name = preview_or(optics!(_1.Some), "anonymous".to_string());
+* assert_eq!(name.get(&(0, Option::Some("bob".to_string()))), "bob");
+* assert_eq!(name.get(&(0, Option::None)), "anonymous");
+* ```
+*/
+pub const fn preview_or<Pv, V>(optic: Pv, value: V) -> PreviewOr<Pv, V> {
+    PreviewOr { optic, value }
+}
+
+/**
+* Like `preview_or`, but the fallback is computed only when there is no focus.
+* ## Example
+* ```
+* use lens_rs::*;
+* let text = preview_or_else(optics!(_0._utf8), || "<invalid>".to_string());
+* assert_eq!(text.get(&(vec![0xff], 0)), "<invalid>");
+* ```
+*/
+pub const fn preview_or_else<Pv, F>(optic: Pv, fallback: F) -> PreviewOrElse<Pv, F> {
+    PreviewOrElse { optic, fallback }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PreviewOr<Pv, V> {
+    optic: Pv,
+    value: V,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PreviewOrElse<Pv, F> {
+    optic: Pv,
+    fallback: F,
+}
+
+impl<Pv, T> Preview<T> for PreviewOr<Pv, Pv::To>
+where
+    Pv: Preview<T>,
+    Pv::To: Clone,
+{
+    type To = Pv::To;
+
+    fn preview(&self, source: &T) -> Option<Self::To> {
+        Option::Some(self.get(source))
+    }
+}
+
+impl<Pv, T> Getter<T> for PreviewOr<Pv, Pv::To>
+where
+    Pv: Preview<T>,
+    Pv::To: Clone,
+{
+    fn get(&self, source: &T) -> Self::To {
+        self.optic
+            .preview(source)
+            .unwrap_or_else(|| self.value.clone())
+    }
+}
+
+impl<Pv, F, T> Preview<T> for PreviewOrElse<Pv, F>
+where
+    Pv: Preview<T>,
+    F: Fn() -> Pv::To,
+{
+    type To = Pv::To;
+
+    fn preview(&self, source: &T) -> Option<Self::To> {
+        Option::Some(self.get(source))
+    }
+}
+
+impl<Pv, F, T> Getter<T> for PreviewOrElse<Pv, F>
+where
+    Pv: Preview<T>,
+    F: Fn() -> Pv::To,
+{
+    fn get(&self, source: &T) -> Self::To {
+        self.optic.preview(source).unwrap_or_else(&self.fallback)
+    }
+}
+
+/**
+* Focus on the values of a `BTreeMap` whose keys are in `bounds`, e.g. the events between two instants.
+* Like `BTreeMap::range`, it panics when the start of the range is after its end.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::collections::BTreeMap;
+*
+* let mut events: (BTreeMap<u64, (String, u8)>, ()) = (BTreeMap::new(), ());
+* for (t, name) in [(1, "boot"), (5, "login"), (7, "logout"), (9, "halt")] {
+*     events.0.insert(t, (name.to_string(), 0));
+* }
+*
+* let names = optics!(_0.range(5..9)._0);
+* assert_eq!(names.traverse_ref(&events), vec!["login", "logout"]);
+* optics!(_0.range(..=5)._1).traverse_mut(&mut events).into_iter().for_each(|n| *n = 1);
+* assert_eq!(events.0[&5].1, 1);
+* assert_eq!(events.0[&7].1, 0);
+* ```
+*/
+pub const fn range<Optic, R>(optic: Optic, bounds: R) -> optics::_range<R, Optic> {
+    optics::_range(bounds, optic)
+}
+
+/**
+* Focus on the value of a `Box<dyn Any>`, or of a `Send`/`Sync` one, if it's a `T`,
+* e.g. the state of one kind of plugin in a map of them.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::any::Any;
+*
+* #[derive(Lens)]
+* struct Counter(#[optic] u32);
+*
+* let mut plugins: Vec<Box<dyn Any>> = vec![Box::new(Counter(1)), Box::new("logger"), Box::new(Counter(5))];
+* let counts = optics::_mapped(downcast::<Counter, _>(optics!(_0)));
+* counts.traverse_mut(&mut plugins).into_iter().for_each(|n| *n += 1);
+* assert_eq!(counts.traverse_ref(&plugins), vec![&2, &6]);
+* assert_eq!(downcast::<&str, _>(__).preview(&plugins[1]), Option::Some("logger"));
+* ```
+*/
+pub const fn downcast<T, Optic>(optic: Optic) -> optics::_downcast<T, Optic> {
+    optics::_downcast(PhantomData, optic)
+}
+
+/**
+* Build a value through a composed prism, e.g. `Ok(Some(v))` through `Ok.Some`.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x: Result<Option<(i32, char)>, ()> = review_of(optics!(Ok.Some), (1, 'a'));
+* assert_eq!(x, Result::Ok(Option::Some((1, 'a'))));
+* ```
+*/
+pub fn review_of<Rv, T>(optic: Rv, value: Rv::From) -> T
+where
+    Rv: Review<T>,
+{
+    optic.review(value)
+}
+
+/**
+* Collect the error values of the `Result`s reached through the optic.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x = (0, vec![Result::Ok(1), Result::Err("io"), Result::Err("parse")]);
+* assert_eq!(errors_of(optics!(_1._mapped), &x), vec![&"io", &"parse"]);
+* ```
+*/
+pub fn errors_of<'a, Tr, T, A, E>(optic: Tr, source: &'a T) -> Vec<&'a E>
+where
+    Tr: TraversalRef<T, To = Result<A, E>>,
+    A: 'a,
+{
+    let mut errors = vec![];
+    optic.visit_ref(source, &mut |r| {
+        errors.extend(r.as_ref().err());
+        true
+    });
+    errors
+}
+
+/**
+* Rewrite every error value reached through the optic, e.g. to attach context.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![Result::Ok(1), Result::Err("not found".to_string())]);
+* map_err_of(optics!(_1._mapped), &mut x, |e: &mut String| e.insert_str(0, "config: "));
+* assert_eq!(x.1, vec![Result::Ok(1), Result::Err("config: not found".to_string())]);
+* ```
+*/
+pub fn map_err_of<Tr, T, A, E, F>(optic: Tr, source: &mut T, f: F)
+where
+    Tr: TraversalMut<T, To = Result<A, E>>,
+    F: FnMut(&mut E),
+{
+    optic
+        .traverse_mut(source)
+        .into_iter()
+        .filter_map(|r| r.as_mut().err())
+        .for_each(f)
+}
+
+/**
+* Update every focus of the optic in order, stopping at the first error and returning it,
+* the foci before it stay updated.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![1u8, 200, 3]);
+* let r = try_traverse_mut(optics!(_1._mapped), &mut x, |n| {
+*     *n = n.checked_add(100).ok_or(*n)?;
+*     Result::Ok(())
+* });
+* assert_eq!(r, Result::Err(200));
+* assert_eq!(x.1, vec![101, 200, 3]);
+* ```
+*/
+pub fn try_traverse_mut<Tr, T, E, F>(optic: Tr, source: &mut T, mut f: F) -> Result<(), E>
+where
+    Tr: TraversalMut<T>,
+    F: FnMut(&mut Tr::To) -> Result<(), E>,
+{
+    optic.traverse_mut(source).into_iter().try_for_each(&mut f)
+}
+
+/**
+* The owning `try_traverse_mut`, giving back the updated source or the first error.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x = try_over(optics!(_1._mapped), (0, vec![1, 2]), |n: &mut i32| {
+*     *n *= 10;
+*     Result::<(), ()>::Ok(())
+* });
+* assert_eq!(x, Result::Ok((0, vec![10, 20])));
+* ```
+*/
+pub fn try_over<Tr, T, E, F>(optic: Tr, mut source: T, f: F) -> Result<T, E>
+where
+    Tr: TraversalMut<T>,
+    F: FnMut(&mut Tr::To) -> Result<(), E>,
+{
+    try_traverse_mut(optic, &mut source, f)?;
+    Result::Ok(source)
+}
+
+/**
+* Update every focus of the optic in order, reporting the progress of a long batch to `progress(visited, total_hint)`,
+* once before the first focus and after each one. `total_hint` is the number of foci when it's known up front.
+* `progress` cancels the rest by returning `ControlFlow::Break`, the foci before stay updated.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::ops::ControlFlow;
+*
+* let mut x = (0, vec![1, 2, 3, 4]);
+* let mut reports = vec![];
+* let r = traverse_mut_with_progress(optics!(_1._mapped), &mut x, |n| *n *= 10, |visited, total| {
+*     reports.push((visited, total));
+*     if visited < 2 { ControlFlow::Continue(()) } else { ControlFlow::Break(()) }
+* });
+* assert_eq!(r, Result::Err(Cancelled { visited: 2 }));
+* assert_eq!(reports, vec![(0, Option::Some(4)), (1, Option::Some(4)), (2, Option::Some(4))]);
+* assert_eq!(x.1, vec![10, 20, 3, 4]);
+*
+* let r = traverse_mut_with_progress(optics!(_1._mapped), &mut x, |n| *n += 1, |_, _| ControlFlow::Continue(()));
+* assert_eq!(r, Result::Ok(4));
+* ```
+*/
+pub fn traverse_mut_with_progress<Tr, T, F, P>(
+    optic: Tr,
+    source: &mut T,
+    mut f: F,
+    mut progress: P,
+) -> Result<usize, Cancelled>
+where
+    Tr: TraversalMut<T>,
+    F: FnMut(&mut Tr::To),
+    P: FnMut(usize, Option<usize>) -> ControlFlow<()>,
+{
+    let foci = optic.traverse_mut(source);
+    let total = foci.len();
+    let mut report = |visited| match progress(visited, Option::Some(total)) {
+        ControlFlow::Continue(()) => Result::Ok(()),
+        ControlFlow::Break(()) => Result::Err(Cancelled { visited }),
+    };
+    report(0)?;
+    foci.into_iter().enumerate().try_for_each(|(i, x)| {
+        f(x);
+        report(i + 1)
+    })?;
+    Result::Ok(total)
+}
+
+/// a batch stopped by its progress callback, after `visited` foci.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Cancelled {
+    pub visited: usize,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cancelled after {} foci", self.visited)
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/**
+* Update the focus of a lens and return it, so the new value needn't be viewed again.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, (1, "counter"));
+* let count = over_with_result(optics!(_1._0), &mut x, |n| *n += 1);
+* assert_eq!(*count, 2);
+* *count *= 10;
+* assert_eq!(x.1, (20, "counter"));
+* ```
+*/
+pub fn over_with_result<Ls, T, F>(optic: Ls, source: &mut T, f: F) -> &mut Ls::To
+where
+    Ls: LensMut<T>,
+    F: FnOnce(&mut Ls::To),
+{
+    let focus = optic.view_mut(source);
+    f(focus);
+    focus
+}
+
+/**
+* Set the focus of a lens and return it.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![1]);
+* set_with_result(optics!(_1), &mut x, vec![2, 3]).push(4);
+* assert_eq!(x.1, vec![2, 3, 4]);
+* ```
+*/
+pub fn set_with_result<Ls, T>(optic: Ls, source: &mut T, value: Ls::To) -> &mut Ls::To
+where
+    Ls: LensMut<T>,
+{
+    over_with_result(optic, source, |focus| *focus = value)
+}
+
+/**
+* A `Getter` caching an expensive view computed from the focus of a lens,
+* it's recomputed when the focus no longer equals a clone of the one it was computed from.
+* So every `get` still compares the focus, it saves `compute` rather than the walk over the focus.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::cell::Cell;
+*
+* let runs = Cell::new(0);
+* let total = memoized(optics!(_0), |xs: &Vec<i32>| {
+*     runs.set(runs.get() + 1);
+*     xs.iter().sum::<i32>()
+* });
+*
+* let mut x = (vec![1, 2, 3], 0);
+* assert_eq!(total.get(&x), 6);
+* *optics!(_1).view_mut(&mut x) += 1;  // `_0` unchanged, cached
+* assert_eq!(total.get(&x), 6);
+* optics!(_0).view_mut(&mut x).push(4);
+* assert_eq!(total.get(&x), 10);
+* assert_eq!(runs.get(), 2);
+* ```
+*/
+pub const fn memoized<Ls, A, B, F>(optic: Ls, compute: F) -> Memoized<Ls, A, B, F> {
+    Memoized {
+        optic,
+        compute,
+        cache: RefCell::new(Option::None),
+    }
+}
+
+pub struct Memoized<Ls, A, B, F> {
+    optic: Ls,
+    compute: F,
+    cache: RefCell<Option<(A, B)>>,
+}
+
+impl<Ls, A, B, F> Memoized<Ls, A, B, F> {
+    /// drop the cached view, e.g. when `compute` depends on more than the focus.
+    pub fn invalidate(&self) {
+        self.cache.borrow_mut().take();
+    }
+}
+
+impl<Ls, T, B, F> Preview<T> for Memoized<Ls, Ls::To, B, F>
+where
+    Ls: LensRef<T>,
+    Ls::To: Clone + PartialEq,
+    B: Clone,
+    F: Fn(&Ls::To) -> B,
+{
+    type To = B;
+
+    fn preview(&self, source: &T) -> Option<Self::To> {
+        Option::Some(self.get(source))
+    }
+}
+
+impl<Ls, T, B, F> Getter<T> for Memoized<Ls, Ls::To, B, F>
+where
+    Ls: LensRef<T>,
+    Ls::To: Clone + PartialEq,
+    B: Clone,
+    F: Fn(&Ls::To) -> B,
+{
+    fn get(&self, source: &T) -> Self::To {
+        let focus = self.optic.view_ref(source);
+        if let Option::Some((last, view)) = &*self.cache.borrow() {
+            if last == focus {
+                return view.clone();
+            }
+        }
+        // the cache isn't borrowed while computing, `compute` may get through the memo too
+        let view = (self.compute)(focus);
+        *self.cache.borrow_mut() = Option::Some((focus.clone(), view.clone()));
+        view
+    }
+}
+
+/**
+* An optic over layered values, `(top, fallback)` or a `Vec` of layers from the top down.
+* It reads the first layer whose focus is `Some`, through `primary` for the top layer and `fallback` for the others,
+* and writes to the top layer.
+* ## Example
+* ```
+* use lens_rs::*;
+* let port = overlay(optics!(_0), optics!(_0));
+*
+* let defaults = (Option::Some(80), Option::Some("localhost"));
+* let mut layers = ((Option::None, Option::None), defaults);
+* assert_eq!(port.preview(&layers), Option::Some(80));
+*
+* port.set(&mut layers, 8080);
+* assert_eq!(port.preview(&layers), Option::Some(8080));
+* assert_eq!(layers.1, defaults);
+*
+* let mut stack = vec![(Option::None, Option::None), (Option::None, Option::Some("env")), defaults];
+* assert_eq!(overlay(optics!(_1), optics!(_1)).preview(&stack), Option::Some("env"));
+* port.set(&mut stack, 443);
+* assert_eq!(stack[0], (Option::Some(443), Option::None));
+* ```
+*/
+pub const fn overlay<P, F>(primary: P, fallback: F) -> Overlay<P, F> {
+    Overlay { primary, fallback }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Overlay<P, F> {
+    primary: P,
+    fallback: F,
+}
+
+impl<P, F, S, A> Preview<(S, S)> for Overlay<P, F>
+where
+    P: LensRef<S, To = Option<A>>,
+    F: LensRef<S, To = Option<A>>,
+    A: Clone,
+{
+    type To = A;
+
+    fn preview(&self, source: &(S, S)) -> Option<Self::To> {
+        self.primary
+            .view_ref(&source.0)
+            .as_ref()
+            .or_else(|| self.fallback.view_ref(&source.1).as_ref())
+            .cloned()
+    }
+}
+
+impl<P, F, S, A> Setter<(S, S)> for Overlay<P, F>
+where
+    P: LensMut<S, To = Option<A>>,
+{
+    type To = A;
+
+    fn set(&self, source: &mut (S, S), value: Self::To) {
+        *self.primary.view_mut(&mut source.0) = Option::Some(value);
+    }
+}
+
+impl<P, F, S, A> Preview<Vec<S>> for Overlay<P, F>
+where
+    P: LensRef<S, To = Option<A>>,
+    F: LensRef<S, To = Option<A>>,
+    A: Clone,
+{
+    type To = A;
+
+    fn preview(&self, source: &Vec<S>) -> Option<Self::To> {
+        let (top, rest) = source.split_first()?;
+        self.primary
+            .view_ref(top)
+            .as_ref()
+            .or_else(|| {
+                rest.iter()
+                    .find_map(|layer| self.fallback.view_ref(layer).as_ref())
+            })
+            .cloned()
+    }
+}
+
+/// writing to an empty stack is a no-op, there's no top layer.
+impl<P, F, S, A> Setter<Vec<S>> for Overlay<P, F>
+where
+    P: LensMut<S, To = Option<A>>,
+{
+    type To = A;
+
+    fn set(&self, source: &mut Vec<S>, value: Self::To) {
+        if let Option::Some(top) = source.first_mut() {
+            *self.primary.view_mut(top) = Option::Some(value);
+        }
+    }
+}
+
+/**
+* A one-way binding reading a focus of `S1` through `get` and writing it to `S2` through `set`,
+* e.g. from the domain model to a view model. The focus is converted with `Into` on the way.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* #[derive(Lens)]
+* struct Account(#[optic] String, #[optic] u64);
+*
+* #[derive(Lens)]
+* struct AccountView(#[optic] String, #[optic] Option<u64>);
+*
+* let balance = bridge(optics!(_1), optics!(_1.Some));
+* let owner = bridge(optics!(_0), optics!(_0));
+*
+* let account = Account("alice".to_string(), 120);
+* let mut view = AccountView(String::new(), Option::Some(0));
+* assert!(balance.sync(&account, &mut view));
+* owner.sync(&account, &mut view);
+* assert_eq!((view.0.as_str(), view.1), ("alice", Option::Some(120)));
+*
+* let mut hidden = AccountView(String::new(), Option::None);
+* balance.sync(&account, &mut hidden); // no `Some` to write to
+* assert_eq!(hidden.1, Option::None);
+*
+* let mut tabs = vec![AccountView(String::new(), Option::None), AccountView(String::new(), Option::None)];
+* owner.sync_all(&account, &mut tabs);
+* assert!(tabs.iter().all(|tab| tab.0 == "alice"));
+* ```
+*/
+pub const fn bridge<S1, S2, G, St>(get: G, set: St) -> Bridged<S1, S2, G, St> {
+    Bridged {
+        get,
+        set,
+        sources: PhantomData,
+    }
+}
+
+pub struct Bridged<S1, S2, G, St> {
+    get: G,
+    set: St,
+    sources: PhantomData<fn(&S1, &mut S2)>,
+}
+
+impl<S1, S2, G, St> Bridged<S1, S2, G, St>
+where
+    G: Preview<S1>,
+    St: Setter<S2>,
+    G::To: Into<St::To>,
+{
+    /// write the focus of `from` to `to`, returns whether `from` had the focus.
+    pub fn sync(&self, from: &S1, to: &mut S2) -> bool {
+        match self.get.preview(from) {
+            Option::Some(focus) => {
+                self.set.set(to, focus.into());
+                true
+            }
+            Option::None => false,
+        }
+    }
+
+    /// write the focus of `from` to every target, e.g. the views open on the same model.
+    pub fn sync_all<'a, I>(&self, from: &S1, targets: I)
+    where
+        S2: 'a,
+        St::To: Clone,
+        I: IntoIterator<Item = &'a mut S2>,
+    {
+        if let Option::Some(focus) = self.get.preview(from) {
+            let focus = focus.into();
+            targets
+                .into_iter()
+                .for_each(|to| self.set.set(to, focus.clone()))
+        }
+    }
+}
+
+impl<S1, S2, G, St> Preview<S1> for Bridged<S1, S2, G, St>
+where
+    G: Preview<S1>,
+{
+    type To = G::To;
+
+    fn preview(&self, source: &S1) -> Option<Self::To> {
+        self.get.preview(source)
+    }
+}
+
+impl<S1, S2, G, St> Getter<S1> for Bridged<S1, S2, G, St>
+where
+    G: Getter<S1>,
+{
+    fn get(&self, source: &S1) -> Self::To {
+        self.get.get(source)
+    }
+}
+
+impl<S1, S2, G, St> Setter<S2> for Bridged<S1, S2, G, St>
+where
+    St: Setter<S2>,
+{
+    type To = St::To;
+
+    fn set(&self, source: &mut S2, value: Self::To) {
+        self.set.set(source, value)
+    }
+}
+
+/**
+* Pair up the foci of two structures in lockstep and apply `f` to each pair,
+* the extra foci of the longer side are left alone.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut dst = (vec![0, 0, 0], "dst");
+* let src = vec![(1, 'a'), (2, 'b')];
+* zip_traverse(optics!(_0._mapped), optics!(_mapped._0), &mut dst, &src, |a, b| *a = *b);
+* assert_eq!(dst.0, vec![1, 2, 0]);
+* ```
+*/
+pub fn zip_traverse<TrA, TrB, S1, S2, F>(
+    optic_a: TrA,
+    optic_b: TrB,
+    target: &mut S1,
+    source: &S2,
+    mut f: F,
+) where
+    TrA: TraversalMut<S1>,
+    TrB: TraversalRef<S2>,
+    F: FnMut(&mut TrA::To, &TrB::To),
+{
+    optic_a
+        .traverse_mut(target)
+        .into_iter()
+        .zip(optic_b.traverse_ref(source))
+        .for_each(|(a, b)| f(a, b))
+}
+
+/**
+* Stably sort every collection reached through the first optic by the key focused by the second one.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![(3, 'c'), (1, 'a'), (2, 'b')]);
+* sort_by_lens(optics!(_1), optics!(_0), &mut x);
+* assert_eq!(x.1, vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+*
+* let mut nested = vec![vec![("b", 2), ("a", 1)], vec![("d", 0), ("c", 9)]];
+* sort_by_lens(optics!(_mapped), optics!(_1), &mut nested);
+* assert_eq!(nested, vec![vec![("a", 1), ("b", 2)], vec![("d", 0), ("c", 9)]]);
+* ```
+*/
+pub fn sort_by_lens<Tr, Ls, S, C, T>(collection: Tr, key: Ls, source: &mut S)
+where
+    Tr: TraversalMut<S, To = C>,
+    C: AsMut<[T]>,
+    Ls: LensRef<T>,
+    Ls::To: Ord,
+{
+    for xs in collection.traverse_mut(source) {
+        xs.as_mut()
+            .sort_by(|a, b| key.view_ref(a).cmp(key.view_ref(b)))
+    }
+}
+
+/// Like `sort_by_lens`, but from the largest key down.
+pub fn sort_by_lens_desc<Tr, Ls, S, C, T>(collection: Tr, key: Ls, source: &mut S)
+where
+    Tr: TraversalMut<S, To = C>,
+    C: AsMut<[T]>,
+    Ls: LensRef<T>,
+    Ls::To: Ord,
+{
+    for xs in collection.traverse_mut(source) {
+        xs.as_mut()
+            .sort_by(|a, b| key.view_ref(b).cmp(key.view_ref(a)))
+    }
+}
+
+/**
+* Apply `f` to the focus of a lens on every element of the collections reached through the first optic,
+* e.g. a column of rows, same as `optics!(rows._mapped.price)` but without collecting every focus first.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut table = ("prices", vec![(1, 10), (2, 20)]);
+* map_over_each(optics!(_1), optics!(_1), &mut table, |price| *price *= 2);
+* assert_eq!(table.1, vec![(1, 20), (2, 40)]);
+* ```
+*/
+pub fn map_over_each<Tr, Ls, S, C, T, F>(collection: Tr, field: Ls, source: &mut S, mut f: F)
+where
+    Tr: TraversalMut<S, To = C>,
+    for<'c> &'c mut C: IntoIterator<Item = &'c mut T>,
+    Ls: LensMut<T>,
+    F: FnMut(&mut Ls::To),
+{
+    for xs in collection.traverse_mut(source) {
+        xs.into_iter().for_each(|x| f(field.view_mut(x)))
+    }
+}
+
+/// The collections that can drop their elements in place, see `retain_of`.
+pub trait Retain {
+    type Item;
+    fn retain_by(&mut self, f: &mut dyn FnMut(&mut Self::Item) -> bool);
+}
+
+impl<T> Retain for Vec<T> {
+    type Item = T;
+
+    fn retain_by(&mut self, f: &mut dyn FnMut(&mut T) -> bool) {
+        self.retain_mut(f)
+    }
+}
+
+impl<T> Retain for VecDeque<T> {
+    type Item = T;
+
+    fn retain_by(&mut self, f: &mut dyn FnMut(&mut T) -> bool) {
+        self.retain_mut(f)
+    }
+}
+
+/// the predicate sees the values, the keys stay untouched.
+impl<K: Eq + Hash, V> Retain for HashMap<K, V> {
+    type Item = V;
+
+    fn retain_by(&mut self, f: &mut dyn FnMut(&mut V) -> bool) {
+        self.retain(|_, v| f(v))
+    }
+}
+
+impl<K: Ord, V> Retain for BTreeMap<K, V> {
+    type Item = V;
+
+    fn retain_by(&mut self, f: &mut dyn FnMut(&mut V) -> bool) {
+        self.retain(|_, v| f(v))
+    }
+}
+
+/// the kept elements are reinserted, since they may have been modified.
+impl<T: Eq + Hash> Retain for HashSet<T> {
+    type Item = T;
+
+    fn retain_by(&mut self, f: &mut dyn FnMut(&mut T) -> bool) {
+        *self = std::mem::take(self)
+            .into_iter()
+            .filter_map(|mut x| f(&mut x).then_some(x))
+            .collect()
+    }
+}
+
+impl<T: Ord> Retain for BTreeSet<T> {
+    type Item = T;
+
+    fn retain_by(&mut self, f: &mut dyn FnMut(&mut T) -> bool) {
+        *self = std::mem::take(self)
+            .into_iter()
+            .filter_map(|mut x| f(&mut x).then_some(x))
+            .collect()
+    }
+}
+
+/**
+* Modify or drop the elements of every collection reached through the optic,
+* the elements for which `pred` returns `false` are removed.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![vec![1, -2, 3], vec![-4, 5]]);
+* retain_of(optics!(_1._mapped), &mut x, |n: &mut i32| {
+*     *n *= 10;
+*     *n > 0
+* });
+* assert_eq!(x.1, vec![vec![10, 30], vec![50]]);
+* ```
+*/
+pub fn retain_of<Tr, T, C, F>(optic: Tr, source: &mut T, mut pred: F)
+where
+    Tr: TraversalMut<T, To = C>,
+    C: Retain,
+    F: FnMut(&mut C::Item) -> bool,
+{
+    for xs in optic.traverse_mut(source) {
+        xs.retain_by(&mut pred)
+    }
+}
+
+/**
+* Keep only the first focus of every key, the key is focused by the second optic on each focus of the first one.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![(1, 'a'), (2, 'b'), (1, 'c')]);
+* let firsts = unique_by(optics!(_1._mapped), optics!(_0));
+* assert_eq!(firsts.traverse_ref(&x), vec![&(1, 'a'), &(2, 'b')]);
+*
+* firsts.traverse_mut(&mut x).into_iter().for_each(|p| p.1 = 'z');
+* assert_eq!(x.1, vec![(1, 'z'), (2, 'z'), (1, 'c')]);
+* ```
+*/
+pub const fn unique_by<Tr, K>(optic: Tr, key: K) -> UniqueBy<Tr, K> {
+    UniqueBy { optic, key }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct UniqueBy<Tr, K> {
+    optic: Tr,
+    key: K,
+}
+
+impl<Tr, K> UniqueBy<Tr, K> {
+    // whether each focus is the first one of its key, in the order of the foci.
+    fn firsts<T>(&self, source: &T) -> Vec<bool>
+    where
+        Tr: TraversalRef<T>,
+        K: LensRef<Tr::To>,
+        K::To: Eq + Hash,
+    {
+        let mut seen = HashSet::new();
+        self.optic
+            .traverse_ref(source)
+            .into_iter()
+            .map(|x| seen.insert(self.key.view_ref(x)))
+            .collect()
+    }
+}
+
+impl<Tr, K, T> TraversalRef<T> for UniqueBy<Tr, K>
+where
+    Tr: TraversalRef<T>,
+    K: LensRef<Tr::To>,
+    K::To: Eq + Hash,
+{
+    type To = Tr::To;
+
+    fn traverse_ref<'a>(&self, source: &'a T) -> Vec<&'a Self::To> {
+        self.optic
+            .traverse_ref(source)
+            .into_iter()
+            .zip(self.firsts(source))
+            .filter_map(|(x, first)| first.then_some(x))
+            .collect()
+    }
+}
+
+impl<Tr, K, T> TraversalMut<T> for UniqueBy<Tr, K>
+where
+    Tr: TraversalMut<T>,
+    K: LensRef<Tr::To>,
+    K::To: Eq + Hash,
+{
+    fn traverse_mut<'a>(&self, source: &'a mut T) -> Vec<&'a mut Self::To> {
+        let firsts = self.firsts(source);
+        self.optic
+            .traverse_mut(source)
+            .into_iter()
+            .zip(firsts)
+            .filter_map(|(x, first)| first.then_some(x))
+            .collect()
+    }
+}
+
+impl<Tr, K, T> Traversal<T> for UniqueBy<Tr, K>
+where
+    Tr: Traversal<T>,
+    K: LensRef<Tr::To>,
+    K::To: Eq + Hash,
+{
+    fn traverse(&self, source: T) -> Vec<Self::To> {
+        let firsts = self.firsts(&source);
+        self.optic
+            .traverse(source)
+            .into_iter()
+            .zip(firsts)
+            .filter_map(|(x, first)| first.then_some(x))
+            .collect()
+    }
+}
+
+/**
+* Focus on the `n`th focus of a traversal, counting from 0.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![vec![1, 2], vec![3]]);
+* let third = nth_of(optics!(_1._mapped._mapped), 2);
+* assert_eq!(third.pm_ref(&x), Option::Some(&3));
+* *third.pm_mut(&mut x).unwrap() = 30;
+* assert_eq!(x.1, vec![vec![1, 2], vec![30]]);
+* assert_eq!(nth_of(optics!(_1._mapped._mapped), 3).pm_ref(&x), Option::None);
+* ```
+*/
+pub const fn nth_of<Tr>(optic: Tr, n: usize) -> Nth<Tr> {
+    Nth { optic, n }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Nth<Tr> {
+    optic: Tr,
+    n: usize,
+}
+
+impl<Tr, T> TraversalRef<T> for Nth<Tr>
+where
+    Tr: TraversalRef<T>,
+{
+    type To = Tr::To;
+
+    fn traverse_ref<'a>(&self, source: &'a T) -> Vec<&'a Self::To> {
+        self.pm_ref(source).into_iter().collect()
+    }
+}
+
+impl<Tr, T> TraversalMut<T> for Nth<Tr>
+where
+    Tr: TraversalMut<T>,
+{
+    fn traverse_mut<'a>(&self, source: &'a mut T) -> Vec<&'a mut Self::To> {
+        self.pm_mut(source).into_iter().collect()
+    }
+}
+
+impl<Tr, T> Traversal<T> for Nth<Tr>
+where
+    Tr: Traversal<T>,
+{
+    fn traverse(&self, source: T) -> Vec<Self::To> {
+        self.pm(source).into_iter().collect()
+    }
+}
+
+impl<Tr, T> PrismRef<T> for Nth<Tr>
+where
+    Tr: TraversalRef<T>,
+{
+    fn pm_ref<'a>(&self, source: &'a T) -> Option<&'a Self::To> {
+        let mut skipped = 0;
+        let mut found = Option::None;
+        self.optic.visit_ref(source, &mut |focus| {
+            if skipped == self.n {
+                found = Option::Some(focus);
+                return false;
+            }
+            skipped += 1;
+            true
+        });
+        found
+    }
+}
+
+impl<Tr, T> PrismMut<T> for Nth<Tr>
+where
+    Tr: TraversalMut<T>,
+{
+    fn pm_mut<'a>(&self, source: &'a mut T) -> Option<&'a mut Self::To> {
+        self.optic.traverse_mut(source).into_iter().nth(self.n)
+    }
+}
+
+impl<Tr, T> Prism<T> for Nth<Tr>
+where
+    Tr: Traversal<T>,
+{
+    fn pm(&self, source: T) -> Option<Self::To> {
+        self.optic.traverse(source).into_iter().nth(self.n)
+    }
+}
+
+/**
+* Focus on the `page`th run of `size` foci of a traversal, counting pages from 0, e.g. for a background migration
+* to rewrite a bounded batch at a time.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![1, 2, 3, 4, 5]);
+* assert_eq!(page_of(optics!(_1._mapped), 1, 2).traverse_ref(&x), vec![&3, &4]);
+* assert_eq!(page_of(optics!(_1._mapped), 2, 2).traverse_ref(&x), vec![&5]);
+*
+* page_of(optics!(_1._mapped), 0, 2).traverse_mut(&mut x).into_iter().for_each(|n| *n = 0);
+* assert_eq!(x.1, vec![0, 0, 3, 4, 5]);
+* ```
+*/
+pub const fn page_of<Tr>(optic: Tr, page: usize, size: usize) -> Page<Tr> {
+    Page { optic, page, size }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Page<Tr> {
+    optic: Tr,
+    page: usize,
+    size: usize,
+}
+
+impl<Tr> Page<Tr> {
+    fn window<A>(&self, foci: Vec<A>) -> Vec<A> {
+        foci.into_iter()
+            .skip(self.page.saturating_mul(self.size))
+            .take(self.size)
+            .collect()
+    }
+}
+
+impl<Tr, T> TraversalRef<T> for Page<Tr>
+where
+    Tr: TraversalRef<T>,
+{
+    type To = Tr::To;
+
+    fn traverse_ref<'a>(&self, source: &'a T) -> Vec<&'a Self::To> {
+        self.window(self.optic.traverse_ref(source))
+    }
+}
+
+impl<Tr, T> TraversalMut<T> for Page<Tr>
+where
+    Tr: TraversalMut<T>,
+{
+    fn traverse_mut<'a>(&self, source: &'a mut T) -> Vec<&'a mut Self::To> {
+        self.window(self.optic.traverse_mut(source))
+    }
+}
+
+impl<Tr, T> Traversal<T> for Page<Tr>
+where
+    Tr: Traversal<T>,
+{
+    fn traverse(&self, source: T) -> Vec<Self::To> {
+        self.window(self.optic.traverse(source))
+    }
+}
+
+/**
+* Focus on `k` foci of a traversal picked at random, in traversal order,
+* `rng` gives the random numbers so any generator can be plugged in.
+* Each traversal draws a new sample.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut seed = 7u64;
+* let xorshift = move || {
+*     seed ^= seed << 13;
+*     seed ^= seed >> 7;
+*     seed ^= seed << 17;
+*     seed
+* };
+* let x = (0, (1..=100).collect::<Vec<_>>());
+* let sample = sample_of(optics!(_1._mapped), xorshift, 5).traverse_ref(&x);
+* assert_eq!(sample.len(), 5);
+* assert!(sample.windows(2).all(|w| w[0] < w[1]));
+* ```
+*/
+pub fn sample_of<Tr, R>(optic: Tr, rng: R, k: usize) -> Sample<Tr, R>
+where
+    R: FnMut() -> u64,
+{
+    Sample {
+        optic,
+        rng: RefCell::new(rng),
+        k,
+    }
+}
+
+pub struct Sample<Tr, R> {
+    optic: Tr,
+    rng: RefCell<R>,
+    k: usize,
+}
+
+impl<Tr, R: FnMut() -> u64> Sample<Tr, R> {
+    // reservoir sampling of the indices, so every focus is as likely to be picked.
+    fn pick<A>(&self, foci: Vec<A>) -> Vec<A> {
+        let mut rng = self.rng.borrow_mut();
+        let mut picked = (0..self.k.min(foci.len())).collect::<Vec<_>>();
+        for i in self.k..foci.len() {
+            let j = (rng() % (i as u64 + 1)) as usize;
+            if j < self.k {
+                picked[j] = i;
+            }
+        }
+        let picked = picked.into_iter().collect::<HashSet<_>>();
+        foci.into_iter()
+            .enumerate()
+            .filter_map(|(i, focus)| picked.contains(&i).then_some(focus))
+            .collect()
+    }
+}
+
+impl<Tr, R, T> TraversalRef<T> for Sample<Tr, R>
+where
+    Tr: TraversalRef<T>,
+    R: FnMut() -> u64,
+{
+    type To = Tr::To;
+
+    fn traverse_ref<'a>(&self, source: &'a T) -> Vec<&'a Self::To> {
+        self.pick(self.optic.traverse_ref(source))
+    }
+}
+
+impl<Tr, R, T> TraversalMut<T> for Sample<Tr, R>
+where
+    Tr: TraversalMut<T>,
+    R: FnMut() -> u64,
+{
+    fn traverse_mut<'a>(&self, source: &'a mut T) -> Vec<&'a mut Self::To> {
+        self.pick(self.optic.traverse_mut(source))
+    }
+}
+
+impl<Tr, R, T> Traversal<T> for Sample<Tr, R>
+where
+    Tr: Traversal<T>,
+    R: FnMut() -> u64,
+{
+    fn traverse(&self, source: T) -> Vec<Self::To> {
+        self.pick(self.optic.traverse(source))
+    }
+}
+
+/**
+* Remove the later elements of the same key from every collection reached through the first optic,
+* the key is focused by the second optic on each element.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![vec![(1, 'a'), (2, 'b'), (1, 'c')], vec![(1, 'd')]]);
+* dedup_of(optics!(_1._mapped), optics!(_0), &mut x);
+* assert_eq!(x.1, vec![vec![(1, 'a'), (2, 'b')], vec![(1, 'd')]]);
+* ```
+*/
+pub fn dedup_of<Tr, Ls, S, C>(collection: Tr, key: Ls, source: &mut S)
+where
+    Tr: TraversalMut<S, To = C>,
+    C: Retain,
+    Ls: LensRef<C::Item>,
+    Ls::To: Eq + Hash + Clone,
+{
+    for xs in collection.traverse_mut(source) {
+        let mut seen = HashSet::new();
+        xs.retain_by(&mut |x| seen.insert(key.view_ref(x).clone()))
+    }
+}
+
+/**
+* Narrow an optic to the foci satisfying `pred`, e.g. a lens into an affine traversal
+* which only updates the state of a workflow while it's in a given state.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* #[derive(Copy, Clone, Debug, PartialEq)]
+* enum State {
+*     Open,
+*     Closed,
+* }
+*
+* let open = guarded(optics!(_1), |s: &State| *s == State::Open);
+* let mut conn = ("db", State::Closed);
+* open.set(&mut conn, State::Closed);
+* assert!(open.pm_ref(&conn).is_none());
+*
+* conn.1 = State::Open;
+* assert_eq!(open.preview(&conn), Option::Some(State::Open));
+* open.set(&mut conn, State::Closed);
+* assert_eq!(conn.1, State::Closed);
+* ```
+*/
+pub const fn guarded<Optic, P>(optic: Optic, pred: P) -> Guarded<Optic, P> {
+    Guarded { optic, pred }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Guarded<Optic, P> {
+    optic: Optic,
+    pred: P,
+}
+
+impl<Pm, P, T> TraversalRef<T> for Guarded<Pm, P>
+where
+    Pm: PrismRef<T>,
+    P: Fn(&Pm::To) -> bool,
+{
+    type To = Pm::To;
+
+    fn traverse_ref<'a>(&self, source: &'a T) -> Vec<&'a Self::To> {
+        self.pm_ref(source).into_iter().collect()
+    }
+}
+
+impl<Pm, P, T> TraversalMut<T> for Guarded<Pm, P>
+where
+    Pm: PrismMut<T>,
+    P: Fn(&Pm::To) -> bool,
+{
+    fn traverse_mut<'a>(&self, source: &'a mut T) -> Vec<&'a mut Self::To> {
+        self.pm_mut(source).into_iter().collect()
+    }
+}
+
+impl<Pm, P, T> Traversal<T> for Guarded<Pm, P>
+where
+    Pm: Prism<T>,
+    P: Fn(&Pm::To) -> bool,
+{
+    fn traverse(&self, source: T) -> Vec<Self::To> {
+        self.pm(source).into_iter().collect()
+    }
+}
+
+impl<Pm, P, T> PrismRef<T> for Guarded<Pm, P>
+where
+    Pm: PrismRef<T>,
+    P: Fn(&Pm::To) -> bool,
+{
+    fn pm_ref<'a>(&self, source: &'a T) -> Option<&'a Self::To> {
+        self.optic.pm_ref(source).filter(|x| (self.pred)(x))
+    }
+}
+
+impl<Pm, P, T> PrismMut<T> for Guarded<Pm, P>
+where
+    Pm: PrismMut<T>,
+    P: Fn(&Pm::To) -> bool,
+{
+    fn pm_mut<'a>(&self, source: &'a mut T) -> Option<&'a mut Self::To> {
+        self.optic.pm_mut(source).filter(|x| (self.pred)(x))
+    }
+}
+
+impl<Pm, P, T> Prism<T> for Guarded<Pm, P>
+where
+    Pm: Prism<T>,
+    P: Fn(&Pm::To) -> bool,
+{
+    fn pm(&self, source: T) -> Option<Self::To> {
+        self.optic.pm(source).filter(|x| (self.pred)(x))
+    }
+}
+
+impl<Pm, P, T> Preview<T> for Guarded<Pm, P>
+where
+    Pm: PrismRef<T>,
+    Pm::To: Clone,
+    P: Fn(&Pm::To) -> bool,
+{
+    type To = Pm::To;
+
+    fn preview(&self, source: &T) -> Option<Self::To> {
+        self.pm_ref(source).cloned()
+    }
+}
+
+/// setting is a no-op when the focus doesn't satisfy the predicate.
+impl<Pm, P, T> Setter<T> for Guarded<Pm, P>
+where
+    Pm: PrismMut<T>,
+    P: Fn(&Pm::To) -> bool,
+{
+    type To = Pm::To;
+
+    fn set(&self, source: &mut T, value: Self::To) {
+        if let Option::Some(x) = self.pm_mut(source) {
+            *x = value
+        }
+    }
+}
+
+impl<Pm, P, T> Over<T> for Guarded<Pm, P>
+where
+    Pm: PrismMut<T>,
+    P: Fn(&Pm::To) -> bool,
+{
+    fn over(&self, source: &mut T, f: &mut dyn FnMut(&mut Self::To)) {
+        self.pm_mut(source).into_iter().for_each(f)
+    }
+}
+
+/**
+* Concatenate two traversals over the same source, the foci of `first` then the foci of `second`,
+* e.g. to update the tags of both the header and the body at once.
+* Both sets of foci can't be borrowed mutably at the same time,
+* so it's written through `set` and `over`, which visit `first` then `second`.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut doc = ((vec!["draft"], 1), (vec!["a", "b"], 2));
+*
+* let tags = optics!(_0._0._mapped).and_also(optics!(_1._0._mapped));
+* assert_eq!(tags.traverse_ref(&doc), vec![&"draft", &"a", &"b"]);
+*
+* optics!(_0._0).and_also(optics!(_1._0)).over(&mut doc, &mut |tags| tags.push("new"));
+* assert_eq!(length_of(tags, &doc), 5);
+* tags.set(&mut doc, "x");
+* assert_eq!(doc, ((vec!["x", "x"], 1), (vec!["x", "x", "x"], 2)));
+* ```
+*/
+pub const fn and_also<A, B>(first: A, second: B) -> AndAlso<A, B> {
+    AndAlso { first, second }
+}
+
+/// `optic.and_also(other)`, see `and_also`.
+pub trait Concat: Sized {
+    fn and_also<B>(self, second: B) -> AndAlso<Self, B> {
+        and_also(self, second)
+    }
+}
+
+impl<A> Concat for A {}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AndAlso<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B, T> TraversalRef<T> for AndAlso<A, B>
+where
+    A: TraversalRef<T>,
+    B: TraversalRef<T, To = A::To>,
+{
+    type To = A::To;
+
+    fn traverse_ref<'a>(&self, source: &'a T) -> Vec<&'a Self::To> {
+        let mut foci = self.first.traverse_ref(source);
+        foci.extend(self.second.traverse_ref(source));
+        foci
+    }
+
+    fn visit_ref<'a>(&self, source: &'a T, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+        self.first.visit_ref(source, f) && self.second.visit_ref(source, f)
+    }
+}
+
+/// every focus is set to a clone of the value.
+impl<A, B, T> Setter<T> for AndAlso<A, B>
+where
+    A: TraversalMut<T>,
+    B: TraversalMut<T, To = A::To>,
+    A::To: Clone,
+{
+    type To = A::To;
+
+    fn set(&self, source: &mut T, value: Self::To) {
+        self.over(source, &mut |x| *x = value.clone())
+    }
+}
+
+impl<A, B, T> Over<T> for AndAlso<A, B>
+where
+    A: TraversalMut<T>,
+    B: TraversalMut<T, To = A::To>,
+    Self: Setter<T, To = A::To>,
+{
+    fn over(&self, source: &mut T, f: &mut dyn FnMut(&mut Self::To)) {
+        self.first
+            .traverse_mut(source)
+            .into_iter()
+            .for_each(&mut *f);
+        self.second.traverse_mut(source).into_iter().for_each(f)
+    }
+}
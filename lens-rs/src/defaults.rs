@@ -0,0 +1,85 @@
+use crate::*;
+
+type Fill<S> = Box<dyn Fn(&mut S)>;
+
+/**
+* A table of the default values of some foci, applied to a value after it's loaded or constructed.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let defaults = Defaults::new()
+*     .fill(optics!(_0), "localhost".to_string())
+*     .fill_with(optics!(_1._mapped), || 8080)
+*     .fill_empty(optics!(_2), vec!["info".to_string()]);
+*
+* let mut config: (Option<String>, Vec<Option<u16>>, Vec<String>) =
+*     (Option::None, vec![Option::Some(80), Option::None], vec![]);
+* defaults.apply_defaults(&mut config);
+* assert_eq!(config.0.as_deref(), Option::Some("localhost"));
+* assert_eq!(config.1, vec![Option::Some(80), Option::Some(8080)]);
+* assert_eq!(config.2, vec!["info".to_string()]);
+* ```
+*/
+pub struct Defaults<S> {
+    fills: Vec<Fill<S>>,
+}
+
+impl<S> Default for Defaults<S> {
+    fn default() -> Self {
+        Self { fills: vec![] }
+    }
+}
+
+impl<S> Defaults<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// fill every focused `None` with `value`.
+    pub fn fill<Tr, A>(self, optic: Tr, value: A) -> Self
+    where
+        Tr: TraversalMut<S, To = Option<A>> + 'static,
+        A: Clone + 'static,
+    {
+        self.fill_with(optic, move || value.clone())
+    }
+
+    /// fill every focused `None` with a value made by `factory`.
+    pub fn fill_with<Tr, A, F>(mut self, optic: Tr, factory: F) -> Self
+    where
+        Tr: TraversalMut<S, To = Option<A>> + 'static,
+        F: Fn() -> A + 'static,
+    {
+        self.fills.push(Box::new(move |source| {
+            optic
+                .traverse_mut(source)
+                .into_iter()
+                .filter(|x| x.is_none())
+                .for_each(|x| *x = Option::Some(factory()))
+        }));
+        self
+    }
+
+    /// replace every focus equal to its `Default`, e.g. an empty string or collection, with `value`.
+    pub fn fill_empty<Tr>(mut self, optic: Tr, value: Tr::To) -> Self
+    where
+        Tr: TraversalMut<S> + 'static,
+        Tr::To: Default + PartialEq + Clone + 'static,
+    {
+        self.fills.push(Box::new(move |source| {
+            let empty = Tr::To::default();
+            optic
+                .traverse_mut(source)
+                .into_iter()
+                .filter(|x| **x == empty)
+                .for_each(|x| *x = value.clone())
+        }));
+        self
+    }
+
+    /// fill the foci in the order the defaults were added.
+    pub fn apply_defaults(&self, source: &mut S) {
+        self.fills.iter().for_each(|fill| fill(source));
+    }
+}
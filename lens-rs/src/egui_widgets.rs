@@ -0,0 +1,95 @@
+/*!
+* Bind `egui` widgets to the foci of optics, so a form edits the fields of the state in place:
+* a lens focuses the field of a widget, a traversal the rows of a list.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::egui_widgets::LensUi;
+*
+* let mut profile = ("ann".to_string(), (true, vec![1.5f32, 2.0]));
+* let ctx = ::egui::Context::default();
+* let _ = ctx.run(Default::default(), |ctx| {
+*     ::egui::CentralPanel::default().show(ctx, |ui| {
+*         ui.text_edit_lens(&mut profile, optics!(_0));
+*         ui.checkbox_lens(&mut profile, optics!(_1._0), "active");
+*         let weights = ui.list_lens(&mut profile, optics!(_1._1._mapped), |ui, weight| {
+*             ui.drag_value_lens(weight, __)
+*         });
+*         assert_eq!(weights.len(), 2);
+*     });
+* });
+* ```
+*/
+use crate::*;
+use ::egui::emath::Numeric;
+use ::egui::{DragValue, Response, Ui, WidgetText};
+
+pub trait LensUi {
+    /// a single line text edit of the focus.
+    fn text_edit_lens<S, Ls>(&mut self, source: &mut S, optic: Ls) -> Response
+    where
+        Ls: LensMut<S, To = String>;
+
+    fn checkbox_lens<S, Ls>(
+        &mut self,
+        source: &mut S,
+        optic: Ls,
+        text: impl Into<WidgetText>,
+    ) -> Response
+    where
+        Ls: LensMut<S, To = bool>;
+
+    /// a number dragged or typed in.
+    fn drag_value_lens<S, Ls>(&mut self, source: &mut S, optic: Ls) -> Response
+    where
+        Ls: LensMut<S>,
+        Ls::To: Numeric;
+
+    /// the rows of the foci in traversal order, each under its own id so their widgets keep their state.
+    fn list_lens<S, Tr, R, F>(&mut self, source: &mut S, optic: Tr, add_row: F) -> Vec<R>
+    where
+        Tr: TraversalMut<S>,
+        F: FnMut(&mut Ui, &mut Tr::To) -> R;
+}
+
+impl LensUi for Ui {
+    fn text_edit_lens<S, Ls>(&mut self, source: &mut S, optic: Ls) -> Response
+    where
+        Ls: LensMut<S, To = String>,
+    {
+        self.text_edit_singleline(optic.view_mut(source))
+    }
+
+    fn checkbox_lens<S, Ls>(
+        &mut self,
+        source: &mut S,
+        optic: Ls,
+        text: impl Into<WidgetText>,
+    ) -> Response
+    where
+        Ls: LensMut<S, To = bool>,
+    {
+        self.checkbox(optic.view_mut(source), text)
+    }
+
+    fn drag_value_lens<S, Ls>(&mut self, source: &mut S, optic: Ls) -> Response
+    where
+        Ls: LensMut<S>,
+        Ls::To: Numeric,
+    {
+        self.add(DragValue::new(optic.view_mut(source)))
+    }
+
+    fn list_lens<S, Tr, R, F>(&mut self, source: &mut S, optic: Tr, mut add_row: F) -> Vec<R>
+    where
+        Tr: TraversalMut<S>,
+        F: FnMut(&mut Ui, &mut Tr::To) -> R,
+    {
+        optic
+            .traverse_mut(source)
+            .into_iter()
+            .enumerate()
+            .map(|(i, focus)| self.push_id(i, |ui| add_row(ui, focus)).inner)
+            .collect()
+    }
+}
@@ -0,0 +1,78 @@
+/*!
+* A lens from a `bitflags!` type to whether one of its flags is set.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::flags::flag;
+* use ::bitflags::bitflags;
+*
+* bitflags! {
+*     #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+*     struct Perms: u8 {
+*         const READ = 0b01;
+*         const WRITE = 0b10;
+*     }
+* }
+*
+* let mut user = ("alice", Perms::READ);
+* let can_write = optics::_1(flag(Perms::WRITE));
+* assert!(!can_write.get(&user));
+*
+* can_write.set(&mut user, true);
+* assert_eq!(user.1, Perms::READ | Perms::WRITE);
+* optics::_1(flag(Perms::READ)).over(&mut user, &mut |read| *read = !*read);
+* assert_eq!(user.1, Perms::WRITE);
+* ```
+*/
+use crate::*;
+use ::bitflags::Flags;
+
+/// whether all the flags of `flags` are set, see `flag`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Flag<B>(B);
+
+/// focus on whether all the flags of `flags` are set, setting it inserts or removes them.
+pub const fn flag<B: Flags>(flags: B) -> Flag<B> {
+    Flag(flags)
+}
+
+impl<B: Flags> Flag<B> {
+    // the flags needn't be `Clone`, their bits always are.
+    fn flags(&self) -> B {
+        B::from_bits_retain(self.0.bits())
+    }
+}
+
+impl<B: Flags> Preview<B> for Flag<B> {
+    type To = bool;
+
+    #[inline]
+    fn preview(&self, source: &B) -> Option<Self::To> {
+        Option::Some(self.get(source))
+    }
+}
+
+impl<B: Flags> Getter<B> for Flag<B> {
+    #[inline]
+    fn get(&self, source: &B) -> Self::To {
+        source.contains(self.flags())
+    }
+}
+
+impl<B: Flags> Setter<B> for Flag<B> {
+    type To = bool;
+
+    #[inline]
+    fn set(&self, source: &mut B, value: Self::To) {
+        source.set(self.flags(), value)
+    }
+}
+
+impl<B: Flags> Over<B> for Flag<B> {
+    #[inline]
+    fn over(&self, source: &mut B, f: &mut dyn FnMut(&mut Self::To)) {
+        let mut value = self.get(source);
+        f(&mut value);
+        self.set(source, value)
+    }
+}
@@ -0,0 +1,500 @@
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::rc::Rc;
+
+/**
+* Fold the foci in order into an accumulator, without collecting them.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x = (0, vec![Some(3), None, Some(7), Some(5)]);
+* let (sum, max) = fold_of(optics!(_1._mapped.Some), &x, (0, i32::MIN), |(sum, max), i| {
+*     (sum + i, max.max(*i))
+* });
+* assert_eq!((sum, max), (15, 7));
+* ```
+*/
+pub fn fold_of<Tr, T, B, F>(optic: Tr, source: &T, init: B, mut f: F) -> B
+where
+    Tr: TraversalRef<T>,
+    F: FnMut(B, &Tr::To) -> B,
+{
+    // the accumulator is moved through `f`, it's only missing while `f` runs
+    let mut acc = Option::Some(init);
+    optic.visit_ref(source, &mut |focus| {
+        acc = acc.take().map(|acc| f(acc, focus));
+        true
+    });
+    acc.unwrap_or_else(|| unreachable!())
+}
+
+/**
+* Fold the foci in order into an accumulator, `f` may adjust each focus as it goes.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut x = (0, vec![1, 2, 3]);
+* let total = fold_mut_of(optics!(_1._mapped), &mut x, 0, |total, i| {
+*     *i += total;
+*     *i
+* });
+* assert_eq!(total, 6);
+* assert_eq!(x.1, vec![1, 3, 6]);
+* ```
+*/
+pub fn fold_mut_of<Tr, T, B, F>(optic: Tr, source: &mut T, init: B, f: F) -> B
+where
+    Tr: TraversalMut<T>,
+    F: FnMut(B, &mut Tr::To) -> B,
+{
+    optic.traverse_mut(source).into_iter().fold(init, f)
+}
+
+/**
+* Find the first focus satisfying the predicate, without visiting the rest.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x = (0, vec![Some(1), None, Some(4), Some(6)]);
+* assert_eq!(find_of(optics!(_1._mapped.Some), &x, |i| i % 2 == 0), Option::Some(&4));
+* ```
+*/
+pub fn find_of<Tr, T, P>(optic: Tr, source: &T, mut pred: P) -> Option<&Tr::To>
+where
+    Tr: TraversalRef<T>,
+    P: FnMut(&Tr::To) -> bool,
+{
+    let mut found = Option::None;
+    optic.visit_ref(source, &mut |focus| {
+        if pred(focus) {
+            found = Option::Some(focus);
+            false
+        } else {
+            true
+        }
+    });
+    found
+}
+
+/**
+* The index of the first focus satisfying the predicate, without visiting the rest.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x = (0, vec![Some(1), None, Some(4), Some(6)]);
+* assert_eq!(position_of(optics!(_1._mapped.Some), &x, |i| i % 2 == 0), Option::Some(1));
+* ```
+*/
+pub fn position_of<Tr, T, P>(optic: Tr, source: &T, mut pred: P) -> Option<usize>
+where
+    Tr: TraversalRef<T>,
+    P: FnMut(&Tr::To) -> bool,
+{
+    let mut index = 0;
+    let mut found = Option::None;
+    optic.visit_ref(source, &mut |focus| {
+        if pred(focus) {
+            found = Option::Some(index);
+            false
+        } else {
+            index += 1;
+            true
+        }
+    });
+    found
+}
+
+/**
+* Count the foci without collecting them.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x = (0, vec![Some(1), None, Some(4)]);
+* assert_eq!(length_of(optics!(_1._mapped.Some), &x), 2);
+* assert!(!is_empty_of(optics!(_1._mapped.Some), &x));
+* assert!(is_empty_of(optics!(_1._mapped.Some), &(0, vec![Option::<i32>::None])));
+* ```
+*/
+pub fn length_of<Tr, T>(optic: Tr, source: &T) -> usize
+where
+    Tr: TraversalRef<T>,
+{
+    let mut length = 0;
+    optic.visit_ref(source, &mut |_| {
+        length += 1;
+        true
+    });
+    length
+}
+
+/// whether the optic has no focus, stops at the first one.
+pub fn is_empty_of<Tr, T>(optic: Tr, source: &T) -> bool
+where
+    Tr: TraversalRef<T>,
+{
+    optic.visit_ref(source, &mut |_| false)
+}
+
+/**
+* Split the foci into those satisfying the predicate and the rest, in a single pass.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x = (0, vec![Some(1), None, Some(4), Some(6)]);
+* let (even, odd) = partition_of(optics!(_1._mapped.Some), &x, |i| i % 2 == 0);
+* assert_eq!((even, odd), (vec![&4, &6], vec![&1]));
+* ```
+*/
+pub fn partition_of<Tr, T, P>(optic: Tr, source: &T, mut pred: P) -> (Vec<&Tr::To>, Vec<&Tr::To>)
+where
+    Tr: TraversalRef<T>,
+    P: FnMut(&Tr::To) -> bool,
+{
+    let mut matched = vec![];
+    let mut rest = vec![];
+    optic.visit_ref(source, &mut |focus| {
+        if pred(focus) {
+            matched.push(focus);
+        } else {
+            rest.push(focus);
+        }
+        true
+    });
+    (matched, rest)
+}
+
+/// like `partition_of`, but takes the foci out of the source.
+pub fn partition_owned_of<Tr, T, P>(optic: Tr, source: T, pred: P) -> (Vec<Tr::To>, Vec<Tr::To>)
+where
+    Tr: Traversal<T>,
+    P: FnMut(&Tr::To) -> bool,
+{
+    optic.traverse(source).into_iter().partition(pred)
+}
+
+/**
+* Group the foci by a key, e.g. all line items across all orders by SKU.
+* ## Example
+* ```
+* use lens_rs::*;
+* let orders = vec![vec![("apple", 1), ("pear", 2)], vec![("apple", 3)]];
+* let by_sku = group_by_of(optics!(_mapped._mapped), &orders, |item| item.0);
+* assert_eq!(by_sku["apple"], vec![&("apple", 1), &("apple", 3)]);
+* assert_eq!(by_sku["pear"], vec![&("pear", 2)]);
+* ```
+*/
+pub fn group_by_of<Tr, T, K, F>(optic: Tr, source: &T, mut key: F) -> HashMap<K, Vec<&Tr::To>>
+where
+    Tr: TraversalRef<T>,
+    K: Eq + Hash,
+    F: FnMut(&Tr::To) -> K,
+{
+    let mut groups: HashMap<K, Vec<_>> = HashMap::new();
+    optic.visit_ref(source, &mut |focus| {
+        groups.entry(key(focus)).or_default().push(focus);
+        true
+    });
+    groups
+}
+
+/**
+* Pair every focus of the first optic with every focus of the second one, both over the same source,
+* e.g. every listener with every queued event.
+* ## Example
+* ```
+* use lens_rs::*;
+* let bus = (vec!["log", "audit"], vec![1, 2]);
+* assert_eq!(
+*     product_of(optics!(_0._mapped), optics!(_1._mapped), &bus),
+*     vec![(&"log", &1), (&"log", &2), (&"audit", &1), (&"audit", &2)]
+* );
+* ```
+*/
+pub fn product_of<TrA, TrB, T>(left: TrA, right: TrB, source: &T) -> Vec<(&TrA::To, &TrB::To)>
+where
+    TrA: TraversalRef<T>,
+    TrB: TraversalRef<T>,
+{
+    let rights = right.traverse_ref(source);
+    left.traverse_ref(source)
+        .into_iter()
+        .flat_map(|a| rights.iter().map(move |&b| (a, b)))
+        .collect()
+}
+
+/**
+* Split every sequence reached through the optic into consecutive chunks of `n` elements,
+* the last chunk of a sequence may be shorter.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::num::NonZeroUsize;
+*
+* let x = (0, vec![1, 2, 3, 4, 5]);
+* let two = NonZeroUsize::new(2).unwrap();
+* assert_eq!(chunks_of(optics!(_1), &x, two), vec![&[1, 2][..], &[3, 4], &[5]]);
+* ```
+*/
+pub fn chunks_of<'a, Tr, T, A>(optic: Tr, source: &'a T, n: NonZeroUsize) -> Vec<&'a [A]>
+where
+    Tr: TraversalRef<T>,
+    Tr::To: AsRef<[A]> + 'a,
+{
+    optic
+        .traverse_ref(source)
+        .into_iter()
+        .flat_map(|xs| xs.as_ref().chunks(n.get()))
+        .collect()
+}
+
+/**
+* Like `chunks_of`, but the chunks can be modified.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::num::NonZeroUsize;
+*
+* let mut x = (0, vec![3, 1, 4, 2]);
+* let two = NonZeroUsize::new(2).unwrap();
+* chunks_mut_of(optics!(_1), &mut x, two).into_iter().for_each(|pair| pair.sort());
+* assert_eq!(x.1, vec![1, 3, 2, 4]);
+* ```
+*/
+pub fn chunks_mut_of<'a, Tr, T, A>(
+    optic: Tr,
+    source: &'a mut T,
+    n: NonZeroUsize,
+) -> Vec<&'a mut [A]>
+where
+    Tr: TraversalMut<T>,
+    Tr::To: AsMut<[A]> + 'a,
+{
+    optic
+        .traverse_mut(source)
+        .into_iter()
+        .flat_map(|xs| xs.as_mut().chunks_mut(n.get()))
+        .collect()
+}
+
+/**
+* The overlapping windows of `n` consecutive elements of every sequence reached through the optic.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::num::NonZeroUsize;
+*
+* let x = (0, vec![1, 4, 9, 16]);
+* let two = NonZeroUsize::new(2).unwrap();
+* let diffs: Vec<_> = windows_of(optics!(_1), &x, two).iter().map(|w| w[1] - w[0]).collect();
+* assert_eq!(diffs, vec![3, 5, 7]);
+* ```
+*/
+pub fn windows_of<'a, Tr, T, A>(optic: Tr, source: &'a T, n: NonZeroUsize) -> Vec<&'a [A]>
+where
+    Tr: TraversalRef<T>,
+    Tr::To: AsRef<[A]> + 'a,
+{
+    optic
+        .traverse_ref(source)
+        .into_iter()
+        .flat_map(|xs| xs.as_ref().windows(n.get()))
+        .collect()
+}
+
+/**
+* Preview every focus of the traversal through a second optic, skipping the ones without a focus,
+* e.g. the observers still alive in a list of `Weak`s.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::rc::Rc;
+*
+* let alive = Rc::new("alive");
+* let observers = (0, vec![Rc::downgrade(&alive), Rc::downgrade(&Rc::new("dropped"))]);
+* let upgraded = previews_of(optics!(_1._mapped), &observers, optics!(_upgraded));
+* assert_eq!(upgraded, vec![alive]);
+* ```
+*/
+pub fn previews_of<Tr, T, Pv>(optic: Tr, source: &T, preview: Pv) -> Vec<Pv::To>
+where
+    Tr: TraversalRef<T>,
+    Pv: Preview<Tr::To>,
+{
+    let mut foci = vec![];
+    optic.visit_ref(source, &mut |focus| {
+        foci.extend(preview.preview(focus));
+        true
+    });
+    foci
+}
+
+/// what `reachable_of` does on a link back to a node on the path to it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum OnCycle {
+    Skip,
+    Fail,
+}
+
+/// a link closing a cycle at `node`, see `reachable_of`.
+#[derive(Clone, Debug)]
+pub struct CycleError<T> {
+    pub node: Rc<T>,
+}
+
+impl<T> fmt::Display for CycleError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a cycle closes at the node {:p}", Rc::as_ptr(&self.node))
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for CycleError<T> {}
+
+/**
+* The nodes of an `Rc`-linked structure reachable from `root`, depth first and each once by pointer identity,
+* the links are the foci of the traversal previewed as an `Rc`, e.g. through `_upgraded` for the `Weak`s.
+* A graph with cycles is walked without looping, a link back to a node on the path is skipped or fails.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::rc::{Rc, Weak};
+*
+* #[derive(Lens, Debug)]
+* struct Node(#[optic(ref)] &'static str, #[optic(ref)] Vec<Weak<Node>>);
+*
+* // a -> b -> c -> a, and a -> c
+* let mut arena = vec![];
+* let a = Rc::new_cyclic(|a| {
+*     let c = Rc::new(Node("c", vec![a.clone()]));
+*     let b = Rc::new(Node("b", vec![Rc::downgrade(&c)]));
+*     let node = Node("a", vec![Rc::downgrade(&b), Rc::downgrade(&c)]);
+*     arena.push(b);
+*     arena.push(c);
+*     node
+* });
+*
+* let nodes = reachable_of(optics!(_1._mapped), &a, optics!(_upgraded), OnCycle::Skip).unwrap();
+* assert_eq!(nodes.iter().map(|node| node.0).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+*
+* let cycle = reachable_of(optics!(_1._mapped), &a, optics!(_upgraded), OnCycle::Fail).unwrap_err();
+* assert!(Rc::ptr_eq(&cycle.node, &a));
+* ```
+*/
+pub fn reachable_of<Tr, T, Pv>(
+    links: Tr,
+    root: &Rc<T>,
+    link: Pv,
+    on_cycle: OnCycle,
+) -> Result<Vec<Rc<T>>, CycleError<T>>
+where
+    Tr: TraversalRef<T>,
+    Pv: Preview<Tr::To, To = Rc<T>>,
+{
+    // a link to a node on `path`, the nodes being walked, closes a cycle,
+    // a link to another visited node only shares it.
+    struct Walk<'o, Tr, Pv, T> {
+        links: &'o Tr,
+        link: &'o Pv,
+        on_cycle: OnCycle,
+        path: HashSet<*const T>,
+        visited: HashSet<*const T>,
+        nodes: Vec<Rc<T>>,
+    }
+
+    impl<Tr, Pv, T> Walk<'_, Tr, Pv, T>
+    where
+        Tr: TraversalRef<T>,
+        Pv: Preview<Tr::To, To = Rc<T>>,
+    {
+        fn walk(&mut self, node: Rc<T>) -> Result<(), CycleError<T>> {
+            let ptr = Rc::as_ptr(&node);
+            self.path.insert(ptr);
+            self.visited.insert(ptr);
+            let mut next = vec![];
+            self.links.visit_ref(&node, &mut |focus| {
+                next.extend(self.link.preview(focus));
+                true
+            });
+            self.nodes.push(node);
+            for next in next {
+                if self.path.contains(&Rc::as_ptr(&next)) {
+                    if self.on_cycle == OnCycle::Fail {
+                        return Result::Err(CycleError { node: next });
+                    }
+                } else if !self.visited.contains(&Rc::as_ptr(&next)) {
+                    self.walk(next)?;
+                }
+            }
+            self.path.remove(&ptr);
+            Result::Ok(())
+        }
+    }
+
+    let mut walk = Walk {
+        links: &links,
+        link: &link,
+        on_cycle,
+        path: HashSet::new(),
+        visited: HashSet::new(),
+        nodes: vec![],
+    };
+    walk.walk(root.clone())?;
+    Result::Ok(walk.nodes)
+}
+
+/**
+* The lenses of the columns extracted by `collect_columns`, a tuple of up to 8 lenses.
+*/
+pub trait Columns<Row> {
+    type Columns;
+    fn collect_columns(&self, rows: &[&Row]) -> Self::Columns;
+}
+
+macro_rules! impl_columns {
+    ($($ls:ident $i:tt),+) => {
+        impl<Row, $($ls),+> Columns<Row> for ($($ls,)+)
+        where
+            $($ls: LensRef<Row>, $ls::To: Clone,)+
+        {
+            type Columns = ($(Vec<$ls::To>,)+);
+
+            fn collect_columns(&self, rows: &[&Row]) -> Self::Columns {
+                ($(rows.iter().map(|row| self.$i.view_ref(row).clone()).collect::<Vec<_>>(),)+)
+            }
+        }
+    };
+}
+
+impl_columns!(A 0);
+impl_columns!(A 0, B 1);
+impl_columns!(A 0, B 1, C 2);
+impl_columns!(A 0, B 1, C 2, D 3);
+impl_columns!(A 0, B 1, C 2, D 3, E 4);
+impl_columns!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_columns!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_columns!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/**
+* Extract some lenses of every row reached by the traversal into parallel column vectors,
+* e.g. to export nested structs as a table.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let table = ((), vec![("alice", (30, true)), ("bob", (25, false))]);
+* let (names, ages, active) =
+*     collect_columns(optics!(_1._mapped), &table, (optics!(_0), optics!(_1._0), optics!(_1._1)));
+* assert_eq!(names, vec!["alice", "bob"]);
+* assert_eq!(ages, vec![30, 25]);
+* assert_eq!(active, vec![true, false]);
+* ```
+*/
+pub fn collect_columns<Tr, T, C>(rows: Tr, source: &T, columns: C) -> C::Columns
+where
+    Tr: TraversalRef<T>,
+    C: Columns<Tr::To>,
+{
+    columns.collect_columns(&rows.traverse_ref(source))
+}
@@ -0,0 +1,148 @@
+use crate::*;
+
+/**
+* Replace every focus of the optic with a freshly generated value, leaving the rest of the structure intact.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut u = arbitrary::Unstructured::new(&[7, 9]);
+* let mut x = (1u8, vec![2u8, 3]);
+* mutate_of(optics!(_1._mapped), &mut x, &mut u).unwrap();
+* assert_eq!(x.0, 1);
+* assert_eq!(x.1, vec![7, 9]);
+* ```
+*/
+#[cfg(feature = "arbitrary")]
+pub fn mutate_of<'a, Tr, T, A>(
+    optic: Tr,
+    source: &mut T,
+    rng: &mut arbitrary::Unstructured<'a>,
+) -> arbitrary::Result<()>
+where
+    Tr: TraversalMut<T, To = A>,
+    A: arbitrary::Arbitrary<'a>,
+{
+    for x in optic.traverse_mut(source) {
+        *x = A::arbitrary(rng)?;
+    }
+    Result::Ok(())
+}
+
+/**
+* A proptest strategy yielding copies of `source` whose foci are drawn from `strategy`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use proptest::prelude::*;
+*
+* proptest! {
+*     fn only_the_focus_changes(x in mutated_of(optics!(_1), (1u8, 2u8), any::<u8>())) {
+*         prop_assert_eq!(x.0, 1);
+*     }
+* }
+* only_the_focus_changes();
+* ```
+*/
+#[cfg(feature = "proptest")]
+pub fn mutated_of<Tr, T, S>(
+    optic: Tr,
+    source: T,
+    strategy: S,
+) -> impl proptest::strategy::Strategy<Value = T>
+where
+    Tr: TraversalMut<T, To = S::Value>,
+    T: Clone + std::fmt::Debug,
+    S: proptest::strategy::Strategy,
+    S::Value: Clone,
+{
+    strategy.prop_map(move |value| {
+        let mut source = source.clone();
+        for x in optic.traverse_mut(&mut source) {
+            *x = value.clone();
+        }
+        source
+    })
+}
+
+#[cfg(feature = "arbitrary")]
+type Choice<'a, T> = (
+    u32,
+    Box<dyn Fn(&mut arbitrary::Unstructured<'a>) -> arbitrary::Result<T>>,
+);
+
+/**
+* Weighted reviews building the variants of a type, `gen_of` picks one and builds it from a generated value.
+* ## Example
+* ```
+* use lens_rs::*;
+* let choices = Reviews::<Result<u8, u8>>::new()
+*     .with(optics!(Ok), 3)
+*     .with(optics!(Err), 1);
+*
+* let mut u = arbitrary::Unstructured::new(&[0, 5, 3, 9]);
+* assert_eq!(gen_of(&choices, &mut u).unwrap(), Result::Ok(5));
+* assert_eq!(gen_of(&choices, &mut u).unwrap(), Result::Err(9));
+* ```
+*/
+#[cfg(feature = "arbitrary")]
+pub struct Reviews<'a, T> {
+    choices: Vec<Choice<'a, T>>,
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T> Default for Reviews<'a, T> {
+    fn default() -> Self {
+        Self { choices: vec![] }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T> Reviews<'a, T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// build with `optic` in `weight` of the total weight, a zero weight never picks it.
+    pub fn with<Rv>(mut self, optic: Rv, weight: u32) -> Self
+    where
+        Rv: Review<T> + 'static,
+        Rv::From: arbitrary::Arbitrary<'a>,
+    {
+        self.choices.push((
+            weight,
+            Box::new(move |rng| Result::Ok(optic.review(arbitrary::Arbitrary::arbitrary(rng)?))),
+        ));
+        self
+    }
+}
+
+/**
+* Pick one of the reviews by weight and build a value through it, for the fuzz targets and the property tests
+* generating structured values, see `Reviews`.
+* It fails with `IncorrectFormat` when there is no review of a positive weight.
+*/
+#[cfg(feature = "arbitrary")]
+pub fn gen_of<'a, T>(
+    reviews: &Reviews<'a, T>,
+    rng: &mut arbitrary::Unstructured<'a>,
+) -> arbitrary::Result<T> {
+    let total = reviews
+        .choices
+        .iter()
+        .map(|(weight, _)| *weight)
+        .sum::<u32>();
+    if total == 0 {
+        return Result::Err(arbitrary::Error::IncorrectFormat);
+    }
+    let mut pick = rng.int_in_range(0..=total - 1)?;
+    let (_, review) = reviews
+        .choices
+        .iter()
+        .find(|(weight, _)| {
+            let found = pick < *weight;
+            pick = pick.saturating_sub(*weight);
+            found
+        })
+        .unwrap_or_else(|| unreachable!());
+    review(rng)
+}
@@ -0,0 +1,368 @@
+/*!
+Adapters turning the accessors of the lenses of other optics crates, e.g. the `get_ref`/`get_mut_ref`
+of `pl-lens` or the `get`/`set` of a value lens, into lens-rs optics, so they compose under the optics here.
+With the `pl-lens` feature a lens of that crate is adapted as is by `PlLens` or `PlValueLens`,
+the lenses of other crates, e.g. `lenses-rs` or `optics`, go through their accessors.
+The other way round, an optic of lens-rs is already a pair of accessors, `|s| optic.view_ref(s)`.
+## Example
+```
+use lens_rs::*;
+use lens_rs::interop::{from_get_set, from_refs};
+
+// the lenses of another crate
+struct Celsius;
+impl Celsius {
+    fn get_ref<'a>(&self, s: &'a (f64, u8)) -> &'a f64 { &s.0 }
+    fn get_mut_ref<'a>(&self, s: &'a mut (f64, u8)) -> &'a mut f64 { &mut s.0 }
+}
+
+let celsius = from_refs(move |s| Celsius.get_ref(s), move |s| Celsius.get_mut_ref(s));
+let mut sensors = vec![(20.0, 1), (25.0, 2)];
+optics::_mapped(celsius).traverse_mut(&mut sensors).into_iter().for_each(|t| *t += 1.0);
+assert_eq!(sensors, vec![(21.0, 1), (26.0, 2)]);
+
+let fahrenheit = from_get_set(|s: &(f64, u8)| s.0 * 1.8 + 32.0, |s: &mut (f64, u8), f| s.0 = (f - 32.0) / 1.8);
+fahrenheit.set(&mut sensors[0], 212.0);
+assert_eq!(fahrenheit.get(&sensors[0]), 212.0);
+```
+*/
+use crate::*;
+use std::marker::PhantomData;
+
+/// a lens from a pair of accessors borrowing the focus, see `from_refs`.
+pub struct RefLens<S, A, G, M> {
+    get: G,
+    get_mut: M,
+    focus: PhantomData<fn(&S) -> &A>,
+}
+
+/// a lens computing the focus, written back by `set`, see `from_get_set`.
+pub struct GetSetLens<S, A, G, St> {
+    get: G,
+    set: St,
+    focus: PhantomData<fn(&S) -> A>,
+}
+
+/// the lens borrowing the focus through `get` and `get_mut`.
+pub fn from_refs<S, A, G, M>(get: G, get_mut: M) -> RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+    M: Fn(&mut S) -> &mut A,
+{
+    RefLens {
+        get,
+        get_mut,
+        focus: PhantomData,
+    }
+}
+
+/// the lens reading the focus with `get` and writing it with `set`.
+pub fn from_get_set<S, A, G, St>(get: G, set: St) -> GetSetLens<S, A, G, St>
+where
+    G: Fn(&S) -> A,
+    St: Fn(&mut S, A),
+{
+    GetSetLens {
+        get,
+        set,
+        focus: PhantomData,
+    }
+}
+
+impl<S, A, G, M> TraversalRef<S> for RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+{
+    type To = A;
+
+    fn traverse_ref<'a>(&self, source: &'a S) -> Vec<&'a Self::To> {
+        vec![(self.get)(source)]
+    }
+}
+
+impl<S, A, G, M> TraversalMut<S> for RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+    M: Fn(&mut S) -> &mut A,
+{
+    fn traverse_mut<'a>(&self, source: &'a mut S) -> Vec<&'a mut Self::To> {
+        vec![(self.get_mut)(source)]
+    }
+}
+
+impl<S, A, G, M> PrismRef<S> for RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+{
+    fn pm_ref<'a>(&self, source: &'a S) -> Option<&'a Self::To> {
+        Option::Some((self.get)(source))
+    }
+}
+
+impl<S, A, G, M> PrismMut<S> for RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+    M: Fn(&mut S) -> &mut A,
+{
+    fn pm_mut<'a>(&self, source: &'a mut S) -> Option<&'a mut Self::To> {
+        Option::Some((self.get_mut)(source))
+    }
+}
+
+impl<S, A, G, M> LensRef<S> for RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+{
+    fn view_ref<'a>(&self, source: &'a S) -> &'a Self::To {
+        (self.get)(source)
+    }
+}
+
+impl<S, A, G, M> LensMut<S> for RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+    M: Fn(&mut S) -> &mut A,
+{
+    fn view_mut<'a>(&self, source: &'a mut S) -> &'a mut Self::To {
+        (self.get_mut)(source)
+    }
+}
+
+impl<S, A, G, M> Preview<S> for RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+    A: Clone,
+{
+    type To = A;
+
+    fn preview(&self, source: &S) -> Option<Self::To> {
+        Option::Some(self.get(source))
+    }
+}
+
+impl<S, A, G, M> Getter<S> for RefLens<S, A, G, M>
+where
+    G: Fn(&S) -> &A,
+    A: Clone,
+{
+    fn get(&self, source: &S) -> Self::To {
+        (self.get)(source).clone()
+    }
+}
+
+impl<S, A, G, M> Setter<S> for RefLens<S, A, G, M>
+where
+    M: Fn(&mut S) -> &mut A,
+{
+    type To = A;
+
+    fn set(&self, source: &mut S, value: Self::To) {
+        *(self.get_mut)(source) = value
+    }
+}
+
+impl<S, A, G, St> Preview<S> for GetSetLens<S, A, G, St>
+where
+    G: Fn(&S) -> A,
+{
+    type To = A;
+
+    fn preview(&self, source: &S) -> Option<Self::To> {
+        Option::Some(self.get(source))
+    }
+}
+
+impl<S, A, G, St> Getter<S> for GetSetLens<S, A, G, St>
+where
+    G: Fn(&S) -> A,
+{
+    fn get(&self, source: &S) -> Self::To {
+        (self.get)(source)
+    }
+}
+
+impl<S, A, G, St> Setter<S> for GetSetLens<S, A, G, St>
+where
+    St: Fn(&mut S, A),
+{
+    type To = A;
+
+    fn set(&self, source: &mut S, value: Self::To) {
+        (self.set)(source, value)
+    }
+}
+
+impl<S, A, G, St> Over<S> for GetSetLens<S, A, G, St>
+where
+    G: Fn(&S) -> A,
+    St: Fn(&mut S, A),
+{
+    fn over(&self, source: &mut S, f: &mut dyn FnMut(&mut Self::To)) {
+        let mut focus = (self.get)(source);
+        f(&mut focus);
+        (self.set)(source, focus)
+    }
+}
+
+/**
+* The optic of a `pl-lens` lens borrowing its target, e.g. one of `lens!` or `#[derive(Lenses)]`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::interop::PlLens;
+* use pl_lens::{Lens, LensPath, RefLens};
+*
+* struct Celsius;
+* impl Lens for Celsius {
+*     type Source = (f64, u8);
+*     type Target = f64;
+*     fn path(&self) -> LensPath { LensPath::new(0) }
+*     fn mutate<'a>(&self, source: &'a mut (f64, u8), target: f64) { source.0 = target }
+* }
+* impl RefLens for Celsius {
+*     fn get_ref<'a>(&self, source: &'a (f64, u8)) -> &'a f64 { &source.0 }
+*     fn get_mut_ref<'a>(&self, source: &'a mut (f64, u8)) -> &'a mut f64 { &mut source.0 }
+* }
+*
+* let mut sensors = vec![(20.0, 1), (25.0, 2)];
+* optics::_mapped(PlLens(Celsius)).traverse_mut(&mut sensors).into_iter().for_each(|t| *t += 1.0);
+* assert_eq!(sensors, vec![(21.0, 1), (26.0, 2)]);
+* assert_eq!(*optics::_1(PlLens(Celsius)).view_ref(&(0, (3.0, 4))), 3.0);
+* ```
+*/
+#[cfg(feature = "pl-lens")]
+#[derive(Copy, Clone, Debug)]
+pub struct PlLens<L>(pub L);
+
+/**
+* The optic of a `pl-lens` lens computing its target, read by `ValueLens::get` and written by `Lens::mutate`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::interop::PlValueLens;
+* use pl_lens::{Lens, LensPath, ValueLens};
+*
+* struct Fahrenheit;
+* impl Lens for Fahrenheit {
+*     type Source = (f64, u8);
+*     type Target = f64;
+*     fn path(&self) -> LensPath { LensPath::new(0) }
+*     fn mutate<'a>(&self, source: &'a mut (f64, u8), target: f64) { source.0 = (target - 32.0) / 1.8 }
+* }
+* impl ValueLens for Fahrenheit {
+*     fn get(&self, source: &(f64, u8)) -> f64 { source.0 * 1.8 + 32.0 }
+* }
+*
+* let mut sensor = (0.0, 1);
+* PlValueLens(Fahrenheit).over(&mut sensor, &mut |f| *f += 180.0);
+* assert_eq!(PlValueLens(Fahrenheit).get(&sensor), 212.0);
+* ```
+*/
+#[cfg(feature = "pl-lens")]
+#[derive(Copy, Clone, Debug)]
+pub struct PlValueLens<L>(pub L);
+
+#[cfg(feature = "pl-lens")]
+mod impl_pl_lens {
+    use super::{PlLens, PlValueLens};
+    use crate::*;
+    use pl_lens::{RefLens, ValueLens};
+
+    impl<S, L: RefLens<Source = S>> TraversalRef<S> for PlLens<L> {
+        type To = L::Target;
+
+        fn traverse_ref<'a>(&self, source: &'a S) -> Vec<&'a Self::To> {
+            vec![self.0.get_ref(source)]
+        }
+    }
+
+    impl<S, L: RefLens<Source = S>> TraversalMut<S> for PlLens<L> {
+        fn traverse_mut<'a>(&self, source: &'a mut S) -> Vec<&'a mut Self::To> {
+            vec![self.0.get_mut_ref(source)]
+        }
+    }
+
+    impl<S, L: RefLens<Source = S>> PrismRef<S> for PlLens<L> {
+        fn pm_ref<'a>(&self, source: &'a S) -> Option<&'a Self::To> {
+            Option::Some(self.0.get_ref(source))
+        }
+    }
+
+    impl<S, L: RefLens<Source = S>> PrismMut<S> for PlLens<L> {
+        fn pm_mut<'a>(&self, source: &'a mut S) -> Option<&'a mut Self::To> {
+            Option::Some(self.0.get_mut_ref(source))
+        }
+    }
+
+    impl<S, L: RefLens<Source = S>> LensRef<S> for PlLens<L> {
+        fn view_ref<'a>(&self, source: &'a S) -> &'a Self::To {
+            self.0.get_ref(source)
+        }
+    }
+
+    impl<S, L: RefLens<Source = S>> LensMut<S> for PlLens<L> {
+        fn view_mut<'a>(&self, source: &'a mut S) -> &'a mut Self::To {
+            self.0.get_mut_ref(source)
+        }
+    }
+
+    impl<S, L> Preview<S> for PlLens<L>
+    where
+        L: RefLens<Source = S>,
+        L::Target: Clone,
+    {
+        type To = L::Target;
+
+        fn preview(&self, source: &S) -> Option<Self::To> {
+            Option::Some(self.get(source))
+        }
+    }
+
+    impl<S, L> Getter<S> for PlLens<L>
+    where
+        L: RefLens<Source = S>,
+        L::Target: Clone,
+    {
+        fn get(&self, source: &S) -> Self::To {
+            self.0.get_ref(source).clone()
+        }
+    }
+
+    impl<S, L: RefLens<Source = S>> Setter<S> for PlLens<L> {
+        type To = L::Target;
+
+        fn set(&self, source: &mut S, value: Self::To) {
+            *self.0.get_mut_ref(source) = value
+        }
+    }
+
+    impl<S, L: ValueLens<Source = S>> Preview<S> for PlValueLens<L> {
+        type To = L::Target;
+
+        fn preview(&self, source: &S) -> Option<Self::To> {
+            Option::Some(self.0.get(source))
+        }
+    }
+
+    impl<S, L: ValueLens<Source = S>> Getter<S> for PlValueLens<L> {
+        fn get(&self, source: &S) -> Self::To {
+            self.0.get(source)
+        }
+    }
+
+    impl<S, L: ValueLens<Source = S>> Setter<S> for PlValueLens<L> {
+        type To = L::Target;
+
+        fn set(&self, source: &mut S, value: Self::To) {
+            self.0.mutate(source, value)
+        }
+    }
+
+    impl<S, L: ValueLens<Source = S>> Over<S> for PlValueLens<L> {
+        fn over(&self, source: &mut S, f: &mut dyn FnMut(&mut Self::To)) {
+            let mut focus = self.0.get(source);
+            f(&mut focus);
+            self.0.mutate(source, focus)
+        }
+    }
+}
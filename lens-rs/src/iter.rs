@@ -0,0 +1,108 @@
+use crate::*;
+
+/**
+* Iterator adapters yielding the focus of an affine optic in each element, lazily,
+* the elements without the focus are skipped.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut users = vec![(1, Option::Some("ann")), (2, Option::None), (3, Option::Some("bob"))];
+*
+* let names: Vec<_> = users.iter().foci(optics!(_1.Some)).collect();
+* assert_eq!(names, vec![&"ann", &"bob"]);
+*
+* users.iter_mut().foci_mut(optics!(_0)).filter(|id| **id > 1).for_each(|id| *id *= 10);
+* assert_eq!(users.iter().foci(optics!(_0)).sum::<i32>(), 51);
+* ```
+*/
+pub trait FociIter: Iterator + Sized {
+    fn foci<'a, S, Pm>(self, optic: Pm) -> Foci<Self, Pm>
+    where
+        Self: Iterator<Item = &'a S>,
+        S: 'a,
+        Pm: PrismRef<S>,
+    {
+        foci_iter(self, optic)
+    }
+
+    fn foci_mut<'a, S, Pm>(self, optic: Pm) -> FociMut<Self, Pm>
+    where
+        Self: Iterator<Item = &'a mut S>,
+        S: 'a,
+        Pm: PrismMut<S>,
+    {
+        foci_mut_iter(self, optic)
+    }
+}
+
+impl<I: Iterator> FociIter for I {}
+
+/// the foci of the optic in the borrowed elements, see `FociIter::foci`.
+pub fn foci_iter<'a, I, S, Pm>(iter: I, optic: Pm) -> Foci<I, Pm>
+where
+    I: Iterator<Item = &'a S>,
+    S: 'a,
+    Pm: PrismRef<S>,
+{
+    Foci { iter, optic }
+}
+
+/// the foci of the optic in the mutably borrowed elements, see `FociIter::foci_mut`.
+pub fn foci_mut_iter<'a, I, S, Pm>(iter: I, optic: Pm) -> FociMut<I, Pm>
+where
+    I: Iterator<Item = &'a mut S>,
+    S: 'a,
+    Pm: PrismMut<S>,
+{
+    FociMut { iter, optic }
+}
+
+#[derive(Clone, Debug)]
+pub struct Foci<I, Pm> {
+    iter: I,
+    optic: Pm,
+}
+
+#[derive(Debug)]
+pub struct FociMut<I, Pm> {
+    iter: I,
+    optic: Pm,
+}
+
+impl<'a, I, S, Pm> Iterator for Foci<I, Pm>
+where
+    I: Iterator<Item = &'a S>,
+    S: 'a,
+    Pm: PrismRef<S>,
+    Pm::To: 'a,
+{
+    type Item = &'a Pm::To;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let optic = &self.optic;
+        self.iter.find_map(|source| optic.pm_ref(source))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, I, S, Pm> Iterator for FociMut<I, Pm>
+where
+    I: Iterator<Item = &'a mut S>,
+    S: 'a,
+    Pm: PrismMut<S>,
+    Pm::To: 'a,
+{
+    type Item = &'a mut Pm::To;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let optic = &self.optic;
+        self.iter.find_map(|source| optic.pm_mut(source))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
@@ -0,0 +1,474 @@
+/*!
+* Optics over `serde_json::Value`, the dynamic ones parsed from JSON Pointers and JSONPaths,
+* and the ones shared with the other formats in `lens_rs::value`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::value::{index, key};
+* use serde_json::json;
+*
+* let mut doc = json!({ "servers": [{ "host": "a", "port": 80 }] });
+* let host = key("servers", index(0, key("host", optics!(_string))));
+* host.pm_mut(&mut doc).unwrap().push('b');
+* assert_eq!(host.pm_ref(&doc).unwrap(), "ab");
+*
+* let port = key("servers", index(0, key("port", optics!(_number))));
+* assert_eq!(port.pm_ref(&doc).and_then(|n| n.as_u64()), Option::Some(80));
+* assert_eq!(key("servers", optics!(_string)).pm_ref(&doc), Option::None);
+* ```
+*/
+use crate::value::{impl_value_child, impl_value_variant};
+use crate::*;
+use serde_json::{Map, Number, Value};
+use std::{fmt, str::FromStr};
+
+/**
+* A RFC 6901 JSON Pointer parsed into a dynamic prism over `serde_json::Value`.
+* It can also be the inner optic of a derived one, e.g. `optics::settings(pointer)`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::json::JsonPointer;
+* use serde_json::json;
+*
+* let mut doc = json!({ "servers": [{ "host": "a" }, { "host": "b" }], "a/b": 1 });
+* let host: JsonPointer = "/servers/1/host".parse().unwrap();
+* assert_eq!(host.pm_ref(&doc), Option::Some(&json!("b")));
+*
+* *host.pm_mut(&mut doc).unwrap() = json!("c");
+* assert_eq!(doc["servers"][1]["host"], "c");
+* assert_eq!(JsonPointer::parse("/a~1b").unwrap().pm(doc), Option::Some(json!(1)));
+* ```
+*/
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JsonPointer {
+    tokens: Vec<String>,
+}
+
+/**
+* A subset of JSONPath parsed into a dynamic traversal over `serde_json::Value`:
+* the root `$`, children `.name` or `['name']`, indices `[0]` and wildcards `.*` or `[*]`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::json::JsonPath;
+* use serde_json::json;
+*
+* let mut doc = json!({ "servers": [{ "port": 80 }, { "port": 8080 }] });
+* let ports: JsonPath = "$.servers[*].port".parse().unwrap();
+* assert_eq!(ports.traverse_ref(&doc), vec![&json!(80), &json!(8080)]);
+*
+* ports.traverse_mut(&mut doc).into_iter().for_each(|port| *port = json!(443));
+* assert_eq!(doc, json!({ "servers": [{ "port": 443 }, { "port": 443 }] }));
+* ```
+*/
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JsonPath {
+    segments: Vec<PathSegment>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JsonPathError {
+    /// the byte offset in the parsed string.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for JsonPathError {}
+
+fn error<T>(position: usize, message: &str) -> Result<T, JsonPathError> {
+    Result::Err(JsonPathError {
+        position,
+        message: message.to_string(),
+    })
+}
+
+impl JsonPointer {
+    pub fn parse(pointer: &str) -> Result<Self, JsonPathError> {
+        if pointer.is_empty() {
+            return Result::Ok(Self { tokens: vec![] });
+        }
+        if !pointer.starts_with('/') {
+            return error(0, "a JSON Pointer should start with `/`");
+        }
+
+        let mut position = 1;
+        let mut tokens = vec![];
+        for raw in pointer[1..].split('/') {
+            let mut token = String::with_capacity(raw.len());
+            let mut chars = raw.char_indices();
+            while let Option::Some((i, c)) = chars.next() {
+                match (c, chars.clone().next()) {
+                    ('~', Option::Some((_, '0'))) => token.push('~'),
+                    ('~', Option::Some((_, '1'))) => token.push('/'),
+                    ('~', _) => return error(position + i, "`~` should be escaped as `~0`"),
+                    _ => {
+                        token.push(c);
+                        continue;
+                    }
+                }
+                chars.next();
+            }
+            position += raw.len() + 1;
+            tokens.push(token);
+        }
+        Result::Ok(Self { tokens })
+    }
+}
+
+impl JsonPointer {
+    /// the pointer to the serialized focus of a path of derived optics, e.g. `"servers._1.Some.host"`
+    /// is `/servers/1/host`: a tuple optic is an array index and `Some`/`Ok` are transparent.
+    pub fn from_optic_path(path: &str) -> Self {
+        let tokens = path
+            .split('.')
+            .filter(|segment| !segment.is_empty() && *segment != "Some" && *segment != "Ok")
+            .map(|segment| match segment.strip_prefix('_') {
+                Option::Some(index) if array_index(index).is_some() => index.to_string(),
+                _ => segment.to_string(),
+            })
+            .collect();
+        Self { tokens }
+    }
+
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+}
+
+impl FromStr for JsonPointer {
+    type Err = JsonPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for JsonPointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tokens
+            .iter()
+            .try_for_each(|token| write!(f, "/{}", token.replace('~', "~0").replace('/', "~1")))
+    }
+}
+
+// an array index has no leading zeros, `-` (past the end) never exists.
+fn array_index(token: &str) -> Option<usize> {
+    if token == "0" || !token.starts_with('0') {
+        token.parse().ok()
+    } else {
+        Option::None
+    }
+}
+
+fn pointer_step<'a>(value: &'a Value, token: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map.get(token),
+        Value::Array(vec) => array_index(token).and_then(|i| vec.get(i)),
+        _ => Option::None,
+    }
+}
+
+fn pointer_step_mut<'a>(value: &'a mut Value, token: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Object(map) => map.get_mut(token),
+        Value::Array(vec) => array_index(token).and_then(move |i| vec.get_mut(i)),
+        _ => Option::None,
+    }
+}
+
+fn pointer_step_owned(value: Value, token: &str) -> Option<Value> {
+    match value {
+        Value::Object(mut map) => map.remove(token),
+        Value::Array(mut vec) => array_index(token).and_then(|i| {
+            if i < vec.len() {
+                Option::Some(vec.swap_remove(i))
+            } else {
+                Option::None
+            }
+        }),
+        _ => Option::None,
+    }
+}
+
+impl TraversalRef<Value> for JsonPointer {
+    type To = Value;
+
+    fn traverse_ref<'a>(&self, source: &'a Value) -> Vec<&'a Self::To> {
+        self.pm_ref(source).into_iter().collect()
+    }
+}
+
+impl TraversalMut<Value> for JsonPointer {
+    fn traverse_mut<'a>(&self, source: &'a mut Value) -> Vec<&'a mut Self::To> {
+        self.pm_mut(source).into_iter().collect()
+    }
+}
+
+impl Traversal<Value> for JsonPointer {
+    fn traverse(&self, source: Value) -> Vec<Self::To> {
+        self.pm(source).into_iter().collect()
+    }
+}
+
+impl PrismRef<Value> for JsonPointer {
+    fn pm_ref<'a>(&self, source: &'a Value) -> Option<&'a Self::To> {
+        self.tokens
+            .iter()
+            .try_fold(source, |value, token| pointer_step(value, token))
+    }
+}
+
+impl PrismMut<Value> for JsonPointer {
+    fn pm_mut<'a>(&self, source: &'a mut Value) -> Option<&'a mut Self::To> {
+        self.tokens
+            .iter()
+            .try_fold(source, |value, token| pointer_step_mut(value, token))
+    }
+}
+
+impl Prism<Value> for JsonPointer {
+    fn pm(&self, source: Value) -> Option<Self::To> {
+        self.tokens
+            .iter()
+            .try_fold(source, |value, token| pointer_step_owned(value, token))
+    }
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> Result<Self, JsonPathError> {
+        if !path.starts_with('$') {
+            return error(0, "a JSONPath should start with `$`");
+        }
+
+        let bytes = path.as_bytes();
+        let is_name = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'-';
+        let mut segments = vec![];
+        let mut i = 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'.' if bytes.get(i + 1) == Option::Some(&b'*') => {
+                    segments.push(PathSegment::Wildcard);
+                    i += 2;
+                }
+                b'.' => {
+                    let len = bytes[i + 1..].iter().take_while(|&&b| is_name(b)).count();
+                    if len == 0 {
+                        return error(i + 1, "expect a name after `.`");
+                    }
+                    segments.push(PathSegment::Key(path[i + 1..i + 1 + len].to_string()));
+                    i += 1 + len;
+                }
+                b'[' => {
+                    let close = match path[i..].find(']') {
+                        Option::Some(close) => i + close,
+                        Option::None => return error(i, "unclosed `[`"),
+                    };
+                    let inner = &path[i + 1..close];
+                    let quoted = inner.len() >= 2
+                        && (inner.starts_with('\'') && inner.ends_with('\'')
+                            || inner.starts_with('"') && inner.ends_with('"'));
+                    if inner == "*" {
+                        segments.push(PathSegment::Wildcard);
+                    } else if quoted {
+                        segments.push(PathSegment::Key(inner[1..inner.len() - 1].to_string()));
+                    } else if let Result::Ok(index) = inner.parse() {
+                        segments.push(PathSegment::Index(index));
+                    } else {
+                        return error(i + 1, "expect `*`, an index or a quoted name in `[]`");
+                    }
+                    i = close + 1;
+                }
+                _ => return error(i, "expect `.` or `[`"),
+            }
+        }
+        Result::Ok(Self { segments })
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = JsonPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn visit_path<'a>(
+    segments: &[PathSegment],
+    value: &'a Value,
+    f: &mut dyn FnMut(&'a Value) -> bool,
+) -> bool {
+    let (segment, rest) = match segments.split_first() {
+        Option::Some(split) => split,
+        Option::None => return f(value),
+    };
+    let child = match (segment, value) {
+        (PathSegment::Key(key), Value::Object(map)) => map.get(key),
+        (PathSegment::Index(i), Value::Array(vec)) => vec.get(*i),
+        (PathSegment::Wildcard, Value::Object(map)) => {
+            return map.values().all(|value| visit_path(rest, value, f))
+        }
+        (PathSegment::Wildcard, Value::Array(vec)) => {
+            return vec.iter().all(|value| visit_path(rest, value, f))
+        }
+        _ => Option::None,
+    };
+    match child {
+        Option::Some(value) => visit_path(rest, value, f),
+        Option::None => true,
+    }
+}
+
+fn collect_path_mut<'a>(
+    segments: &[PathSegment],
+    value: &'a mut Value,
+    foci: &mut Vec<&'a mut Value>,
+) {
+    let (segment, rest) = match segments.split_first() {
+        Option::Some(split) => split,
+        Option::None => return foci.push(value),
+    };
+    match (segment, value) {
+        (PathSegment::Key(key), Value::Object(map)) => {
+            if let Option::Some(value) = map.get_mut(key) {
+                collect_path_mut(rest, value, foci)
+            }
+        }
+        (PathSegment::Index(i), Value::Array(vec)) => {
+            if let Option::Some(value) = vec.get_mut(*i) {
+                collect_path_mut(rest, value, foci)
+            }
+        }
+        (PathSegment::Wildcard, Value::Object(map)) => map
+            .values_mut()
+            .for_each(|value| collect_path_mut(rest, value, foci)),
+        (PathSegment::Wildcard, Value::Array(vec)) => vec
+            .iter_mut()
+            .for_each(|value| collect_path_mut(rest, value, foci)),
+        _ => (),
+    }
+}
+
+fn collect_path_owned(segments: &[PathSegment], value: Value, foci: &mut Vec<Value>) {
+    let (segment, rest) = match segments.split_first() {
+        Option::Some(split) => split,
+        Option::None => return foci.push(value),
+    };
+    match (segment, value) {
+        (PathSegment::Key(key), Value::Object(mut map)) => {
+            if let Option::Some(value) = map.remove(key) {
+                collect_path_owned(rest, value, foci)
+            }
+        }
+        (PathSegment::Index(i), Value::Array(vec)) => {
+            if let Option::Some(value) = vec.into_iter().nth(*i) {
+                collect_path_owned(rest, value, foci)
+            }
+        }
+        (PathSegment::Wildcard, Value::Object(map)) => map
+            .into_iter()
+            .for_each(|(_, value)| collect_path_owned(rest, value, foci)),
+        (PathSegment::Wildcard, Value::Array(vec)) => vec
+            .into_iter()
+            .for_each(|value| collect_path_owned(rest, value, foci)),
+        _ => (),
+    }
+}
+
+impl TraversalRef<Value> for JsonPath {
+    type To = Value;
+
+    fn traverse_ref<'a>(&self, source: &'a Value) -> Vec<&'a Self::To> {
+        let mut foci = vec![];
+        self.visit_ref(source, &mut |value| {
+            foci.push(value);
+            true
+        });
+        foci
+    }
+
+    fn visit_ref<'a>(&self, source: &'a Value, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+        visit_path(&self.segments, source, f)
+    }
+}
+
+impl TraversalMut<Value> for JsonPath {
+    fn traverse_mut<'a>(&self, source: &'a mut Value) -> Vec<&'a mut Self::To> {
+        let mut foci = vec![];
+        collect_path_mut(&self.segments, source, &mut foci);
+        foci
+    }
+}
+
+impl Traversal<Value> for JsonPath {
+    fn traverse(&self, source: Value) -> Vec<Self::To> {
+        let mut foci = vec![];
+        collect_path_owned(&self.segments, source, &mut foci);
+        foci
+    }
+}
+
+fn key_ref<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map.get(key),
+        _ => Option::None,
+    }
+}
+
+fn key_mut<'a>(value: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Object(map) => map.get_mut(key),
+        _ => Option::None,
+    }
+}
+
+fn key_take(value: Value, key: &str) -> Option<Value> {
+    match value {
+        Value::Object(mut map) => map.remove(key),
+        _ => Option::None,
+    }
+}
+
+fn index_ref<'a>(value: &'a Value, index: &usize) -> Option<&'a Value> {
+    match value {
+        Value::Array(vec) => vec.get(*index),
+        _ => Option::None,
+    }
+}
+
+fn index_mut<'a>(value: &'a mut Value, index: &usize) -> Option<&'a mut Value> {
+    match value {
+        Value::Array(vec) => vec.get_mut(*index),
+        _ => Option::None,
+    }
+}
+
+fn index_take(value: Value, index: &usize) -> Option<Value> {
+    match value {
+        Value::Array(vec) => vec.into_iter().nth(*index),
+        _ => Option::None,
+    }
+}
+
+impl_value_child!(Value, _key, key_ref, key_mut, key_take);
+impl_value_child!(Value, _index, index_ref, index_mut, index_take);
+
+impl_value_variant!(Value, _bool, Value::Bool, bool);
+impl_value_variant!(Value, _number, Value::Number, Number);
+impl_value_variant!(Value, _string, Value::String, String);
+impl_value_variant!(Value, _array, Value::Array, Vec<Value>);
+impl_value_variant!(Value, _object, Value::Object, Map<String, Value>);
@@ -1,36 +1,400 @@
+pub mod builder;
+#[cfg(feature = "bytes")]
+pub mod bytes_buf;
+pub mod combinator;
+pub mod defaults;
+#[cfg(feature = "egui")]
+pub mod egui_widgets;
+#[cfg(feature = "bitflags")]
+pub mod flags;
+pub mod fold;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub mod fuzz;
+pub mod interop;
+pub mod iter;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod merge;
+pub mod migration;
 pub mod optics;
+pub mod patched;
+pub mod prelude;
+pub mod raw;
+pub mod redact;
+pub mod reflect;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod snapshot;
+pub mod store;
+pub mod sync;
+#[cfg(feature = "toml")]
+pub mod toml_value;
 pub mod traits;
+pub mod value;
+#[cfg(feature = "yaml")]
+pub mod yaml;
 
 pub use traits::{
-    Lens, LensMut, LensRef, Prism, PrismMut, PrismRef, Review, Traversal, TraversalMut,
-    TraversalRef,
+    BoxFuture, Compose, ConstOptic, Fetch, Getter, IntoTraversal, Lens, LensAsyncMut, LensAsyncRef,
+    LensMut, LensRef, Over, Preview, Prism, PrismMut, PrismRef, Review, Setter, Traversal,
+    TraversalMut, TraversalRef,
 };
 
 pub use optics::{
-    _both, _mapped, _arc, _box, Err, _mut, _rc, _ref, _0, _1, _2, _3, _4, _5, _6,
-    __,
+    _arc, _array, _bool, _both, _box, _component_list, _datetime, _downcast, _end, _extension,
+    _fetch, _file_name, _file_stem, _flattened, _float, _index, _integer, _ip, _iterated, _key,
+    _mapped, _micros, _millis, _mut, _nonzero, _number, _object, _or_default, _port, _range, _rc,
+    _ref, _rev, _secs, _secs_f64, _sequence, _since_epoch, _start, _string, _upgraded, _utf8, Err,
+    _0, _1, _2, _3, _4, _5, _6, _V4, _V6, __,
 };
 
-pub use lens_rs_derive::{Lens, Prism, Review};
+#[cfg(feature = "tuple16")]
+pub use optics::{_10, _11, _12, _13, _14, _15, _7, _8, _9};
 
+#[cfg(feature = "tuple32")]
+pub use optics::{_16, _17, _18, _19, _20, _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31};
+
+#[cfg(feature = "codec")]
+pub use optics::{_base64, _hex};
+
+pub use builder::Builder;
+
+pub use combinator::{
+    and_also, bridge, dedup_of, downcast, errors_of, guarded, map_err_of, map_over_each, memoized,
+    nth_of, over_with_result, overlay, page_of, preview_or, preview_or_else, range, retain_of,
+    review_of, sample_of, set_with_result, sort_by_lens, sort_by_lens_desc,
+    traverse_mut_with_progress, try_over, try_traverse_mut, unique_by, zip_traverse, AndAlso,
+    Bridged, Cancelled, Concat, Guarded, Memoized, Nth, Overlay, Page, PreviewOr, PreviewOrElse,
+    Retain, Sample, UniqueBy,
+};
+
+pub use defaults::Defaults;
+
+pub use fold::{
+    chunks_mut_of, chunks_of, collect_columns, find_of, fold_mut_of, fold_of, group_by_of,
+    is_empty_of, length_of, partition_of, partition_owned_of, position_of, previews_of, product_of,
+    reachable_of, windows_of, Columns, CycleError, OnCycle,
+};
+
+#[cfg(feature = "arbitrary")]
+pub use fuzz::{gen_of, mutate_of, Reviews};
+
+#[cfg(feature = "proptest")]
+pub use fuzz::mutated_of;
+
+pub use iter::{foci_iter, foci_mut_iter, Foci, FociIter, FociMut};
+
+pub use merge::{
+    merge3_with, merge_rule, merge_with, take_mine, take_theirs, MergeError, MergeRule,
+};
+
+pub use migration::{Migration, MigrationError};
+
+pub use patched::Patched;
+
+pub use raw::{Plain, RawLens};
+
+pub use redact::Redacted;
+
+pub use snapshot::{clone_of, restore, snapshot_of, transact, Snapshot};
+
+pub use reflect::{FocusType, OpticDoc, OpticInfo, PatchError, Registry};
+
+pub use store::{Selector, Store};
+
+pub use sync::{update_via, view_via};
+
+#[cfg(feature = "arc-swap")]
+pub use sync::update_swap;
+
+pub use lens_rs_derive::{Optic, Prism, Project};
+
+/**
+* Derive the reviews of the `#[optic]` variants of an enum, with a `construct_*` function for each.
+*
+* The reviews of a recursive enum also build its `Box`ed variants, so a composed review nests them.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* // restricted variants, a doctest's own optics live in `tree_optics`
+* #[derive(Debug, PartialEq, Prism, Review)]
+* enum Tree {
+*     #[optic(vis = "pub(self)")]
+*     Leaf(i32),
+*     #[optic(vis = "pub(self)")]
+*     Branch(Box<Tree>),
+* }
+*
+* use tree_optics::{Branch, Leaf};
+*
+* // optics!(Branch.Branch.Leaf) for public variants
+* let tree: Tree = review_of(Branch(Branch(Leaf(__))), 1);
+* assert_eq!(tree, Tree::construct_branch(Box::new(Tree::construct_branch(Box::new(Tree::construct_leaf(1))))));
+* ```
+*
+* A `#[non_exhaustive]` variant is preview-only, a `#[non_exhaustive]` enum is reviewed as usual.
+* ```
+* use lens_rs::*;
+*
+* #[derive(Debug, PartialEq, Prism, Review)]
+* #[non_exhaustive]
+* enum Event {
+*     #[optic(vis = "pub(self)")]
+*     Click(u32),
+*     #[optic(vis = "pub(self)")]
+*     #[non_exhaustive]
+*     Key(char),
+* }
+*
+* use event_optics::{Click, Key};
+*
+* let click: Event = review_of(Click(__), 3);
+* assert_eq!(Key(__).pm_ref(&Event::Key('a')), Option::Some(&'a'));
+* assert_eq!(Key(__).pm(click), Option::None);
+* ```
+* ```compile_fail
+* use lens_rs::*;
+*
+* #[derive(Prism, Review)]
+* enum Event {
+*     #[optic(vis = "pub(self)")]
+*     #[non_exhaustive]
+*     Key(char),
+* }
+*
+* let key: Event = review_of(event_optics::Key(__), 'a');
+* ```
+*
+* Only an enum derives `Review`, a `#[non_exhaustive]` struct can't be built outside its crate anyway.
+* ```compile_fail
+* use lens_rs::*;
+*
+* #[derive(Lens, Review)]
+* #[non_exhaustive]
+* struct Key(#[optic] char);
+* ```
+*/
+pub use lens_rs_derive::Review;
+
+/**
+* Derive the lenses of the `#[optic]` fields of a struct.
+*
+* The lenses of a recursive struct also see through its `Box`es, so a path walks down without `_box` segments.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* #[derive(Lens)]
+* struct Node(#[optic] i32, #[optic] Option<Box<Node>>);
+*
+* let list = Node(1, Some(Box::new(Node(2, Some(Box::new(Node(3, None)))))));
+*
+* // optics!(next.Some.next.Some.value) with named fields
+* assert_eq!(optics!(_1.Some._1.Some._0).pm_ref(&list), Option::Some(&3));
+* ```
+*
+* A derive sees only its own type, so `#[optic(flatten)]` looks the field's type up by name in the src,
+* examples and tests of the deriving crate, a type defined elsewhere is an error.
+* ```compile_fail
+* use lens_rs::*;
+*
+* // a doctest isn't in the sources of its crate
+* #[derive(Lens)]
+* struct Endpoint(#[optic] u16);
+*
+* #[derive(Lens)]
+* struct Service(#[optic(flatten)] Endpoint);
+* ```
+*/
+pub use lens_rs_derive::Lens;
+
+/**
+* Compose the optics along a path, the result is a constant expression.
+*
+* A segment can also call a constructor of `lens_rs::combinator` for an optic carrying a value or a type,
+* e.g. `range(5..)`, `nth_of(2)` or `downcast::<u16>()`, with the rest of the path as its first argument.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::ops::RangeFrom;
+*
+* static PORT: field![_1._0] = optics!(_1._0);
+* static RECENT: _range<RangeFrom<u64>, field![_0]> = range(optics!(_0), 10..);
+*
+* let flags: [(&str, field![_0]); 2] = [("--name", optics!(_0)), ("--nick", optics!(_0))];
+* assert_eq!(*PORT.view_ref(&("host", (8080, ()))), 8080);
+* assert_eq!(flags.len(), 2);
+* let _ = &RECENT;
+* ```
+* ```
+* use lens_rs::*;
+* use std::any::Any;
+* use std::collections::BTreeMap;
+*
+* let mut ports: (BTreeMap<u64, Box<dyn Any>>, ()) = (BTreeMap::new(), ());
+* ports.0.insert(1, Box::new(80u16));
+* ports.0.insert(2, Box::new("disabled"));
+* ports.0.insert(3, Box::new(8080u16));
+*
+* let open = optics!(_0.range(2..).downcast::<u16>());
+* assert_eq!(open.traverse_ref(&ports), vec![&8080]);
+* assert_eq!(optics!(_0.range(..).downcast::<&str>()).traverse_ref(&ports), vec![&"disabled"]);
+* ```
+* ```
+* use lens_rs::*;
+*
+* let mut service = ("web", vec![80u16, 443, 8080]);
+* assert_eq!(optics!(_1.nth_of(1)._mapped).traverse_ref(&service), vec![&443]);
+*
+* let unprivileged = optics!(_1._mapped.guarded(|port: &u16| *port >= 1024));
+* unprivileged.traverse_mut(&mut service).into_iter().for_each(|port| *port += 1);
+* assert_eq!(service.1, vec![80, 443, 8081]);
+* ```
+*/
 #[macro_export]
 macro_rules! optics {
-    () => { __ };
-    ($optic:ident) => { lens_rs::optics::$optic(__) };
+    () => { $crate::optics::__ };
+    ($optic:ident) => { $crate::optics::$optic($crate::optics::__) };
     ($optic:ident . $($optics:tt)*) => {
-        lens_rs::optics::$optic(optics!($($optics)*))
-    }
+        $crate::optics::$optic($crate::optics!($($optics)*))
+    };
+    ($optic:ident $(::<$($ty:ty),+>)? ($($arg:expr),* $(,)?) $(. $($optics:tt)*)?) => {
+        $crate::combinator::$optic$(::<$($ty,)+ _>)?($crate::optics!($($($optics)*)?), $($arg,)*)
+    };
 }
 
+/**
+* The type of the optic `optics!` composes along the same path, to name a path in a signature
+* or share it across modules. `<Alias>.rest` extends the path of an alias.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* type Server = field![_1];
+* type ServerPort = field![<Server>._0];
+* const SERVER_PORT: ServerPort = ServerPort::OPTIC;
+*
+* fn port<S>(config: &S, optic: ServerPort) -> u16
+* where
+*     ServerPort: LensRef<S, To = u16>,
+* {
+*     *optic.view_ref(config)
+* }
+*
+* assert_eq!(port(&("app", (8080u16, "localhost")), SERVER_PORT), 8080);
+* assert_eq!(SERVER_PORT, optics!(_1._0));
+* ```
+*/
 #[macro_export]
 macro_rules! field {
     [] => { __ };
+    [<$alias:ty> . $($optics:tt)*] => {
+        <$alias as lens_rs::Compose<field![$($optics)*]>>::Output
+    };
     [$optic:ident] => { lens_rs::optics::$optic<__> };
     [$optic:ident . $($optics:tt)*] => {
         lens_rs::optics::$optic<field![$($optics)*]>
     }
 }
 
+/**
+* Try the optics in order against the borrowed source and run the arm of the first one with a focus.
+* ## Example
+* ```
+* use lens_rs::*;
+* let describe = |x: (Option<i32>, Result<String, ()>)| {
+*     match_optics!(x {
+*         _0.Some => |n| format!("number {}", n),
+*         _1.Ok => |s| format!("string {}", s),
+*         _ => "nothing".to_string(),
+*     })
+* };
+* assert_eq!(describe((Option::Some(1), Result::Ok("a".into()))), "number 1");
+* assert_eq!(describe((Option::None, Result::Ok("a".into()))), "string a");
+* assert_eq!(describe((Option::None, Result::Err(()))), "nothing");
+* ```
+*/
+#[macro_export]
+macro_rules! match_optics {
+    (@arms $source:ident;) => { () };
+    (@arms $source:ident; _ => $default:expr $(,)?) => { $default };
+    (@arms $source:ident; $($optic:ident).+ => |$x:ident| $body:expr $(, $($arms:tt)*)?) => {
+        if let ::core::option::Option::Some($x) = lens_rs::PrismRef::pm_ref(&optics!($($optic).+), $source) {
+            $body
+        } else {
+            match_optics!(@arms $source; $($($arms)*)?)
+        }
+    };
+    ($source:tt { $($arms:tt)* }) => {{
+        let source = &$source;
+        match_optics!(@arms source; $($arms)*)
+    }};
+}
+
+/**
+* Assert the focus of a lens equals the expected value, the failure message shows the optic path.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x = (1, (2, "three"));
+* assert_view_eq!(_1._1, &x, "three");
+* ```
+* ```should_panic
+* use lens_rs::*;
+* // panics with: assertion `optics!(_1._0)` failed, focus: `2`, expected: `3`
+* assert_view_eq!(_1._0, &(1, (2, 3)), 3);
+* ```
+*/
+#[macro_export]
+macro_rules! assert_view_eq {
+    ($($optic:ident).+, $source:expr, $expected:expr $(,)?) => {
+        match (lens_rs::LensRef::view_ref(&optics!($($optic).+), $source), &$expected) {
+            (focus, expected) => {
+                if !(*focus == *expected) {
+                    panic!(
+                        "assertion `optics!({})` failed, focus: `{:?}`, expected: `{:?}`",
+                        stringify!($($optic).+),
+                        focus,
+                        expected,
+                    )
+                }
+            }
+        }
+    };
+}
+
+/**
+* Assert the optic has a focus in the value, the failure message shows the optic path and the value.
+* ## Example
+* ```
+* use lens_rs::*;
+* let x: (i32, Result<_, ()>) = (1, Result::Ok(Option::Some(2)));
+* assert_matches_optic!(_1.Ok.Some, &x);
+* ```
+* ```should_panic
+* use lens_rs::*;
+* // panics with: assertion `optics!(_1.Some)` failed, no focus in `(1, None)`
+* assert_matches_optic!(_1.Some, &(1, Option::<i32>::None));
+* ```
+*/
+#[macro_export]
+macro_rules! assert_matches_optic {
+    ($($optic:ident).+, $source:expr $(,)?) => {
+        match $source {
+            source => {
+                if lens_rs::PrismRef::pm_ref(&optics!($($optic).+), source).is_none() {
+                    panic!(
+                        "assertion `optics!({})` failed, no focus in `{:?}`",
+                        stringify!($($optic).+),
+                        source,
+                    )
+                }
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
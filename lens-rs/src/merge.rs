@@ -0,0 +1,169 @@
+use crate::*;
+
+type Resolve<S> = Box<dyn Fn(&mut S, Option<&S>, &S)>;
+type Count<S> = Box<dyn Fn(&S) -> usize>;
+
+/// how to merge the foci of an optic, see `merge_rule`.
+pub struct MergeRule<S> {
+    resolve: Resolve<S>,
+    count: Count<S>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MergeError {
+    /// the optic of the `rule`th rule has `mine` foci in mine and `theirs` in theirs, so they can't be paired.
+    FociMismatch {
+        rule: usize,
+        mine: usize,
+        theirs: usize,
+    },
+}
+
+/**
+* Merge the foci of the optic with `f(mine, theirs)` where they conflict, `take_mine` and `take_theirs`
+* are the usual choices. The foci are paired in traversal order, both sides must have as many of them.
+*/
+pub fn merge_rule<Tr, S, F>(optic: Tr, f: F) -> MergeRule<S>
+where
+    Tr: TraversalMut<S> + 'static,
+    Tr::To: Clone + PartialEq,
+    F: Fn(&mut Tr::To, &Tr::To) + 'static,
+{
+    let optic = std::rc::Rc::new(optic);
+    let counted = optic.clone();
+    let count = move |source: &S| {
+        let mut foci = 0;
+        counted.visit_ref(source, &mut |_| {
+            foci += 1;
+            true
+        });
+        foci
+    };
+    let resolve = move |mine: &mut S, ancestor: Option<&S>, theirs: &S| {
+        let ancestors = ancestor.map(|ancestor| optic.traverse_ref(ancestor));
+        optic
+            .traverse_mut(mine)
+            .into_iter()
+            .zip(optic.traverse_ref(theirs))
+            .enumerate()
+            .for_each(|(i, (mine, theirs))| {
+                match ancestors.as_ref().and_then(|ancestors| ancestors.get(i)) {
+                    Option::Some(ancestor) if *ancestor == theirs => (),
+                    Option::Some(ancestor) if *ancestor == &*mine => mine.clone_from(theirs),
+                    _ if *mine == *theirs => (),
+                    _ => f(mine, theirs),
+                }
+            })
+    };
+    MergeRule {
+        resolve: Box::new(resolve),
+        count: Box::new(count),
+    }
+}
+
+/// resolve a conflict with my focus.
+pub fn take_mine<A>(_mine: &mut A, _theirs: &A) {}
+
+/// resolve a conflict with their focus.
+pub fn take_theirs<A: Clone>(mine: &mut A, theirs: &A) {
+    mine.clone_from(theirs)
+}
+
+/**
+* Merge `theirs` into `mine` through the rules, every focus that differs is a conflict.
+* Only the foci of the rules are merged, the rest of `mine` is kept.
+* Nothing is merged if a rule's optic has more foci on one side than on the other.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut mine = ("prod".to_string(), vec!["a", "b"]);
+* let theirs = ("staging".to_string(), vec!["c"]);
+* merge_with(&mut mine, &theirs, vec![
+*     merge_rule(optics!(_0), take_theirs),
+*     merge_rule(optics!(_1), |mine: &mut Vec<_>, theirs: &Vec<_>| mine.extend(theirs)),
+* ])
+* .unwrap();
+* assert_eq!(mine, ("staging".to_string(), vec!["a", "b", "c"]));
+*
+* let conflict = merge_with(&mut mine, &theirs, vec![
+*     merge_rule(optics!(_0), take_mine),
+*     merge_rule(optics!(_1._mapped), take_theirs),
+* ]);
+* assert_eq!(conflict, Result::Err(MergeError::FociMismatch { rule: 1, mine: 3, theirs: 1 }));
+* assert_eq!(mine, ("staging".to_string(), vec!["a", "b", "c"]));
+* ```
+*/
+pub fn merge_with<S>(
+    mine: &mut S,
+    theirs: &S,
+    rules: impl IntoIterator<Item = MergeRule<S>>,
+) -> Result<(), MergeError> {
+    let rules = paired(&*mine, theirs, rules)?;
+    rules
+        .iter()
+        .for_each(|rule| (rule.resolve)(mine, Option::None, theirs));
+    Result::Ok(())
+}
+
+/**
+* Three-way merge of `theirs` into `mine`, both changed from `ancestor`, through the rules:
+* a focus only one side changed takes that change, the rule resolves the foci both sides changed differently.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let ancestor = ("prod".to_string(), (8080u16, vec!["a"]));
+* let mut mine = ancestor.clone();
+* mine.1 .0 = 9090;
+* mine.1 .1.push("mine");
+* let mut theirs = ancestor.clone();
+* theirs.0 = "staging".to_string();
+* theirs.1 .0 = 7070;
+* theirs.1 .1.push("theirs");
+*
+* merge3_with(&mut mine, &ancestor, &theirs, vec![
+*     merge_rule(optics!(_0), take_mine),
+*     merge_rule(optics!(_1._0), take_mine),
+*     merge_rule(optics!(_1._1), |mine: &mut Vec<&str>, theirs: &Vec<&str>| {
+*         let new: Vec<_> = theirs.iter().filter(|x| !mine.contains(x)).copied().collect();
+*         mine.extend(new)
+*     }),
+* ])
+* .unwrap();
+* assert_eq!(mine, ("staging".to_string(), (9090, vec!["a", "mine", "theirs"])));
+* ```
+*/
+pub fn merge3_with<S>(
+    mine: &mut S,
+    ancestor: &S,
+    theirs: &S,
+    rules: impl IntoIterator<Item = MergeRule<S>>,
+) -> Result<(), MergeError> {
+    let rules = paired(&*mine, theirs, rules)?;
+    rules
+        .iter()
+        .for_each(|rule| (rule.resolve)(mine, Option::Some(ancestor), theirs));
+    Result::Ok(())
+}
+
+// the rules, if the foci of each pair up between mine and theirs.
+fn paired<S>(
+    mine: &S,
+    theirs: &S,
+    rules: impl IntoIterator<Item = MergeRule<S>>,
+) -> Result<Vec<MergeRule<S>>, MergeError> {
+    let rules = rules.into_iter().collect::<Vec<_>>();
+    rules
+        .iter()
+        .enumerate()
+        .try_for_each(|(rule, merge_rule)| {
+            let (mine, theirs) = ((merge_rule.count)(mine), (merge_rule.count)(theirs));
+            if mine == theirs {
+                Result::Ok(())
+            } else {
+                Result::Err(MergeError::FociMismatch { rule, mine, theirs })
+            }
+        })?;
+    Result::Ok(rules)
+}
@@ -0,0 +1,100 @@
+use crate::*;
+use std::fmt;
+
+type Step<S1, S2> = Box<dyn Fn(&S1, &mut S2) -> Result<(), String>>;
+
+/**
+* Upgrade one version of a struct to the next, field by field, through pairs of optics.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* // (name, timeout in seconds) => (timeout in millis, (name, retries))
+* let migration = Migration::new()
+*     .field(optics!(_0), optics!(_1._0), |name: String| name)
+*     .try_field(optics!(_1), optics!(_0), |secs: i64| {
+*         if secs < 0 {
+*             Result::Err(format!("negative timeout {}", secs))
+*         } else {
+*             Result::Ok(secs as u64 * 1000)
+*         }
+*     });
+*
+* let v2: (u64, (String, u8)) = migration.migrate(&("db".to_string(), 3)).unwrap();
+* assert_eq!(v2, (3000, ("db".to_string(), 0)));
+*
+* let err = migration.migrate(&("db".to_string(), -1)).unwrap_err();
+* assert_eq!((err.step, err.message.as_str()), (1, "negative timeout -1"));
+* ```
+*/
+pub struct Migration<S1, S2> {
+    steps: Vec<Step<S1, S2>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationError {
+    /// the index of the failed step, in the order they were registered.
+    pub step: usize,
+    pub message: String,
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "migration step {} failed: {}", self.step, self.message)
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl<S1, S2> Default for Migration<S1, S2> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S1, S2> Migration<S1, S2> {
+    pub fn new() -> Self {
+        Self { steps: vec![] }
+    }
+
+    /// copy the focus of `from` into the focus of `to`, converted by `convert`.
+    pub fn field<Gt, St, F>(self, from: Gt, to: St, convert: F) -> Self
+    where
+        Gt: Getter<S1> + 'static,
+        St: Setter<S2> + 'static,
+        F: Fn(Gt::To) -> St::To + 'static,
+    {
+        self.try_field(from, to, move |a| Result::Ok(convert(a)))
+    }
+
+    /// like `field`, but the conversion may reject the old value.
+    pub fn try_field<Gt, St, F>(mut self, from: Gt, to: St, convert: F) -> Self
+    where
+        Gt: Getter<S1> + 'static,
+        St: Setter<S2> + 'static,
+        F: Fn(Gt::To) -> Result<St::To, String> + 'static,
+    {
+        self.steps.push(Box::new(move |old, new| {
+            to.set(new, convert(from.get(old))?);
+            Result::Ok(())
+        }));
+        self
+    }
+
+    /// run the steps over an existing new value, stops at the first failed step.
+    pub fn migrate_into(&self, old: &S1, new: &mut S2) -> Result<(), MigrationError> {
+        self.steps.iter().enumerate().try_for_each(|(step, f)| {
+            f(old, new).map_err(|message| MigrationError { step, message })
+        })
+    }
+
+    /// run the steps over `S2::default()`, the fields without a step keep their default.
+    pub fn migrate(&self, old: &S1) -> Result<S2, MigrationError>
+    where
+        S2: Default,
+    {
+        let mut new = S2::default();
+        self.migrate_into(old, &mut new)?;
+        Result::Ok(new)
+    }
+}
@@ -1,55 +1,690 @@
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/**
+* The end of an optic path. An optic value equals and hashes as its path,
+* so it can be the key of a map of subscriptions or deduplicated in a list of patches.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::collections::HashMap;
+*
+* let mut subscribers = HashMap::new();
+* subscribers.insert(optics!(_1._0), vec!["sidebar"]);
+* subscribers.entry(optics!(_1._0)).or_insert_with(Vec::new).push("header");
+* assert_eq!(subscribers[&optics!(_1._0)], vec!["sidebar", "header"]);
+*
+* let mut keys = vec![optics::_index(1, __), optics::_index(0, __), optics::_index(1, __)];
+* keys.sort_by_key(|o| o.0);
+* keys.dedup();
+* assert_eq!(keys, vec![optics::_index(0, __), optics::_index(1, __)]);
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct __;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Ok<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Err<Optic>(pub Optic);
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/**
+* The prism of `Option::Some`, it also goes through the references an `Option<&T>` or `Option<&mut T>` holds.
+* ## Example
+* ```
+* use lens_rs::*;
+* let mut point = (1, (2, 'a'));
+* let borrowed: Option<&(i32, (i32, char))> = Option::Some(&point);
+* assert_eq!(optics!(Some._1._0).preview(&borrowed), Option::Some(2));
+*
+* let mut borrowed: Option<&mut (i32, (i32, char))> = Option::Some(&mut point);
+* optics!(Some._1._0).set(&mut borrowed, 3);
+* assert_eq!(point, (1, (3, 'a')));
+*
+* let found: Result<&Vec<i32>, &String> = Result::Ok(&vec![1, 2]);
+* assert_eq!(optics!(Ok._mapped).traverse_ref(&found), vec![&1, &2]);
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Some<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct None<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _or_default<Optic>(pub Optic);
+/**
+* The value of a nested `Option<Option<T>>`, `Option<Result<T, E>>`, `Result<Option<T>, E>`
+* or `Result<Result<T, E>, E>`, as one prism through both layers.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut lookup: (&str, Result<Option<u16>, String>) = ("port", Result::Ok(Option::Some(80)));
+* *optics!(_1._flattened).pm_mut(&mut lookup).unwrap() += 8000;
+* assert_eq!(optics!(_1._flattened).preview(&lookup), Option::Some(8080));
+*
+* lookup.1 = Result::Ok(Option::None);
+* assert!(optics!(_1._flattened).pm_ref(&lookup).is_none());
+*
+* let nested: Option<Option<&str>> = optics!(_flattened).review("set");
+* assert_eq!(nested, Option::Some(Option::Some("set")));
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _flattened<Optic>(pub Optic);
+
+/**
+* The `V4` variant of an `IpAddr` or a `SocketAddr`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+*
+* let mut addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+* assert_eq!(optics!(_V4).preview(&addr).map(|v4| v4.port()), Option::Some(8080));
+* optics!(_V4).pm_mut(&mut addr).unwrap().set_port(9090);
+* assert_eq!(addr.port(), 9090);
+*
+* let ip: IpAddr = optics!(_V4).review(Ipv4Addr::LOCALHOST);
+* assert!(ip.is_loopback());
+* assert!(optics!(_V6).preview(&ip).is_none());
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _V4<Optic>(pub Optic);
+/**
+* The `V6` variant of an `IpAddr` or a `SocketAddr`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::net::IpAddr;
+*
+* let ip: IpAddr = "::1".parse().unwrap();
+* assert_eq!(optics!(_V6).preview(&ip).map(|v6| v6.segments()[7]), Option::Some(1));
+* assert!(optics!(_V4).preview(&ip).is_none());
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _V6<Optic>(pub Optic);
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct _0<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct _1<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct _2<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct _3<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct _4<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct _5<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct _6<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _7<Optic>(pub Optic);
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _8<Optic>(pub Optic);
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _9<Optic>(pub Optic);
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _10<Optic>(pub Optic);
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _11<Optic>(pub Optic);
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _12<Optic>(pub Optic);
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _13<Optic>(pub Optic);
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _14<Optic>(pub Optic);
+#[cfg(feature = "tuple16")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _15<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _16<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _17<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _18<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _19<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _20<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _21<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _22<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _23<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _24<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _25<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _26<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _27<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _28<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _29<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _30<Optic>(pub Optic);
+#[cfg(feature = "tuple32")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct _31<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[allow(non_camel_case_types)]
 pub struct _both<Optic>(pub Optic);
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[allow(non_camel_case_types)]
 pub struct _mapped<Optic>(pub Optic);
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/**
+* Traverse the elements of any container iterable by reference, e.g. a `VecDeque` or a `BTreeSet`,
+* it moves them out if the container is iterable by value too.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::collections::{BTreeSet, VecDeque};
+*
+* let mut queue = ("jobs", VecDeque::from(vec![(1, "build"), (2, "test")]));
+* optics!(_1._iterated._0).traverse_mut(&mut queue).into_iter().for_each(|id| *id *= 10);
+* assert_eq!(optics!(_1._iterated._1).traverse_ref(&queue), vec![&"build", &"test"]);
+* assert_eq!(optics!(_1._iterated._0).traverse(queue), vec![10, 20]);
+*
+* let tags: BTreeSet<&str> = vec!["b", "a"].into_iter().collect();
+* assert_eq!(optics!(_iterated).traverse_ref(&tags), vec![&"a", &"b"]);
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _iterated<Optic>(pub Optic);
+/**
+* The iso between a `Vec`, `VecDeque`, `LinkedList` or the chars of a `String` and the same reversed,
+* a write through it is reversed back, e.g. to push to the front or edit from the end.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut log = ("app", vec![1, 2, 3]);
+* assert_eq!(optics!(_1._rev).get(&log), vec![3, 2, 1]);
+* optics!(_1._rev).over(&mut log, &mut |v| v.push(0));
+* assert_eq!(log.1, vec![0, 1, 2, 3]);
+* optics!(_1._rev).set(&mut log, vec![5, 4]);
+* assert_eq!(log.1, vec![4, 5]);
+* let built: Vec<i32> = optics!(_rev).review(vec![1, 2]);
+* assert_eq!(built, vec![2, 1]);
+*
+* let mut word = "stressed".to_string();
+* assert_eq!(optics!(_rev).get(&word), "desserts");
+* optics!(_rev).over(&mut word, &mut |w| w.push('!'));
+* assert_eq!(word, "!stressed");
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _rev<Optic>(pub Optic);
+/**
+* The elements of a `Vec<Option<T>>` or a `Vec<Result<T, E>>` if they're all `Some` or `Ok`, none otherwise,
+* e.g. to update the fields of a form only once every one of them is valid.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut form = ("signup", vec![Option::Some(1), Option::Some(2)]);
+* optics!(_1._sequence).traverse_mut(&mut form).into_iter().for_each(|x| *x *= 10);
+* assert_eq!(form.1, vec![Option::Some(10), Option::Some(20)]);
+*
+* let parsed: Vec<Result<u8, String>> = vec![Result::Ok(1), Result::Err("x".to_string())];
+* assert!(optics!(_sequence).traverse_ref(&parsed).is_empty());
+*
+* let built: Vec<Option<i32>> = optics!(_sequence).review(3);
+* assert_eq!(optics!(_sequence).traverse(built), vec![3]);
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _sequence<Optic>(pub Optic);
+
+/**
+* The value in a `Box`. Like `Ok`, `Some`, `_mapped`, the tuple and the generated optics,
+* it goes through a `&S` or `&mut S` source as through `S`, so generic code can hold references.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* fn total<S, Tr: TraversalRef<S, To = i32>>(source: &S, optic: Tr) -> i32 {
+*     optic.traverse_ref(source).into_iter().sum()
+* }
+*
+* let mut config = (Box::new(vec![1, 2]), 3);
+* assert_eq!(total(&&config, optics!(_0._box._mapped)), 3);
+*
+* let mut borrowed = &mut config;
+* optics!(_0._box).view_mut(&mut borrowed).push(4);
+* assert_eq!(optics!(_0._box._mapped).traverse_ref(&borrowed), vec![&1, &2, &4]);
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[allow(non_camel_case_types)]
 pub struct _box<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[allow(non_camel_case_types)]
 pub struct _ref<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[allow(non_camel_case_types)]
 pub struct _mut<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[allow(non_camel_case_types)]
 pub struct _rc<Optic>(pub Optic);
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[allow(non_camel_case_types)]
 pub struct _arc<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _upgraded<Optic>(pub Optic);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _fetch<Optic>(pub Optic);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _utf8<Optic>(pub Optic);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _secs<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _secs_f64<Optic>(pub Optic);
+/**
+* The whole milliseconds of a `Duration`, like `_secs` and `_micros` a write keeps the finer part.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::time::Duration;
+*
+* let mut timeout = ("connect", Duration::new(1, 500_000_123));
+* assert_eq!(optics!(_1._millis).get(&timeout), 1500);
+* optics!(_1._millis).set(&mut timeout, 2500);
+* assert_eq!(timeout.1, Duration::new(2, 500_000_123));
+* let micros = optics!(_1._micros).get(&timeout);
+* optics!(_1._micros).set(&mut timeout, micros);
+* optics!(_1._secs).set(&mut timeout, 3);
+* assert_eq!(timeout.1, Duration::new(3, 500_000_123));
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _millis<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _micros<Optic>(pub Optic);
+/**
+* The `Duration` of a `SystemTime` since the Unix epoch, it has no focus before the epoch.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::time::{Duration, UNIX_EPOCH};
+*
+* let mut event = ("deploy", UNIX_EPOCH + Duration::from_secs(60));
+* assert_eq!(optics!(_1._since_epoch._secs).preview(&event), Option::Some(60));
+* optics!(_1._since_epoch._secs).set(&mut event, 120);
+* assert_eq!(event.1, UNIX_EPOCH + Duration::from_secs(120));
+*
+* optics!(_1._since_epoch).set(&mut event, Duration::MAX);  // out of range, unchanged
+* assert_eq!(event.1, UNIX_EPOCH + Duration::from_secs(120));
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _since_epoch<Optic>(pub Optic);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _ip<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _port<Optic>(pub Optic);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _extension<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _file_name<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _file_stem<Optic>(pub Optic);
+/**
+* The components of a `PathBuf` as one `Vec<OsString>`, the path is rebuilt from the whole list when it's set.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::path::PathBuf;
+*
+* let mut path = PathBuf::from("/srv/www/index.html");
+* let mut parts = optics!(_component_list).get(&path);
+* assert_eq!(parts.len(), 4);
+*
+* parts[2] = "static".into();
+* optics!(_component_list).set(&mut path, parts);
+* assert_eq!(path, PathBuf::from("/srv/static/index.html"));
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _component_list<Optic>(pub Optic);
+
+/**
+* The integer as a `NonZero*`, if it isn't zero. The integer in a `Wrapping` or a `Saturating` is its `_0`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::num::{NonZeroU32, Wrapping};
+*
+* let mut retries = (Wrapping(u32::MAX), 3u32);
+* *optics!(_0).view_mut(&mut retries) += Wrapping(1);
+* assert_eq!(*optics!(_0._0).view_ref(&retries), 0);
+* optics!(_0._0).set(&mut retries, 7);
+* assert_eq!(retries.0, Wrapping(7));
+*
+* assert_eq!(optics!(_1._nonzero).preview(&retries), NonZeroU32::new(3));
+* optics!(_1._nonzero).set(&mut retries, NonZeroU32::new(5).unwrap());
+* assert_eq!(retries.1, 5);
+* assert!(optics!(_nonzero).preview(&0u32).is_none());
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _nonzero<Optic>(pub Optic);
+
+/**
+* The start of a `Range`, a `RangeFrom` or a `RangeInclusive`, the last one can only be set as a whole.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut window = ("retry", 10..20);
+* *optics!(_1._start).view_mut(&mut window) = 5;
+* assert_eq!(window.1, 5..20);
+*
+* let mut pages = 1..=3;
+* optics!(_start).set(&mut pages, 2);
+* assert_eq!(*optics!(_start).view_ref(&pages), 2);
+* assert_eq!(pages, 2..=3);
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _start<Optic>(pub Optic);
+/**
+* The end of a `Range`, a `RangeTo`, a `RangeInclusive` or a `RangeToInclusive`.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut limit = ..=7;
+* *optics!(_end).view_mut(&mut limit) += 1;
+* assert_eq!(limit, ..=8);
+* assert_eq!(optics!(_end).view(0..4), 4);
+* ```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _end<Optic>(pub Optic);
+
+/// the value under a key of a JSON object, a TOML table or a YAML mapping.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _key<Optic>(pub String, pub Optic);
+/// the element at an index of a JSON, TOML or YAML array.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _index<Optic>(pub usize, pub Optic);
+/// the values of a `BTreeMap` whose keys are in the range, in key order.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _range<R, Optic>(pub R, pub Optic);
+/// the value in a `Box<dyn Any>` if it's a `T`, see `downcast`.
+#[allow(non_camel_case_types)]
+pub struct _downcast<T, Optic>(pub std::marker::PhantomData<fn() -> T>, pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _bool<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _number<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _integer<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _float<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _string<Optic>(pub Optic);
+/// a TOML date, time or offset date-time.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _datetime<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _array<Optic>(pub Optic);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _object<Optic>(pub Optic);
+
+/**
+* The bytes encoded in a base64 `String`, with padding, a string that isn't valid base64 has none.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut token = ("bearer", "aGk=".to_string());
+* assert_eq!(optics!(_1._base64).preview(&token), Option::Some(b"hi".to_vec()));
+* optics!(_1._base64).set(&mut token, b"hey".to_vec());
+* assert_eq!(token.1, "aGV5");
+*
+* let encoded: String = optics!(_base64).review(vec![0xff]);
+* assert_eq!(encoded, "/w==");
+* assert!(optics!(_base64).preview(&"not base64".to_string()).is_none());
+* ```
+*/
+#[cfg(feature = "codec")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _base64<Optic>(pub Optic);
+/**
+* The bytes encoded in a hex `String`, read in either case and written in lowercase.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut color = "FF8000".to_string();
+* assert_eq!(optics!(_hex).preview(&color), Option::Some(vec![0xff, 0x80, 0x00]));
+* optics!(_hex).set(&mut color, vec![0x00, 0x80, 0xff]);
+* assert_eq!(color, "0080ff");
+* assert!(optics!(_hex).preview(&"0x1".to_string()).is_none());
+* ```
+*/
+#[cfg(feature = "codec")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub struct _hex<Optic>(pub Optic);
 
 //impls
+// all the optics of a public field
+macro_rules! impl_field {
+    (<$($param:ident),*> $data:ty, $field:tt, $optic:ident, $to:ty) => {
+        impl<Tr, $($param),*> TraversalRef<$data> for $optic<Tr>
+        where
+            Tr: TraversalRef<$to>,
+        {
+            type To = Tr::To;
+
+            #[inline]
+            fn traverse_ref<'a>(&self, source: &'a $data) -> Vec<&'a Self::To> {
+                self.0.traverse_ref(&source.$field)
+            }
+
+            #[inline]
+            fn visit_ref<'a>(&self, source: &'a $data, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                self.0.visit_ref(&source.$field, f)
+            }
+        }
+
+        impl<Tr, $($param),*> TraversalMut<$data> for $optic<Tr>
+        where
+            Tr: TraversalMut<$to>,
+        {
+            #[inline]
+            fn traverse_mut<'a>(&self, source: &'a mut $data) -> Vec<&'a mut Self::To> {
+                self.0.traverse_mut(&mut source.$field)
+            }
+        }
+
+        impl<Tr, $($param),*> Traversal<$data> for $optic<Tr>
+        where
+            Tr: Traversal<$to>,
+        {
+            #[inline]
+            fn traverse(&self, source: $data) -> Vec<Self::To> {
+                self.0.traverse(source.$field)
+            }
+        }
+
+        impl<Pm, $($param),*> PrismRef<$data> for $optic<Pm>
+        where
+            Pm: PrismRef<$to>,
+        {
+            #[inline]
+            fn pm_ref<'a>(&self, source: &'a $data) -> Option<&'a Self::To> {
+                self.0.pm_ref(&source.$field)
+            }
+        }
+
+        impl<Pm, $($param),*> PrismMut<$data> for $optic<Pm>
+        where
+            Pm: PrismMut<$to>,
+        {
+            #[inline]
+            fn pm_mut<'a>(&self, source: &'a mut $data) -> Option<&'a mut Self::To> {
+                self.0.pm_mut(&mut source.$field)
+            }
+        }
+
+        impl<Pm, $($param),*> Prism<$data> for $optic<Pm>
+        where
+            Pm: Prism<$to>,
+        {
+            #[inline]
+            fn pm(&self, source: $data) -> Option<Self::To> {
+                self.0.pm(source.$field)
+            }
+        }
+
+        impl<Ls, $($param),*> LensRef<$data> for $optic<Ls>
+        where
+            Ls: LensRef<$to>,
+        {
+            #[inline]
+            fn view_ref<'a>(&self, source: &'a $data) -> &'a Self::To {
+                self.0.view_ref(&source.$field)
+            }
+        }
+
+        impl<Ls, $($param),*> LensMut<$data> for $optic<Ls>
+        where
+            Ls: LensMut<$to>,
+        {
+            #[inline]
+            fn view_mut<'a>(&self, source: &'a mut $data) -> &'a mut Self::To {
+                self.0.view_mut(&mut source.$field)
+            }
+        }
+
+        impl<Ls, $($param),*> Lens<$data> for $optic<Ls>
+        where
+            Ls: Lens<$to>,
+        {
+            #[inline]
+            fn view(&self, source: $data) -> Self::To {
+                self.0.view(source.$field)
+            }
+        }
+
+        impl<Pv, $($param),*> Preview<$data> for $optic<Pv>
+        where
+            Pv: Preview<$to>,
+        {
+            type To = Pv::To;
+
+            #[inline]
+            fn preview(&self, source: &$data) -> Option<Self::To> {
+                self.0.preview(&source.$field)
+            }
+        }
+
+        impl<Gt, $($param),*> Getter<$data> for $optic<Gt>
+        where
+            Gt: Getter<$to>,
+        {
+            #[inline]
+            fn get(&self, source: &$data) -> Self::To {
+                self.0.get(&source.$field)
+            }
+        }
+
+        impl<St, $($param),*> Setter<$data> for $optic<St>
+        where
+            St: Setter<$to>,
+        {
+            type To = St::To;
+
+            #[inline]
+            fn set(&self, source: &mut $data, value: Self::To) {
+                self.0.set(&mut source.$field, value)
+            }
+        }
+
+        impl<Ov, $($param),*> Over<$data> for $optic<Ov>
+        where
+            Ov: Over<$to>,
+        {
+            #[inline]
+            fn over(&self, source: &mut $data, f: &mut dyn FnMut(&mut Self::To)) {
+                self.0.over(&mut source.$field, f)
+            }
+        }
+    };
+}
+
 mod impl__ {
     /***********************************************************
      * impl for __
@@ -59,6 +694,7 @@ mod impl__ {
     impl<T> Review<T> for __ {
         type From = T;
 
+        #[inline]
         fn review(&self, from: Self::From) -> T {
             from
         }
@@ -66,58 +702,142 @@ mod impl__ {
 
     impl<T> TraversalRef<T> for __ {
         type To = T;
+        #[inline]
         fn traverse_ref<'a>(&self, source: &'a T) -> Vec<&'a Self::To> {
             vec![source]
         }
+
+        #[inline]
+        fn visit_ref<'a>(&self, source: &'a T, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+            f(source)
+        }
     }
 
     impl<T> TraversalMut<T> for __ {
+        #[inline]
         fn traverse_mut<'a>(&self, source: &'a mut T) -> Vec<&'a mut Self::To> {
             vec![source]
         }
     }
 
     impl<T> Traversal<T> for __ {
+        #[inline]
         fn traverse(&self, source: T) -> Vec<Self::To> {
             vec![source]
         }
     }
 
     impl<T> PrismRef<T> for __ {
+        #[inline]
         fn pm_ref<'a>(&self, source: &'a T) -> Option<&'a Self::To> {
             Option::Some(source)
         }
     }
 
     impl<T> PrismMut<T> for __ {
+        #[inline]
         fn pm_mut<'a>(&self, source: &'a mut T) -> Option<&'a mut Self::To> {
             Option::Some(source)
         }
     }
 
     impl<T> Prism<T> for __ {
+        #[inline]
         fn pm(&self, source: T) -> Option<Self::To> {
             Option::Some(source)
         }
     }
 
     impl<T> LensRef<T> for __ {
+        #[inline]
         fn view_ref<'a>(&self, source: &'a T) -> &'a Self::To {
             source
         }
     }
 
     impl<T> LensMut<T> for __ {
+        #[inline]
         fn view_mut<'a>(&self, source: &'a mut T) -> &'a mut Self::To {
             source
         }
     }
 
     impl<T> Lens<T> for __ {
+        #[inline]
         fn view(&self, source: T) -> Self::To {
             source
         }
     }
+
+    impl<T> LensAsyncRef<T> for __ {
+        type To = T;
+
+        #[inline]
+        fn view_async_ref<'a>(&'a self, source: &'a T) -> BoxFuture<'a, &'a Self::To> {
+            Box::pin(std::future::ready(source))
+        }
+    }
+
+    impl<T> LensAsyncMut<T> for __ {
+        #[inline]
+        fn view_async_mut<'a>(&'a self, source: &'a mut T) -> BoxFuture<'a, &'a mut Self::To> {
+            Box::pin(std::future::ready(source))
+        }
+    }
+
+    impl<T: Clone> Preview<T> for __ {
+        type To = T;
+
+        #[inline]
+        fn preview(&self, source: &T) -> Option<Self::To> {
+            Option::Some(source.clone())
+        }
+    }
+
+    impl<T: Clone> Getter<T> for __ {
+        #[inline]
+        fn get(&self, source: &T) -> Self::To {
+            source.clone()
+        }
+    }
+
+    impl<T> Setter<T> for __ {
+        type To = T;
+
+        #[inline]
+        fn set(&self, source: &mut T, value: Self::To) {
+            *source = value;
+        }
+    }
+
+    impl<T> Over<T> for __ {
+        #[inline]
+        fn over(&self, source: &mut T, f: &mut dyn FnMut(&mut Self::To)) {
+            f(source)
+        }
+    }
+
+    impl ConstOptic for __ {
+        const OPTIC: Self = __;
+    }
+
+    impl<Next> Compose<Next> for __ {
+        type Output = Next;
+
+        #[inline]
+        fn compose(self, then_optic: Next) -> Self::Output {
+            then_optic
+        }
+    }
+
+    impl<Next> std::ops::Shr<Next> for __ {
+        type Output = Next;
+
+        #[inline]
+        fn shr(self, then_optic: Next) -> Self::Output {
+            then_optic
+        }
+    }
 }
 
 mod impl_result {
@@ -132,6 +852,7 @@ mod impl_result {
     {
         type From = Rv::From;
 
+        #[inline]
         fn review(&self, from: Self::From) -> Result<T, E> {
             Result::Ok(self.0.review(from))
         }
@@ -142,18 +863,32 @@ mod impl_result {
         Tr: TraversalRef<T>,
     {
         type To = Tr::To;
+        #[inline]
         fn traverse_ref<'a>(&self, source: &'a Result<T, E>) -> Vec<&'a Self::To> {
             source
                 .into_iter()
                 .flat_map(|t| self.0.traverse_ref(t))
                 .collect()
         }
+
+        #[inline]
+        fn visit_ref<'a>(
+            &self,
+            source: &'a Result<T, E>,
+            f: &mut dyn FnMut(&'a Self::To) -> bool,
+        ) -> bool {
+            match source {
+                Result::Ok(t) => self.0.visit_ref(t, f),
+                Result::Err(_) => true,
+            }
+        }
     }
 
     impl<Tr, T, E> TraversalMut<Result<T, E>> for optics::Ok<Tr>
     where
         Tr: TraversalMut<T>,
     {
+        #[inline]
         fn traverse_mut<'a>(&self, source: &'a mut Result<T, E>) -> Vec<&'a mut Self::To> {
             source
                 .into_iter()
@@ -166,6 +901,7 @@ mod impl_result {
     where
         Tr: Traversal<T>,
     {
+        #[inline]
         fn traverse(&self, source: Result<T, E>) -> Vec<Self::To> {
             source
                 .into_iter()
@@ -178,6 +914,7 @@ mod impl_result {
     where
         Tr: Traversal<E>,
     {
+        #[inline]
         fn traverse(&self, source: Result<T, E>) -> Vec<Self::To> {
             source
                 .err()
@@ -192,6 +929,7 @@ mod impl_result {
         Tr: TraversalRef<E>,
     {
         type To = Tr::To;
+        #[inline]
         fn traverse_ref<'a>(&self, source: &'a Result<T, E>) -> Vec<&'a Self::To> {
             source
                 .as_ref()
@@ -200,12 +938,25 @@ mod impl_result {
                 .flat_map(|t| self.0.traverse_ref(t))
                 .collect()
         }
+
+        #[inline]
+        fn visit_ref<'a>(
+            &self,
+            source: &'a Result<T, E>,
+            f: &mut dyn FnMut(&'a Self::To) -> bool,
+        ) -> bool {
+            match source {
+                Result::Ok(_) => true,
+                Result::Err(e) => self.0.visit_ref(e, f),
+            }
+        }
     }
 
     impl<Tr, T, E> TraversalMut<Result<T, E>> for optics::Err<Tr>
     where
         Tr: TraversalMut<E>,
     {
+        #[inline]
         fn traverse_mut<'a>(&self, source: &'a mut Result<T, E>) -> Vec<&'a mut Self::To> {
             source
                 .as_mut()
@@ -220,6 +971,7 @@ mod impl_result {
     where
         Pm: PrismRef<T>,
     {
+        #[inline]
         fn pm_ref<'a>(&self, source: &'a Result<T, E>) -> Option<&'a Self::To> {
             source.as_ref().ok().and_then(|t| self.0.pm_ref(t))
         }
@@ -229,6 +981,7 @@ mod impl_result {
     where
         Pm: PrismMut<T>,
     {
+        #[inline]
         fn pm_mut<'a>(&self, source: &'a mut Result<T, E>) -> Option<&'a mut Self::To> {
             source.as_mut().ok().and_then(|t| self.0.pm_mut(t))
         }
@@ -238,6 +991,7 @@ mod impl_result {
     where
         Pm: Prism<T>,
     {
+        #[inline]
         fn pm(&self, source: Result<T, E>) -> Option<Self::To> {
             source.ok().and_then(|t| self.0.pm(t))
         }
@@ -249,6 +1003,7 @@ mod impl_result {
     {
         type From = Rv::From;
 
+        #[inline]
         fn review(&self, from: Self::From) -> Result<T, E> {
             Result::Err(self.0.review(from))
         }
@@ -258,6 +1013,7 @@ mod impl_result {
     where
         Pm: PrismRef<E>,
     {
+        #[inline]
         fn pm_ref<'a>(&self, source: &'a Result<T, E>) -> Option<&'a Self::To> {
             source.as_ref().err().and_then(|t| self.0.pm_ref(t))
         }
@@ -267,6 +1023,7 @@ mod impl_result {
     where
         Pm: PrismMut<E>,
     {
+        #[inline]
         fn pm_mut<'a>(&self, source: &'a mut Result<T, E>) -> Option<&'a mut Self::To> {
             source.as_mut().err().and_then(|t| self.0.pm_mut(t))
         }
@@ -276,10 +1033,63 @@ mod impl_result {
     where
         Pm: Prism<E>,
     {
+        #[inline]
         fn pm(&self, source: Result<T, E>) -> Option<Self::To> {
             source.err().and_then(|t| self.0.pm(t))
         }
     }
+
+    impl<Pv, T, E> Preview<Result<T, E>> for optics::Ok<Pv>
+    where
+        Pv: Preview<T>,
+    {
+        type To = Pv::To;
+
+        #[inline]
+        fn preview(&self, source: &Result<T, E>) -> Option<Self::To> {
+            source.as_ref().ok().and_then(|t| self.0.preview(t))
+        }
+    }
+
+    impl<St, T, E> Setter<Result<T, E>> for optics::Ok<St>
+    where
+        St: Setter<T>,
+    {
+        type To = St::To;
+
+        #[inline]
+        fn set(&self, source: &mut Result<T, E>, value: Self::To) {
+            if let Result::Ok(t) = source {
+                self.0.set(t, value)
+            }
+        }
+    }
+
+    impl<Pv, T, E> Preview<Result<T, E>> for optics::Err<Pv>
+    where
+        Pv: Preview<E>,
+    {
+        type To = Pv::To;
+
+        #[inline]
+        fn preview(&self, source: &Result<T, E>) -> Option<Self::To> {
+            source.as_ref().err().and_then(|e| self.0.preview(e))
+        }
+    }
+
+    impl<St, T, E> Setter<Result<T, E>> for optics::Err<St>
+    where
+        St: Setter<E>,
+    {
+        type To = St::To;
+
+        #[inline]
+        fn set(&self, source: &mut Result<T, E>, value: Self::To) {
+            if let Result::Err(e) = source {
+                self.0.set(e, value)
+            }
+        }
+    }
 }
 
 mod impl_some {
@@ -295,6 +1105,7 @@ mod impl_some {
     {
         type From = Rv::From;
 
+        #[inline]
         fn review(&self, from: Self::From) -> Option<T> {
             Option::Some(self.0.review(from))
         }
@@ -306,18 +1117,32 @@ mod impl_some {
     {
         type To = Tr::To;
 
+        #[inline]
         fn traverse_ref<'a>(&self, source: &'a Option<T>) -> Vec<&'a Self::To> {
             source
                 .into_iter()
                 .flat_map(|t| self.0.traverse_ref(t))
                 .collect()
         }
+
+        #[inline]
+        fn visit_ref<'a>(
+            &self,
+            source: &'a Option<T>,
+            f: &mut dyn FnMut(&'a Self::To) -> bool,
+        ) -> bool {
+            match source {
+                Option::Some(t) => self.0.visit_ref(t, f),
+                Option::None => true,
+            }
+        }
     }
 
     impl<Tr, T> TraversalMut<Option<T>> for optics::Some<Tr>
     where
         Tr: TraversalMut<T>,
     {
+        #[inline]
         fn traverse_mut<'a>(&self, source: &'a mut Option<T>) -> Vec<&'a mut Self::To> {
             source
                 .into_iter()
@@ -330,6 +1155,7 @@ mod impl_some {
     where
         Tr: Traversal<T>,
     {
+        #[inline]
         fn traverse(&self, source: Option<T>) -> Vec<Self::To> {
             source
                 .into_iter()
@@ -342,6 +1168,7 @@ mod impl_some {
     where
         Pm: PrismRef<T>,
     {
+        #[inline]
         fn pm_ref<'a>(&self, source: &'a Option<T>) -> Option<&'a Self::To> {
             source.as_ref().and_then(|t| self.0.pm_ref(t))
         }
@@ -351,6 +1178,7 @@ mod impl_some {
     where
         Pm: PrismMut<T>,
     {
+        #[inline]
         fn pm_mut<'a>(&self, source: &'a mut Option<T>) -> Option<&'a mut Self::To> {
             source.as_mut().and_then(|t| self.0.pm_mut(t))
         }
@@ -360,12 +1188,85 @@ mod impl_some {
     where
         Pm: Prism<T>,
     {
+        #[inline]
         fn pm(&self, source: Option<T>) -> Option<Self::To> {
             source.and_then(|t| self.0.pm(t))
         }
     }
 
+    impl<Pv, T> Preview<Option<T>> for optics::Some<Pv>
+    where
+        Pv: Preview<T>,
+    {
+        type To = Pv::To;
+
+        #[inline]
+        fn preview(&self, source: &Option<T>) -> Option<Self::To> {
+            source.as_ref().and_then(|t| self.0.preview(t))
+        }
+    }
+
+    impl<St, T> Setter<Option<T>> for optics::Some<St>
+    where
+        St: Setter<T>,
+    {
+        type To = St::To;
+
+        #[inline]
+        fn set(&self, source: &mut Option<T>, value: Self::To) {
+            if let Option::Some(t) = source {
+                self.0.set(t, value)
+            }
+        }
+    }
+
+    impl<Pv, T> Preview<Option<T>> for _or_default<Pv>
+    where
+        Pv: Preview<T>,
+        T: Default,
+    {
+        type To = Pv::To;
+
+        #[inline]
+        fn preview(&self, source: &Option<T>) -> Option<Self::To> {
+            match source {
+                Option::Some(t) => self.0.preview(t),
+                Option::None => self.0.preview(&T::default()),
+            }
+        }
+    }
+
+    impl<Gt, T> Getter<Option<T>> for _or_default<Gt>
+    where
+        Gt: Getter<T>,
+        T: Default,
+    {
+        #[inline]
+        fn get(&self, source: &Option<T>) -> Self::To {
+            match source {
+                Option::Some(t) => self.0.get(t),
+                Option::None => self.0.get(&T::default()),
+            }
+        }
+    }
 
+    impl<St, T> Setter<Option<T>> for _or_default<St>
+    where
+        St: Setter<T>,
+        T: Default + PartialEq,
+    {
+        type To = St::To;
+
+        /// writing the default value back leaves `None`.
+        #[inline]
+        fn set(&self, source: &mut Option<T>, value: Self::To) {
+            let mut t = source.take().unwrap_or_default();
+            self.0.set(&mut t, value);
+            if t != T::default() {
+                *source = Option::Some(t);
+            }
+        }
+    }
 
     impl<Rv, T> Review<Option<T>> for optics::None<Rv>
     where
@@ -373,151 +1274,423 @@ mod impl_some {
     {
         type From = ();
 
+        #[inline]
         fn review(&self, _from: Self::From) -> Option<T> {
             Option::None
         }
     }
 }
 
-mod impl_tuples {
-
+mod impl_flattened {
     /***********************************************************
-     * impl for tuple
+     * impl for Option<Option<T>>, Option<Result<T, E>>, ...
      ************************************************************/
+
     use crate::*;
 
-    macro_rules! impl_tuple {
-        ({$($param:ident)*}, $field:tt, $optic:ident, $to:ident) => {
-            impl<Tr, $($param,)*> TraversalRef<($($param,)*)> for $optic<Tr>
+    macro_rules! impl_flattened {
+        (<$($param:ident),*> $data:ty, $outer:path, $inner:path, $to:ident) => {
+            impl<Rv, $($param),*> Review<$data> for _flattened<Rv>
+            where
+                Rv: Review<$to>,
+            {
+                type From = Rv::From;
+
+                #[inline]
+                fn review(&self, from: Self::From) -> $data {
+                    $outer($inner(self.0.review(from)))
+                }
+            }
+
+            impl<Tr, $($param),*> TraversalRef<$data> for _flattened<Tr>
             where
                 Tr: TraversalRef<$to>,
             {
                 type To = Tr::To;
 
-                fn traverse_ref<'a>(&self, source: &'a ($($param,)*)) -> Vec<&'a Self::To> {
-                    self.0.traverse_ref(&source.$field)
+                #[inline]
+                fn traverse_ref<'a>(&self, source: &'a $data) -> Vec<&'a Self::To> {
+                    match source {
+                        $outer($inner(t)) => self.0.traverse_ref(t),
+                        _ => vec![],
+                    }
+                }
+
+                #[inline]
+                fn visit_ref<'a>(
+                    &self,
+                    source: &'a $data,
+                    f: &mut dyn FnMut(&'a Self::To) -> bool,
+                ) -> bool {
+                    match source {
+                        $outer($inner(t)) => self.0.visit_ref(t, f),
+                        _ => true,
+                    }
                 }
             }
 
-            impl<Tr, $($param,)*> TraversalMut<($($param,)*)> for $optic<Tr>
+            impl<Tr, $($param),*> TraversalMut<$data> for _flattened<Tr>
             where
                 Tr: TraversalMut<$to>,
             {
-                fn traverse_mut<'a>(&self, source: &'a mut ($($param,)*)) -> Vec<&'a mut Self::To> {
-                    self.0.traverse_mut(&mut source.$field)
+                #[inline]
+                fn traverse_mut<'a>(&self, source: &'a mut $data) -> Vec<&'a mut Self::To> {
+                    match source {
+                        $outer($inner(t)) => self.0.traverse_mut(t),
+                        _ => vec![],
+                    }
                 }
             }
 
-            impl<Tr, $($param,)*> Traversal<($($param,)*)> for $optic<Tr>
+            impl<Tr, $($param),*> Traversal<$data> for _flattened<Tr>
             where
                 Tr: Traversal<$to>,
             {
-                fn traverse(&self, source: ($($param,)*)) -> Vec<Self::To> {
-                    self.0.traverse(source.$field)
+                #[inline]
+                fn traverse(&self, source: $data) -> Vec<Self::To> {
+                    match source {
+                        $outer($inner(t)) => self.0.traverse(t),
+                        _ => vec![],
+                    }
                 }
             }
 
-            impl<Pm, $($param,)*> PrismRef<($($param,)*)> for $optic<Pm>
+            impl<Pm, $($param),*> PrismRef<$data> for _flattened<Pm>
             where
                 Pm: PrismRef<$to>,
             {
-
-                fn pm_ref<'a>(&self, source: &'a ($($param,)*)) -> Option<&'a Self::To> {
-                    self.0.pm_ref(&source.$field)
+                #[inline]
+                fn pm_ref<'a>(&self, source: &'a $data) -> Option<&'a Self::To> {
+                    match source {
+                        $outer($inner(t)) => self.0.pm_ref(t),
+                        _ => Option::None,
+                    }
                 }
             }
 
-            impl<Pm, $($param,)*> PrismMut<($($param,)*)> for $optic<Pm>
+            impl<Pm, $($param),*> PrismMut<$data> for _flattened<Pm>
             where
                 Pm: PrismMut<$to>,
             {
-
-                fn pm_mut<'a>(&self, source: &'a mut ($($param,)*)) -> Option<&'a mut Self::To> {
-                    self.0.pm_mut(&mut source.$field)
+                #[inline]
+                fn pm_mut<'a>(&self, source: &'a mut $data) -> Option<&'a mut Self::To> {
+                    match source {
+                        $outer($inner(t)) => self.0.pm_mut(t),
+                        _ => Option::None,
+                    }
                 }
             }
 
-            impl<Pm, $($param,)*> Prism<($($param,)*)> for $optic<Pm>
+            impl<Pm, $($param),*> Prism<$data> for _flattened<Pm>
             where
                 Pm: Prism<$to>,
             {
-
-                fn pm(&self, source: ($($param,)*)) -> Option<Self::To> {
-                    self.0.pm(source.$field)
+                #[inline]
+                fn pm(&self, source: $data) -> Option<Self::To> {
+                    match source {
+                        $outer($inner(t)) => self.0.pm(t),
+                        _ => Option::None,
+                    }
                 }
             }
 
-            impl<Ls, $($param,)* > LensRef<($($param,)*)> for $optic<Ls>
+            impl<Pv, $($param),*> Preview<$data> for _flattened<Pv>
             where
-                Ls: LensRef<$to>,
+                Pv: Preview<$to>,
             {
-                fn view_ref<'a>(&self, source: &'a ($($param,)*)) -> &'a Self::To {
-                    self.0.view_ref(&source.$field)
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &$data) -> Option<Self::To> {
+                    match source {
+                        $outer($inner(t)) => self.0.preview(t),
+                        _ => Option::None,
+                    }
                 }
             }
 
-            impl<Ls, $($param,)* > LensMut<($($param,)*)> for $optic<Ls>
+            impl<St, $($param),*> Setter<$data> for _flattened<St>
             where
-                Ls: LensMut<$to>,
+                St: Setter<$to>,
             {
-                fn view_mut<'a>(&self, source: &'a mut ($($param,)*)) -> &'a mut Self::To {
-                    self.0.view_mut(&mut source.$field)
-                }
-            }
+                type To = St::To;
 
-            impl<Ls, $($param,)* > Lens<($($param,)*)> for $optic<Ls>
-            where
-                Ls: Lens<$to>,
-            {
-                fn view(&self, source: ($($param,)*)) -> Self::To {
-                    self.0.view(source.$field)
+                #[inline]
+                fn set(&self, source: &mut $data, value: Self::To) {
+                    if let $outer($inner(t)) = source {
+                        self.0.set(t, value)
+                    }
                 }
             }
-
-        }
+        };
     }
 
-    impl_tuple!({ A }, 0, _0, A);
+    impl_flattened!(<T> Option<Option<T>>, Option::Some, Option::Some, T);
+    impl_flattened!(<T, E> Option<Result<T, E>>, Option::Some, Result::Ok, T);
+    impl_flattened!(<T, E> Result<Option<T>, E>, Result::Ok, Option::Some, T);
+    impl_flattened!(<T, E> Result<Result<T, E>, E>, Result::Ok, Result::Ok, T);
+}
 
-    impl_tuple!({A B}, 0, _0, A);
-    impl_tuple!({A B}, 1, _1, B);
+mod impl_tuples {
 
-    impl_tuple!({A B C}, 0, _0, A);
-    impl_tuple!({A B C}, 1, _1, B);
-    impl_tuple!({A B C}, 2, _2, C);
-
-    impl_tuple!({A B C D}, 0, _0, A);
-    impl_tuple!({A B C D}, 1, _1, B);
-    impl_tuple!({A B C D}, 2, _2, C);
-    impl_tuple!({A B C D}, 3, _3, D);
-
-    impl_tuple!({A B C D E}, 0, _0, A);
-    impl_tuple!({A B C D E}, 1, _1, B);
-    impl_tuple!({A B C D E}, 2, _2, C);
-    impl_tuple!({A B C D E}, 3, _3, D);
-    impl_tuple!({A B C D E}, 4, _4, E);
-
-    impl_tuple!({A B C D E F}, 0, _0, A);
-    impl_tuple!({A B C D E F}, 1, _1, B);
-    impl_tuple!({A B C D E F}, 2, _2, C);
-    impl_tuple!({A B C D E F}, 3, _3, D);
-    impl_tuple!({A B C D E F}, 4, _4, E);
-    impl_tuple!({A B C D E F}, 5, _5, F);
-
-    impl_tuple!({A B C D E F G}, 0, _0, A);
-    impl_tuple!({A B C D E F G}, 1, _1, B);
-    impl_tuple!({A B C D E F G}, 2, _2, C);
-    impl_tuple!({A B C D E F G}, 3, _3, D);
-    impl_tuple!({A B C D E F G}, 4, _4, E);
-    impl_tuple!({A B C D E F G}, 5, _5, F);
-    impl_tuple!({A B C D E F G}, 6, _6, G);
+    /***********************************************************
+     * impl for tuple
+     ************************************************************/
+    use crate::*;
 
-    impl<Rv, A> Review<(A,)> for _0<Rv>
-    where
-        Rv: Review<A>,
-    {
+    macro_rules! impl_tuple {
+        ({$($param:ident)*}, $field:tt, $optic:ident, $to:ident) => {
+            impl<Tr, $($param,)*> TraversalRef<($($param,)*)> for $optic<Tr>
+            where
+                Tr: TraversalRef<$to>,
+            {
+                type To = Tr::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, source: &'a ($($param,)*)) -> Vec<&'a Self::To> {
+                    self.0.traverse_ref(&source.$field)
+                }
+
+                #[inline]
+                fn visit_ref<'a>(&self, source: &'a ($($param,)*), f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    self.0.visit_ref(&source.$field, f)
+                }
+            }
+
+            impl<Tr, $($param,)*> TraversalMut<($($param,)*)> for $optic<Tr>
+            where
+                Tr: TraversalMut<$to>,
+            {
+                #[inline]
+                fn traverse_mut<'a>(&self, source: &'a mut ($($param,)*)) -> Vec<&'a mut Self::To> {
+                    self.0.traverse_mut(&mut source.$field)
+                }
+            }
+
+            impl<Tr, $($param,)*> Traversal<($($param,)*)> for $optic<Tr>
+            where
+                Tr: Traversal<$to>,
+            {
+                #[inline]
+                fn traverse(&self, source: ($($param,)*)) -> Vec<Self::To> {
+                    self.0.traverse(source.$field)
+                }
+            }
+
+            impl<Pm, $($param,)*> PrismRef<($($param,)*)> for $optic<Pm>
+            where
+                Pm: PrismRef<$to>,
+            {
+
+                #[inline]
+                fn pm_ref<'a>(&self, source: &'a ($($param,)*)) -> Option<&'a Self::To> {
+                    self.0.pm_ref(&source.$field)
+                }
+            }
+
+            impl<Pm, $($param,)*> PrismMut<($($param,)*)> for $optic<Pm>
+            where
+                Pm: PrismMut<$to>,
+            {
+
+                #[inline]
+                fn pm_mut<'a>(&self, source: &'a mut ($($param,)*)) -> Option<&'a mut Self::To> {
+                    self.0.pm_mut(&mut source.$field)
+                }
+            }
+
+            impl<Pm, $($param,)*> Prism<($($param,)*)> for $optic<Pm>
+            where
+                Pm: Prism<$to>,
+            {
+
+                #[inline]
+                fn pm(&self, source: ($($param,)*)) -> Option<Self::To> {
+                    self.0.pm(source.$field)
+                }
+            }
+
+            impl<Ls, $($param,)* > LensRef<($($param,)*)> for $optic<Ls>
+            where
+                Ls: LensRef<$to>,
+            {
+                #[inline]
+                fn view_ref<'a>(&self, source: &'a ($($param,)*)) -> &'a Self::To {
+                    self.0.view_ref(&source.$field)
+                }
+            }
+
+            impl<Ls, $($param,)* > LensMut<($($param,)*)> for $optic<Ls>
+            where
+                Ls: LensMut<$to>,
+            {
+                #[inline]
+                fn view_mut<'a>(&self, source: &'a mut ($($param,)*)) -> &'a mut Self::To {
+                    self.0.view_mut(&mut source.$field)
+                }
+            }
+
+            impl<Ls, $($param,)* > Lens<($($param,)*)> for $optic<Ls>
+            where
+                Ls: Lens<$to>,
+            {
+                #[inline]
+                fn view(&self, source: ($($param,)*)) -> Self::To {
+                    self.0.view(source.$field)
+                }
+            }
+
+            impl<Pv, $($param,)* > Preview<($($param,)*)> for $optic<Pv>
+            where
+                Pv: Preview<$to>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &($($param,)*)) -> Option<Self::To> {
+                    self.0.preview(&source.$field)
+                }
+            }
+
+            impl<Gt, $($param,)* > Getter<($($param,)*)> for $optic<Gt>
+            where
+                Gt: Getter<$to>,
+            {
+                #[inline]
+                fn get(&self, source: &($($param,)*)) -> Self::To {
+                    self.0.get(&source.$field)
+                }
+            }
+
+            impl<St, $($param,)* > Setter<($($param,)*)> for $optic<St>
+            where
+                St: Setter<$to>,
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut ($($param,)*), value: Self::To) {
+                    self.0.set(&mut source.$field, value)
+                }
+            }
+
+            impl<Ov, $($param,)* > Over<($($param,)*)> for $optic<Ov>
+            where
+                Ov: Over<$to>,
+            {
+                #[inline]
+                fn over(&self, source: &mut ($($param,)*), f: &mut dyn FnMut(&mut Self::To)) {
+                    self.0.over(&mut source.$field, f)
+                }
+            }
+
+            impl<Ls, $($param,)* > LensAsyncRef<($($param,)*)> for $optic<Ls>
+            where
+                Ls: LensAsyncRef<$to>,
+            {
+                type To = Ls::To;
+
+                #[inline]
+                fn view_async_ref<'a>(&'a self, source: &'a ($($param,)*)) -> BoxFuture<'a, &'a Self::To> {
+                    self.0.view_async_ref(&source.$field)
+                }
+            }
+
+            impl<Ls, $($param,)* > LensAsyncMut<($($param,)*)> for $optic<Ls>
+            where
+                Ls: LensAsyncMut<$to>,
+            {
+                #[inline]
+                fn view_async_mut<'a>(&'a self, source: &'a mut ($($param,)*)) -> BoxFuture<'a, &'a mut Self::To> {
+                    self.0.view_async_mut(&mut source.$field)
+                }
+            }
+
+        }
+    }
+
+    impl_tuple!({ A }, 0, _0, A);
+
+    impl_tuple!({A B}, 0, _0, A);
+    impl_tuple!({A B}, 1, _1, B);
+
+    // `impl_tuple` for every field of a tuple
+    #[allow(unused_macros)]
+    macro_rules! impl_tuple_fields {
+        ($params:tt $(($field:tt, $optic:ident, $to:ident))*) => {
+            $(impl_tuple!($params, $field, $optic, $to);)*
+        };
+    }
+
+    #[cfg(feature = "tuple4")]
+    impl_tuple_fields!({A B C} (0, _0, A) (1, _1, B) (2, _2, C));
+    #[cfg(feature = "tuple4")]
+    impl_tuple_fields!({A B C D} (0, _0, A) (1, _1, B) (2, _2, C) (3, _3, D));
+    #[cfg(feature = "tuple7")]
+    impl_tuple_fields!({A B C D E} (0, _0, A) (1, _1, B) (2, _2, C) (3, _3, D) (4, _4, E));
+    #[cfg(feature = "tuple7")]
+    impl_tuple_fields!({A B C D E F} (0, _0, A) (1, _1, B) (2, _2, C) (3, _3, D) (4, _4, E) (5, _5, F));
+    #[cfg(feature = "tuple7")]
+    impl_tuple_fields!({A B C D E F G} (0, _0, A) (1, _1, B) (2, _2, C) (3, _3, D) (4, _4, E) (5, _5, F) (6, _6, G));
+
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7));
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8));
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9));
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10));
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11));
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12));
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13));
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14));
+    #[cfg(feature = "tuple16")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23) (24, _24, T24));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24 T25} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23) (24, _24, T24) (25, _25, T25));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24 T25 T26} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23) (24, _24, T24) (25, _25, T25) (26, _26, T26));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24 T25 T26 T27} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23) (24, _24, T24) (25, _25, T25) (26, _26, T26) (27, _27, T27));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24 T25 T26 T27 T28} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23) (24, _24, T24) (25, _25, T25) (26, _26, T26) (27, _27, T27) (28, _28, T28));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24 T25 T26 T27 T28 T29} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23) (24, _24, T24) (25, _25, T25) (26, _26, T26) (27, _27, T27) (28, _28, T28) (29, _29, T29));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24 T25 T26 T27 T28 T29 T30} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23) (24, _24, T24) (25, _25, T25) (26, _26, T26) (27, _27, T27) (28, _28, T28) (29, _29, T29) (30, _30, T30));
+    #[cfg(feature = "tuple32")]
+    impl_tuple_fields!({T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T20 T21 T22 T23 T24 T25 T26 T27 T28 T29 T30 T31} (0, _0, T0) (1, _1, T1) (2, _2, T2) (3, _3, T3) (4, _4, T4) (5, _5, T5) (6, _6, T6) (7, _7, T7) (8, _8, T8) (9, _9, T9) (10, _10, T10) (11, _11, T11) (12, _12, T12) (13, _13, T13) (14, _14, T14) (15, _15, T15) (16, _16, T16) (17, _17, T17) (18, _18, T18) (19, _19, T19) (20, _20, T20) (21, _21, T21) (22, _22, T22) (23, _23, T23) (24, _24, T24) (25, _25, T25) (26, _26, T26) (27, _27, T27) (28, _28, T28) (29, _29, T29) (30, _30, T30) (31, _31, T31));
+
+    impl<Rv, A> Review<(A,)> for _0<Rv>
+    where
+        Rv: Review<A>,
+    {
         type From = Rv::From;
 
+        #[inline]
         fn review(&self, from: Self::From) -> (A,) {
             (self.0.review(from),)
         }
@@ -531,17 +1704,24 @@ mod impl_tuples {
             {
                 type To = Tr::To;
 
+                #[inline]
                 fn traverse_ref<'a>(&self, source: &'a $tuple) -> Vec<&'a Self::To> {
                     let mut vec = vec![];
                     $(vec.extend(self.0.traverse_ref(&source.$fields));)*
                     vec
                 }
+
+                #[inline]
+                fn visit_ref<'a>(&self, source: &'a $tuple, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    true $(&& self.0.visit_ref(&source.$fields, f))*
+                }
             }
 
             impl<Tr, $param> TraversalMut<$tuple> for _both<Tr>
             where
                 Tr: TraversalMut<$param>
             {
+                #[inline]
                 fn traverse_mut<'a>(&self, source: &'a mut $tuple) -> Vec<&'a mut Self::To> {
                     let mut vec = vec![];
                     $(vec.extend(self.0.traverse_mut(&mut source.$fields));)*
@@ -553,6 +1733,7 @@ mod impl_tuples {
             where
                 Tr: Traversal<$param>
             {
+                #[inline]
                 fn traverse(&self, source: $tuple) -> Vec<Self::To> {
                     let mut vec = vec![];
                     $(vec.extend(self.0.traverse(source.$fields));)*
@@ -564,16 +1745,72 @@ mod impl_tuples {
 
     impl_both!(<A> (A,), 0);
     impl_both!(<A> (A, A), 0, 1);
+    #[cfg(feature = "tuple4")]
     impl_both!(<A> (A, A, A), 0, 1, 2);
+    #[cfg(feature = "tuple4")]
     impl_both!(<A> (A, A, A, A), 0, 1, 2, 3);
+    #[cfg(feature = "tuple7")]
     impl_both!(<A> (A, A, A, A, A), 0, 1, 2, 3, 4);
+    #[cfg(feature = "tuple7")]
     impl_both!(<A> (A, A, A, A, A, A), 0, 1, 2, 3, 4, 5);
+    #[cfg(feature = "tuple7")]
     impl_both!(<A> (A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14);
+    #[cfg(feature = "tuple16")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30);
+    #[cfg(feature = "tuple32")]
+    impl_both!(<A> (A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A, A), 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31);
 
     impl<Pm, A> PrismRef<(A,)> for _both<Pm>
     where
         Pm: PrismRef<A>,
     {
+        #[inline]
         fn pm_ref<'a>(&self, source: &'a (A,)) -> Option<&'a Self::To> {
             self.0.pm_ref(&source.0)
         }
@@ -583,6 +1820,7 @@ mod impl_tuples {
     where
         Pm: PrismMut<A>,
     {
+        #[inline]
         fn pm_mut<'a>(&self, source: &'a mut (A,)) -> Option<&'a mut Self::To> {
             self.0.pm_mut(&mut source.0)
         }
@@ -592,6 +1830,7 @@ mod impl_tuples {
     where
         Pm: Prism<A>,
     {
+        #[inline]
         fn pm(&self, source: (A,)) -> Option<Self::To> {
             self.0.pm(source.0)
         }
@@ -601,6 +1840,7 @@ mod impl_tuples {
     where
         Ls: LensRef<A>,
     {
+        #[inline]
         fn view_ref<'a>(&self, source: &'a (A,)) -> &'a Self::To {
             self.0.view_ref(&source.0)
         }
@@ -610,6 +1850,7 @@ mod impl_tuples {
     where
         Ls: LensMut<A>,
     {
+        #[inline]
         fn view_mut<'a>(&self, source: &'a mut (A,)) -> &'a mut Self::To {
             self.0.view_mut(&mut source.0)
         }
@@ -619,6 +1860,7 @@ mod impl_tuples {
     where
         Ls: Lens<A>,
     {
+        #[inline]
         fn view(&self, source: (A,)) -> Self::To {
             self.0.view(source.0)
         }
@@ -630,6 +1872,7 @@ mod impl_tuples {
     {
         type From = Rv::From;
 
+        #[inline]
         fn review(&self, from: Self::From) -> (A,) {
             (self.0.review(from),)
         }
@@ -651,15 +1894,22 @@ mod impl_iters {
             {
                 type To = Tr::To;
 
+                #[inline]
                 fn traverse_ref<'a>(&self, source: &'a $iter) -> Vec<&'a Self::To> {
                     source.into_iter().flat_map(|t| self.0.traverse_ref(t)).collect()
                 }
+
+                #[inline]
+                fn visit_ref<'a>(&self, source: &'a $iter, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    source.into_iter().all(|t| self.0.visit_ref(t, f))
+                }
             }
 
             impl<Tr, $($param,)*> TraversalMut<$iter> for _mapped<Tr>
             where
                 Tr: TraversalMut<<$iter as IntoIterator>::Item>,
             {
+                #[inline]
                 fn traverse_mut<'a>(&self, source: &'a mut $iter) -> Vec<&'a mut Self::To> {
                     source
                         .into_iter()
@@ -672,6 +1922,7 @@ mod impl_iters {
             where
                 Tr: Traversal<<$iter as IntoIterator>::Item>,
             {
+                #[inline]
                 fn traverse(&self, source: $iter) -> Vec<Self::To> {
                     source
                         .into_iter()
@@ -685,146 +1936,1954 @@ mod impl_iters {
     impl_iter!(<T> Vec<T>);
     impl_iter!(<T> VecDeque<T>);
     impl_iter!(<T> LinkedList<T>);
-}
 
-mod impl_ptr {
-    use crate::*;
-    use std::ops::Deref;
-    use std::rc::Rc;
-    use std::sync::Arc;
+    impl<Tr, C, A> TraversalRef<C> for _iterated<Tr>
+    where
+        for<'b> &'b C: IntoIterator<Item = &'b A>,
+        Tr: TraversalRef<A>,
+    {
+        type To = Tr::To;
 
-    macro_rules! impl_ref {
-        (<$($life:lifetime),*; $($param:ident),*> $ptr:ty, $optic:ident) => {
-            impl<$($life,)* $($param,)* Tr> TraversalRef<$ptr> for $optic<Tr>
-            where
-                    Tr: TraversalRef<<$ptr as Deref>::Target>,
-            {
-                type To = Tr::To;
+        #[inline]
+        fn traverse_ref<'a>(&self, source: &'a C) -> Vec<&'a Self::To> {
+            source.into_iter().flat_map(|t| self.0.traverse_ref(t)).collect()
+        }
 
-                fn traverse_ref<'a>(&self, source: &'a $ptr) -> Vec<&'a Self::To> {
-                    self.0.traverse_ref(source)
-                }
-            }
+        #[inline]
+        fn visit_ref<'a>(&self, source: &'a C, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+            source.into_iter().all(|t| self.0.visit_ref(t, f))
+        }
+    }
 
-            impl<$($life,)* $($param,)* Pm> PrismRef<$ptr> for $optic<Pm>
-                where
-                    Pm: PrismRef<<$ptr as Deref>::Target>,
-            {
-                fn pm_ref<'a>(&self, source: &'a $ptr) -> Option<&'a Self::To> {
-                    self.0.pm_ref(source)
-                }
-            }
+    impl<Tr, C, A> TraversalMut<C> for _iterated<Tr>
+    where
+        for<'b> &'b C: IntoIterator<Item = &'b A>,
+        for<'b> &'b mut C: IntoIterator<Item = &'b mut A>,
+        Tr: TraversalMut<A>,
+    {
+        #[inline]
+        fn traverse_mut<'a>(&self, source: &'a mut C) -> Vec<&'a mut Self::To> {
+            source
+                .into_iter()
+                .flat_map(|t| self.0.traverse_mut(t))
+                .collect()
+        }
+    }
 
-            impl<$($life,)* $($param,)* Ls> LensRef<$ptr> for $optic<Ls>
-                where
-                    Ls: LensRef<<$ptr as Deref>::Target>
-            {
-                fn view_ref<'a>(&self, source: &'a $ptr) -> &'a Self::To {
-                    self.0.view_ref(source)
-                }
-            }
+    impl<Tr, C, A> Traversal<C> for _iterated<Tr>
+    where
+        for<'b> &'b C: IntoIterator<Item = &'b A>,
+        for<'b> &'b mut C: IntoIterator<Item = &'b mut A>,
+        C: IntoIterator<Item = A>,
+        Tr: Traversal<A>,
+    {
+        #[inline]
+        fn traverse(&self, source: C) -> Vec<Self::To> {
+            source
+                .into_iter()
+                .flat_map(|t| self.0.traverse(t))
+                .collect()
         }
     }
+}
 
-    macro_rules! impl_mut {
-        (<$($life:lifetime),*; $($param:ident),*> $ptr:ty, $optic:ident) => {
-            impl<$($life,)* $($param,)* Tr> TraversalMut<$ptr> for $optic<Tr>
-            where
-                Tr: TraversalMut<<$ptr as Deref>::Target>,
-            {
-                fn traverse_mut<'a>(&self, source: &'a mut $ptr) -> Vec<&'a mut Self::To> {
-                    self.0.traverse_mut(source)
-                }
-            }
+mod impl_range_of_map {
+    /***********************************************************
+     * impl for the key ranges of BTreeMap
+     ************************************************************/
 
-            impl<$($life,)* $($param,)* Pm> PrismMut<$ptr> for $optic<Pm>
-            where
-                Pm: PrismMut<<$ptr as Deref>::Target>,
-            {
-                fn pm_mut<'a>(&self, source: &'a mut $ptr) -> Option<&'a mut Self::To> {
-                    self.0.pm_mut(source)
-                }
-            }
+    use crate::*;
+    use std::collections::BTreeMap;
+    use std::ops::{Bound, RangeBounds};
 
-            impl<$($life,)* $($param,)* Ls> LensMut<$ptr> for $optic<Ls>
-            where
-                Ls: LensMut<<$ptr as Deref>::Target>
-            {
-                fn view_mut<'a>(&self, source: &'a mut $ptr) -> &'a mut Self::To {
-                    self.0.view_mut(source)
-                }
-            }
-        }
+    fn bounds<R: RangeBounds<K>, K>(range: &R) -> (Bound<&K>, Bound<&K>) {
+        (range.start_bound(), range.end_bound())
     }
 
-    impl<Rv, T> Review<Box<T>> for _box<Rv>
+    impl<Tr, R, K, V> TraversalRef<BTreeMap<K, V>> for _range<R, Tr>
     where
-        Rv: Review<T>,
+        Tr: TraversalRef<V>,
+        R: RangeBounds<K>,
+        K: Ord,
     {
-        type From = Rv::From;
+        type To = Tr::To;
 
-        fn review(&self, from: Self::From) -> Box<T> {
-            Box::new(self.0.review(from))
+        #[inline]
+        fn traverse_ref<'a>(&self, source: &'a BTreeMap<K, V>) -> Vec<&'a Self::To> {
+            source
+                .range(bounds(&self.0))
+                .flat_map(|(_, v)| self.1.traverse_ref(v))
+                .collect()
         }
-    }
 
-    impl<Tr, T> Traversal<Box<T>> for _box<Tr>
-    where
-        Tr: Traversal<T>,
-    {
-        fn traverse(&self, source: Box<T>) -> Vec<Self::To> {
-            self.0.traverse(*source)
+        #[inline]
+        fn visit_ref<'a>(
+            &self,
+            source: &'a BTreeMap<K, V>,
+            f: &mut dyn FnMut(&'a Self::To) -> bool,
+        ) -> bool {
+            source
+                .range(bounds(&self.0))
+                .all(|(_, v)| self.1.visit_ref(v, f))
         }
     }
 
-    impl<Pm, T> Prism<Box<T>> for _box<Pm>
+    impl<Tr, R, K, V> TraversalMut<BTreeMap<K, V>> for _range<R, Tr>
     where
-        Pm: Prism<T>,
+        Tr: TraversalMut<V>,
+        R: RangeBounds<K>,
+        K: Ord,
     {
-        fn pm(&self, source: Box<T>) -> Option<Self::To> {
-            self.0.pm(*source)
+        #[inline]
+        fn traverse_mut<'a>(&self, source: &'a mut BTreeMap<K, V>) -> Vec<&'a mut Self::To> {
+            source
+                .range_mut(bounds(&self.0))
+                .flat_map(|(_, v)| self.1.traverse_mut(v))
+                .collect()
         }
     }
 
-    impl<Ls, T> Lens<Box<T>> for _box<Ls>
+    impl<Tr, R, K, V> Traversal<BTreeMap<K, V>> for _range<R, Tr>
     where
-        Ls: Lens<T>,
+        Tr: Traversal<V>,
+        R: RangeBounds<K>,
+        K: Ord,
     {
-        fn view(&self, source: Box<T>) -> Self::To {
-            self.0.view(*source)
+        #[inline]
+        fn traverse(&self, source: BTreeMap<K, V>) -> Vec<Self::To> {
+            source
+                .into_iter()
+                .filter(|(k, _)| self.0.contains(k))
+                .flat_map(|(_, v)| self.1.traverse(v))
+                .collect()
+        }
+    }
+}
+
+mod impl_downcast {
+    /***********************************************************
+     * impl for the values of Box<dyn Any>
+     ************************************************************/
+    use crate::*;
+    use std::any::Any;
+    use std::fmt;
+    use std::hash::{Hash, Hasher};
+
+    // not derived, `T` needn't implement the traits.
+    impl<T, Optic: Clone> Clone for _downcast<T, Optic> {
+        fn clone(&self) -> Self {
+            _downcast(self.0, self.1.clone())
+        }
+    }
+
+    impl<T, Optic: Copy> Copy for _downcast<T, Optic> {}
+
+    impl<T, Optic: PartialEq> PartialEq for _downcast<T, Optic> {
+        fn eq(&self, other: &Self) -> bool {
+            self.1 == other.1
+        }
+    }
+
+    impl<T, Optic: Eq> Eq for _downcast<T, Optic> {}
+
+    impl<T, Optic: Hash> Hash for _downcast<T, Optic> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.1.hash(state)
+        }
+    }
+
+    impl<T, Optic: fmt::Debug> fmt::Debug for _downcast<T, Optic> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_tuple("_downcast")
+                .field(&std::any::type_name::<T>())
+                .field(&self.1)
+                .finish()
+        }
+    }
+
+    macro_rules! impl_downcast {
+        ($any:ty $(, $bound:ident)*) => {
+            impl<T: Any $(+ $bound)*, Rv> Review<Box<$any>> for _downcast<T, Rv>
+            where
+                Rv: Review<T>,
+            {
+                type From = Rv::From;
+
+                #[inline]
+                fn review(&self, from: Self::From) -> Box<$any> {
+                    Box::new(self.1.review(from))
+                }
+            }
+
+            impl<T: Any, Tr> TraversalRef<Box<$any>> for _downcast<T, Tr>
+            where
+                Tr: TraversalRef<T>,
+            {
+                type To = Tr::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, source: &'a Box<$any>) -> Vec<&'a Self::To> {
+                    match (**source).downcast_ref::<T>() {
+                        Option::Some(x) => self.1.traverse_ref(x),
+                        Option::None => vec![],
+                    }
+                }
+            }
+
+            impl<T: Any, Tr> TraversalMut<Box<$any>> for _downcast<T, Tr>
+            where
+                Tr: TraversalMut<T>,
+            {
+                #[inline]
+                fn traverse_mut<'a>(&self, source: &'a mut Box<$any>) -> Vec<&'a mut Self::To> {
+                    match (**source).downcast_mut::<T>() {
+                        Option::Some(x) => self.1.traverse_mut(x),
+                        Option::None => vec![],
+                    }
+                }
+            }
+
+            impl<T: Any, Tr> Traversal<Box<$any>> for _downcast<T, Tr>
+            where
+                Tr: Traversal<T>,
+            {
+                #[inline]
+                fn traverse(&self, source: Box<$any>) -> Vec<Self::To> {
+                    match source.downcast::<T>() {
+                        Result::Ok(x) => self.1.traverse(*x),
+                        Result::Err(_) => vec![],
+                    }
+                }
+            }
+
+            impl<T: Any, Pm> PrismRef<Box<$any>> for _downcast<T, Pm>
+            where
+                Pm: PrismRef<T>,
+            {
+                #[inline]
+                fn pm_ref<'a>(&self, source: &'a Box<$any>) -> Option<&'a Self::To> {
+                    (**source).downcast_ref::<T>().and_then(|x| self.1.pm_ref(x))
+                }
+            }
+
+            impl<T: Any, Pm> PrismMut<Box<$any>> for _downcast<T, Pm>
+            where
+                Pm: PrismMut<T>,
+            {
+                #[inline]
+                fn pm_mut<'a>(&self, source: &'a mut Box<$any>) -> Option<&'a mut Self::To> {
+                    (**source).downcast_mut::<T>().and_then(|x| self.1.pm_mut(x))
+                }
+            }
+
+            impl<T: Any, Pm> Prism<Box<$any>> for _downcast<T, Pm>
+            where
+                Pm: Prism<T>,
+            {
+                #[inline]
+                fn pm(&self, source: Box<$any>) -> Option<Self::To> {
+                    source.downcast::<T>().ok().and_then(|x| self.1.pm(*x))
+                }
+            }
+
+            impl<T: Any, Pv> Preview<Box<$any>> for _downcast<T, Pv>
+            where
+                Pv: Preview<T>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &Box<$any>) -> Option<Self::To> {
+                    (**source).downcast_ref::<T>().and_then(|x| self.1.preview(x))
+                }
+            }
+
+            impl<T: Any, St> Setter<Box<$any>> for _downcast<T, St>
+            where
+                St: Setter<T>,
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut Box<$any>, value: Self::To) {
+                    if let Option::Some(x) = (**source).downcast_mut::<T>() {
+                        self.1.set(x, value)
+                    }
+                }
+            }
+        };
+    }
+
+    impl_downcast!(dyn Any);
+    impl_downcast!(dyn Any + Send, Send);
+    impl_downcast!(dyn Any + Send + Sync, Send, Sync);
+}
+
+mod impl_rev {
+    /***********************************************************
+     * impl for reversed sequence
+     ************************************************************/
+    use crate::*;
+    use std::collections::*;
+    use std::iter::FromIterator;
+
+    // the iso is its own inverse, writes reverse the sequence back.
+    macro_rules! impl_rev {
+        (<$($param:ident)*> $seq:ty, $reversed:expr) => {
+            impl<Pv, $($param,)*> Preview<$seq> for _rev<Pv>
+            where
+                Pv: Preview<$seq>,
+                $seq: Clone,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &$seq) -> Option<Self::To> {
+                    self.0.preview(&$reversed(source.clone()))
+                }
+            }
+
+            impl<Gt, $($param,)*> Getter<$seq> for _rev<Gt>
+            where
+                Gt: Getter<$seq>,
+                $seq: Clone,
+            {
+                #[inline]
+                fn get(&self, source: &$seq) -> Self::To {
+                    self.0.get(&$reversed(source.clone()))
+                }
+            }
+
+            impl<St, $($param,)*> Setter<$seq> for _rev<St>
+            where
+                St: Setter<$seq>,
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut $seq, value: Self::To) {
+                    let mut reversed = $reversed(std::mem::take(source));
+                    self.0.set(&mut reversed, value);
+                    *source = $reversed(reversed);
+                }
+            }
+
+            impl<Ov, $($param,)*> Over<$seq> for _rev<Ov>
+            where
+                Ov: Over<$seq>,
+            {
+                #[inline]
+                fn over(&self, source: &mut $seq, f: &mut dyn FnMut(&mut Self::To)) {
+                    let mut reversed = $reversed(std::mem::take(source));
+                    self.0.over(&mut reversed, f);
+                    *source = $reversed(reversed);
+                }
+            }
+
+            impl<Rv, $($param,)*> Review<$seq> for _rev<Rv>
+            where
+                Rv: Review<$seq>,
+            {
+                type From = Rv::From;
+
+                #[inline]
+                fn review(&self, from: Self::From) -> $seq {
+                    $reversed(self.0.review(from))
+                }
+            }
+        }
+    }
+
+    fn reversed<C>(seq: C) -> C
+    where
+        C: IntoIterator + FromIterator<C::Item>,
+        C::IntoIter: DoubleEndedIterator,
+    {
+        seq.into_iter().rev().collect()
+    }
+
+    impl_rev!(<T> Vec<T>, reversed);
+    impl_rev!(<T> VecDeque<T>, reversed);
+    impl_rev!(<T> LinkedList<T>, reversed);
+    impl_rev!(<> String, |s: String| s.chars().rev().collect::<String>());
+}
+
+mod impl_sequence {
+    /***********************************************************
+     * impl for sequence, all the elements or nothing
+     ************************************************************/
+    use crate::*;
+
+    macro_rules! impl_sequence {
+        (<$($param:ident)*> $wrapper:ty, $inner:ty, $wrap:path, $get_ref:expr, $get_mut:expr, $get:expr) => {
+            impl<Tr, $($param,)*> TraversalRef<Vec<$wrapper>> for _sequence<Tr>
+            where
+                Tr: TraversalRef<$inner>,
+            {
+                type To = Tr::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, source: &'a Vec<$wrapper>) -> Vec<&'a Self::To> {
+                    if !source.iter().all(|t| $get_ref(t).is_some()) {
+                        return vec![];
+                    }
+                    source
+                        .iter()
+                        .filter_map($get_ref)
+                        .flat_map(|t| self.0.traverse_ref(t))
+                        .collect()
+                }
+
+                #[inline]
+                fn visit_ref<'a>(&self, source: &'a Vec<$wrapper>, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    !source.iter().all(|t| $get_ref(t).is_some())
+                        || source.iter().filter_map($get_ref).all(|t| self.0.visit_ref(t, f))
+                }
+            }
+
+            impl<Tr, $($param,)*> TraversalMut<Vec<$wrapper>> for _sequence<Tr>
+            where
+                Tr: TraversalMut<$inner>,
+            {
+                #[inline]
+                fn traverse_mut<'a>(&self, source: &'a mut Vec<$wrapper>) -> Vec<&'a mut Self::To> {
+                    if !source.iter().all(|t| $get_ref(t).is_some()) {
+                        return vec![];
+                    }
+                    source
+                        .iter_mut()
+                        .filter_map($get_mut)
+                        .flat_map(|t| self.0.traverse_mut(t))
+                        .collect()
+                }
+            }
+
+            impl<Tr, $($param,)*> Traversal<Vec<$wrapper>> for _sequence<Tr>
+            where
+                Tr: Traversal<$inner>,
+            {
+                #[inline]
+                fn traverse(&self, source: Vec<$wrapper>) -> Vec<Self::To> {
+                    if !source.iter().all(|t| $get_ref(t).is_some()) {
+                        return vec![];
+                    }
+                    source
+                        .into_iter()
+                        .filter_map($get)
+                        .flat_map(|t| self.0.traverse(t))
+                        .collect()
+                }
+            }
+
+            /// the sequence of the one element the traversal then focuses.
+            impl<Rv, $($param,)*> Review<Vec<$wrapper>> for _sequence<Rv>
+            where
+                Rv: Review<$inner>,
+            {
+                type From = Rv::From;
+
+                #[inline]
+                fn review(&self, from: Self::From) -> Vec<$wrapper> {
+                    vec![$wrap(self.0.review(from))]
+                }
+            }
+        }
+    }
+
+    fn ok_ref<T, E>(result: &Result<T, E>) -> Option<&T> {
+        result.as_ref().ok()
+    }
+
+    fn ok_mut<T, E>(result: &mut Result<T, E>) -> Option<&mut T> {
+        result.as_mut().ok()
+    }
+
+    impl_sequence!(<T> Option<T>, T, Option::Some, Option::as_ref, Option::as_mut, std::convert::identity);
+    impl_sequence!(<T E> Result<T, E>, T, Result::Ok, ok_ref, ok_mut, Result::ok);
+}
+
+mod impl_ptr {
+    use crate::*;
+    use std::ops::Deref;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    macro_rules! impl_ref {
+        (<$($life:lifetime),*; $($param:ident),*> $ptr:ty, $optic:ident) => {
+            impl<$($life,)* $($param,)* Tr> TraversalRef<$ptr> for $optic<Tr>
+            where
+                    Tr: TraversalRef<<$ptr as Deref>::Target>,
+            {
+                type To = Tr::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, source: &'a $ptr) -> Vec<&'a Self::To> {
+                    self.0.traverse_ref(source)
+                }
+
+                #[inline]
+                fn visit_ref<'a>(&self, source: &'a $ptr, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    self.0.visit_ref(source, f)
+                }
+            }
+
+            impl<$($life,)* $($param,)* Pm> PrismRef<$ptr> for $optic<Pm>
+                where
+                    Pm: PrismRef<<$ptr as Deref>::Target>,
+            {
+                #[inline]
+                fn pm_ref<'a>(&self, source: &'a $ptr) -> Option<&'a Self::To> {
+                    self.0.pm_ref(source)
+                }
+            }
+
+            impl<$($life,)* $($param,)* Ls> LensRef<$ptr> for $optic<Ls>
+                where
+                    Ls: LensRef<<$ptr as Deref>::Target>
+            {
+                #[inline]
+                fn view_ref<'a>(&self, source: &'a $ptr) -> &'a Self::To {
+                    self.0.view_ref(source)
+                }
+            }
+
+            impl<$($life,)* $($param,)* Pv> Preview<$ptr> for $optic<Pv>
+                where
+                    Pv: Preview<<$ptr as Deref>::Target>
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &$ptr) -> Option<Self::To> {
+                    self.0.preview(source)
+                }
+            }
+
+            impl<$($life,)* $($param,)* Gt> Getter<$ptr> for $optic<Gt>
+                where
+                    Gt: Getter<<$ptr as Deref>::Target>
+            {
+                #[inline]
+                fn get(&self, source: &$ptr) -> Self::To {
+                    self.0.get(source)
+                }
+            }
+
+            impl<$($life,)* $($param,)* Ls> LensAsyncRef<$ptr> for $optic<Ls>
+                where
+                    Ls: LensAsyncRef<<$ptr as Deref>::Target>
+            {
+                type To = Ls::To;
+
+                #[inline]
+                fn view_async_ref<'a>(&'a self, source: &'a $ptr) -> BoxFuture<'a, &'a Self::To> {
+                    self.0.view_async_ref(source)
+                }
+            }
+        }
+    }
+
+    macro_rules! impl_mut {
+        (<$($life:lifetime),*; $($param:ident),*> $ptr:ty, $optic:ident) => {
+            impl<$($life,)* $($param,)* Tr> TraversalMut<$ptr> for $optic<Tr>
+            where
+                Tr: TraversalMut<<$ptr as Deref>::Target>,
+            {
+                #[inline]
+                fn traverse_mut<'a>(&self, source: &'a mut $ptr) -> Vec<&'a mut Self::To> {
+                    self.0.traverse_mut(source)
+                }
+            }
+
+            impl<$($life,)* $($param,)* Pm> PrismMut<$ptr> for $optic<Pm>
+            where
+                Pm: PrismMut<<$ptr as Deref>::Target>,
+            {
+                #[inline]
+                fn pm_mut<'a>(&self, source: &'a mut $ptr) -> Option<&'a mut Self::To> {
+                    self.0.pm_mut(source)
+                }
+            }
+
+            impl<$($life,)* $($param,)* Ls> LensMut<$ptr> for $optic<Ls>
+            where
+                Ls: LensMut<<$ptr as Deref>::Target>
+            {
+                #[inline]
+                fn view_mut<'a>(&self, source: &'a mut $ptr) -> &'a mut Self::To {
+                    self.0.view_mut(source)
+                }
+            }
+
+            impl<$($life,)* $($param,)* St> Setter<$ptr> for $optic<St>
+            where
+                St: Setter<<$ptr as Deref>::Target>
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut $ptr, value: Self::To) {
+                    self.0.set(source, value)
+                }
+            }
+
+            impl<$($life,)* $($param,)* Ls> LensAsyncMut<$ptr> for $optic<Ls>
+            where
+                Ls: LensAsyncMut<<$ptr as Deref>::Target>
+            {
+                #[inline]
+                fn view_async_mut<'a>(&'a self, source: &'a mut $ptr) -> BoxFuture<'a, &'a mut Self::To> {
+                    self.0.view_async_mut(source)
+                }
+            }
+        }
+    }
+
+    impl<Rv, T> Review<Box<T>> for _box<Rv>
+    where
+        Rv: Review<T>,
+    {
+        type From = Rv::From;
+
+        #[inline]
+        fn review(&self, from: Self::From) -> Box<T> {
+            Box::new(self.0.review(from))
+        }
+    }
+
+    impl<Tr, T> Traversal<Box<T>> for _box<Tr>
+    where
+        Tr: Traversal<T>,
+    {
+        #[inline]
+        fn traverse(&self, source: Box<T>) -> Vec<Self::To> {
+            self.0.traverse(*source)
+        }
+    }
+
+    impl<Pm, T> Prism<Box<T>> for _box<Pm>
+    where
+        Pm: Prism<T>,
+    {
+        #[inline]
+        fn pm(&self, source: Box<T>) -> Option<Self::To> {
+            self.0.pm(*source)
+        }
+    }
+
+    impl<Ls, T> Lens<Box<T>> for _box<Ls>
+    where
+        Ls: Lens<T>,
+    {
+        #[inline]
+        fn view(&self, source: Box<T>) -> Self::To {
+            self.0.view(*source)
         }
     }
 
     impl<Rv, T> Review<Rc<T>> for _rc<Rv>
     where
-        Rv: Review<T>,
+        Rv: Review<T>,
+    {
+        type From = Rv::From;
+
+        #[inline]
+        fn review(&self, from: Self::From) -> Rc<T> {
+            Rc::new(self.0.review(from))
+        }
+    }
+
+    impl<Rv, T> Review<Arc<T>> for _arc<Rv>
+    where
+        Rv: Review<T>,
+    {
+        type From = Rv::From;
+
+        #[inline]
+        fn review(&self, from: Self::From) -> Arc<T> {
+            Arc::new(self.0.review(from))
+        }
+    }
+
+    impl_ref!(<; T> Box<T>, _box);
+    impl_ref!(<; T> Rc<T>, _rc);
+    impl_ref!(<; T> Arc<T>, _arc);
+    impl_ref!(<'t; T> &'t mut T, _mut);
+    impl_ref!(<'t; T> &'t T, _ref);
+
+    impl_mut!(<; T> Box<T>, _box);
+    impl_mut!(<'t; T> &'t mut T, _mut);
+
+    // copy on write, a shared pointee is cloned before it's borrowed mutably, see `clone_of`.
+    macro_rules! impl_make_mut {
+        ($ptr:ident, $optic:ident) => {
+            impl<T: Clone, Tr> TraversalMut<$ptr<T>> for $optic<Tr>
+            where
+                Tr: TraversalMut<T>,
+            {
+                #[inline]
+                fn traverse_mut<'a>(&self, source: &'a mut $ptr<T>) -> Vec<&'a mut Self::To> {
+                    self.0.traverse_mut($ptr::make_mut(source))
+                }
+            }
+
+            impl<T: Clone, Pm> PrismMut<$ptr<T>> for $optic<Pm>
+            where
+                Pm: PrismMut<T>,
+            {
+                #[inline]
+                fn pm_mut<'a>(&self, source: &'a mut $ptr<T>) -> Option<&'a mut Self::To> {
+                    self.0.pm_mut($ptr::make_mut(source))
+                }
+            }
+
+            impl<T: Clone, Ls> LensMut<$ptr<T>> for $optic<Ls>
+            where
+                Ls: LensMut<T>,
+            {
+                #[inline]
+                fn view_mut<'a>(&self, source: &'a mut $ptr<T>) -> &'a mut Self::To {
+                    self.0.view_mut($ptr::make_mut(source))
+                }
+            }
+
+            impl<T: Clone, St> Setter<$ptr<T>> for $optic<St>
+            where
+                St: Setter<T>,
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut $ptr<T>, value: Self::To) {
+                    self.0.set($ptr::make_mut(source), value)
+                }
+            }
+        };
+    }
+
+    impl_make_mut!(Rc, _rc);
+    impl_make_mut!(Arc, _arc);
+}
+
+mod impl_weak {
+    /***********************************************************
+     * impl for the Weak of Rc and Arc
+     ************************************************************/
+
+    use crate::*;
+    use std::rc::{self, Rc};
+    use std::sync::{self, Arc};
+
+    macro_rules! impl_upgraded {
+        ($weak:ty, $strong:ty) => {
+            impl<Pv, T> Preview<$weak> for _upgraded<Pv>
+            where
+                Pv: Preview<$strong>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &$weak) -> Option<Self::To> {
+                    source.upgrade().and_then(|strong| self.0.preview(&strong))
+                }
+            }
+        };
+    }
+
+    impl_upgraded!(rc::Weak<T>, Rc<T>);
+    impl_upgraded!(sync::Weak<T>, Arc<T>);
+}
+
+mod impl_utf8 {
+    /***********************************************************
+     * impl for utf8
+     ************************************************************/
+    use crate::*;
+
+    impl<Pv> Preview<Vec<u8>> for _utf8<Pv>
+    where
+        Pv: Preview<String>,
+    {
+        type To = Pv::To;
+
+        #[inline]
+        fn preview(&self, source: &Vec<u8>) -> Option<Self::To> {
+            std::str::from_utf8(source)
+                .ok()
+                .and_then(|s| self.0.preview(&s.to_owned()))
+        }
+    }
+
+    impl<St> Setter<Vec<u8>> for _utf8<St>
+    where
+        St: Setter<String>,
+    {
+        type To = St::To;
+
+        /// the bytes stay unchanged if they aren't valid utf8.
+        #[inline]
+        fn set(&self, source: &mut Vec<u8>, value: Self::To) {
+            match String::from_utf8(std::mem::take(source)) {
+                Result::Ok(mut s) => {
+                    self.0.set(&mut s, value);
+                    *source = s.into_bytes();
+                }
+                Result::Err(e) => *source = e.into_bytes(),
+            }
+        }
+    }
+
+    impl<Rv> Review<Vec<u8>> for _utf8<Rv>
+    where
+        Rv: Review<String>,
+    {
+        type From = Rv::From;
+
+        #[inline]
+        fn review(&self, from: Self::From) -> Vec<u8> {
+            self.0.review(from).into_bytes()
+        }
+    }
+}
+
+mod impl_num {
+    /***********************************************************
+     * impl for Wrapping, Saturating and NonZero
+     ************************************************************/
+    use crate::*;
+    use std::num::*;
+
+    macro_rules! impl_newtype {
+        ($wrapper:ident) => {
+            impl<Rv, T> Review<$wrapper<T>> for _0<Rv>
+            where
+                Rv: Review<T>,
+            {
+                type From = Rv::From;
+
+                #[inline]
+                fn review(&self, from: Self::From) -> $wrapper<T> {
+                    $wrapper(self.0.review(from))
+                }
+            }
+
+            impl<Tr, T> TraversalRef<$wrapper<T>> for _0<Tr>
+            where
+                Tr: TraversalRef<T>,
+            {
+                type To = Tr::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, source: &'a $wrapper<T>) -> Vec<&'a Self::To> {
+                    self.0.traverse_ref(&source.0)
+                }
+
+                #[inline]
+                fn visit_ref<'a>(&self, source: &'a $wrapper<T>, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    self.0.visit_ref(&source.0, f)
+                }
+            }
+
+            impl<Tr, T> TraversalMut<$wrapper<T>> for _0<Tr>
+            where
+                Tr: TraversalMut<T>,
+            {
+                #[inline]
+                fn traverse_mut<'a>(&self, source: &'a mut $wrapper<T>) -> Vec<&'a mut Self::To> {
+                    self.0.traverse_mut(&mut source.0)
+                }
+            }
+
+            impl<Tr, T> Traversal<$wrapper<T>> for _0<Tr>
+            where
+                Tr: Traversal<T>,
+            {
+                #[inline]
+                fn traverse(&self, source: $wrapper<T>) -> Vec<Self::To> {
+                    self.0.traverse(source.0)
+                }
+            }
+
+            impl<Pm, T> PrismRef<$wrapper<T>> for _0<Pm>
+            where
+                Pm: PrismRef<T>,
+            {
+                #[inline]
+                fn pm_ref<'a>(&self, source: &'a $wrapper<T>) -> Option<&'a Self::To> {
+                    self.0.pm_ref(&source.0)
+                }
+            }
+
+            impl<Pm, T> PrismMut<$wrapper<T>> for _0<Pm>
+            where
+                Pm: PrismMut<T>,
+            {
+                #[inline]
+                fn pm_mut<'a>(&self, source: &'a mut $wrapper<T>) -> Option<&'a mut Self::To> {
+                    self.0.pm_mut(&mut source.0)
+                }
+            }
+
+            impl<Pm, T> Prism<$wrapper<T>> for _0<Pm>
+            where
+                Pm: Prism<T>,
+            {
+                #[inline]
+                fn pm(&self, source: $wrapper<T>) -> Option<Self::To> {
+                    self.0.pm(source.0)
+                }
+            }
+
+            impl<Ls, T> LensRef<$wrapper<T>> for _0<Ls>
+            where
+                Ls: LensRef<T>,
+            {
+                #[inline]
+                fn view_ref<'a>(&self, source: &'a $wrapper<T>) -> &'a Self::To {
+                    self.0.view_ref(&source.0)
+                }
+            }
+
+            impl<Ls, T> LensMut<$wrapper<T>> for _0<Ls>
+            where
+                Ls: LensMut<T>,
+            {
+                #[inline]
+                fn view_mut<'a>(&self, source: &'a mut $wrapper<T>) -> &'a mut Self::To {
+                    self.0.view_mut(&mut source.0)
+                }
+            }
+
+            impl<Ls, T> Lens<$wrapper<T>> for _0<Ls>
+            where
+                Ls: Lens<T>,
+            {
+                #[inline]
+                fn view(&self, source: $wrapper<T>) -> Self::To {
+                    self.0.view(source.0)
+                }
+            }
+
+            impl<Pv, T> Preview<$wrapper<T>> for _0<Pv>
+            where
+                Pv: Preview<T>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &$wrapper<T>) -> Option<Self::To> {
+                    self.0.preview(&source.0)
+                }
+            }
+
+            impl<Gt, T> Getter<$wrapper<T>> for _0<Gt>
+            where
+                Gt: Getter<T>,
+            {
+                #[inline]
+                fn get(&self, source: &$wrapper<T>) -> Self::To {
+                    self.0.get(&source.0)
+                }
+            }
+
+            impl<St, T> Setter<$wrapper<T>> for _0<St>
+            where
+                St: Setter<T>,
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut $wrapper<T>, value: Self::To) {
+                    self.0.set(&mut source.0, value)
+                }
+            }
+        };
+    }
+
+    impl_newtype!(Wrapping);
+    impl_newtype!(Saturating);
+
+    macro_rules! impl_nonzero {
+        ($($int:ty => $nonzero:ty),*) => {$(
+            impl<Pv> Preview<$int> for _nonzero<Pv>
+            where
+                Pv: Preview<$nonzero>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &$int) -> Option<Self::To> {
+                    <$nonzero>::new(*source).and_then(|n| self.0.preview(&n))
+                }
+            }
+
+            impl<St> Setter<$int> for _nonzero<St>
+            where
+                St: Setter<$nonzero>,
+            {
+                type To = St::To;
+
+                /// zero stays unchanged.
+                #[inline]
+                fn set(&self, source: &mut $int, value: Self::To) {
+                    if let Option::Some(mut n) = <$nonzero>::new(*source) {
+                        self.0.set(&mut n, value);
+                        *source = n.get();
+                    }
+                }
+            }
+
+            impl<Rv> Review<$int> for _nonzero<Rv>
+            where
+                Rv: Review<$nonzero>,
+            {
+                type From = Rv::From;
+
+                #[inline]
+                fn review(&self, from: Self::From) -> $int {
+                    self.0.review(from).get()
+                }
+            }
+        )*};
+    }
+
+    impl_nonzero!(
+        u8 => NonZeroU8, u16 => NonZeroU16, u32 => NonZeroU32, u64 => NonZeroU64,
+        u128 => NonZeroU128, usize => NonZeroUsize,
+        i8 => NonZeroI8, i16 => NonZeroI16, i32 => NonZeroI32, i64 => NonZeroI64,
+        i128 => NonZeroI128, isize => NonZeroIsize
+    );
+}
+
+mod impl_range {
+    /***********************************************************
+     * impl for ranges
+     ************************************************************/
+    use crate::*;
+    use std::ops::*;
+
+    impl_field!(<Idx> Range<Idx>, start, _start, Idx);
+    impl_field!(<Idx> Range<Idx>, end, _end, Idx);
+    impl_field!(<Idx> RangeFrom<Idx>, start, _start, Idx);
+    impl_field!(<Idx> RangeTo<Idx>, end, _end, Idx);
+    impl_field!(<Idx> RangeToInclusive<Idx>, end, _end, Idx);
+
+    // the bounds of `RangeInclusive` are private, so they can only be set by rebuilding it.
+    macro_rules! impl_inclusive {
+        ($optic:ident, $bound:ident, $index:tt) => {
+            impl<Tr, Idx> TraversalRef<RangeInclusive<Idx>> for $optic<Tr>
+            where
+                Tr: TraversalRef<Idx>,
+            {
+                type To = Tr::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, source: &'a RangeInclusive<Idx>) -> Vec<&'a Self::To> {
+                    self.0.traverse_ref(source.$bound())
+                }
+
+                #[inline]
+                fn visit_ref<'a>(
+                    &self,
+                    source: &'a RangeInclusive<Idx>,
+                    f: &mut dyn FnMut(&'a Self::To) -> bool,
+                ) -> bool {
+                    self.0.visit_ref(source.$bound(), f)
+                }
+            }
+
+            impl<Pm, Idx> PrismRef<RangeInclusive<Idx>> for $optic<Pm>
+            where
+                Pm: PrismRef<Idx>,
+            {
+                #[inline]
+                fn pm_ref<'a>(&self, source: &'a RangeInclusive<Idx>) -> Option<&'a Self::To> {
+                    self.0.pm_ref(source.$bound())
+                }
+            }
+
+            impl<Ls, Idx> LensRef<RangeInclusive<Idx>> for $optic<Ls>
+            where
+                Ls: LensRef<Idx>,
+            {
+                #[inline]
+                fn view_ref<'a>(&self, source: &'a RangeInclusive<Idx>) -> &'a Self::To {
+                    self.0.view_ref(source.$bound())
+                }
+            }
+
+            impl<Pv, Idx> Preview<RangeInclusive<Idx>> for $optic<Pv>
+            where
+                Pv: Preview<Idx>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &RangeInclusive<Idx>) -> Option<Self::To> {
+                    self.0.preview(source.$bound())
+                }
+            }
+
+            impl<Gt, Idx> Getter<RangeInclusive<Idx>> for $optic<Gt>
+            where
+                Gt: Getter<Idx>,
+            {
+                #[inline]
+                fn get(&self, source: &RangeInclusive<Idx>) -> Self::To {
+                    self.0.get(source.$bound())
+                }
+            }
+
+            impl<St, Idx> Setter<RangeInclusive<Idx>> for $optic<St>
+            where
+                St: Setter<Idx>,
+                Idx: Clone,
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut RangeInclusive<Idx>, value: Self::To) {
+                    let mut bounds = source.clone().into_inner();
+                    self.0.set(&mut bounds.$index, value);
+                    *source = bounds.0..=bounds.1;
+                }
+            }
+        };
+    }
+
+    impl_inclusive!(_start, start, 0);
+    impl_inclusive!(_end, end, 1);
+}
+
+mod impl_time {
+    /***********************************************************
+     * impl for Duration and SystemTime
+     ************************************************************/
+    use crate::*;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    macro_rules! impl_duration {
+        ($optic:ident, $to:ty, $get:expr, $set:expr) => {
+            impl<Pv> Preview<Duration> for $optic<Pv>
+            where
+                Pv: Preview<$to>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &Duration) -> Option<Self::To> {
+                    self.0.preview(&$get(source))
+                }
+            }
+
+            impl<Gt> Getter<Duration> for $optic<Gt>
+            where
+                Gt: Getter<$to>,
+            {
+                #[inline]
+                fn get(&self, source: &Duration) -> Self::To {
+                    self.0.get(&$get(source))
+                }
+            }
+
+            impl<St> Setter<Duration> for $optic<St>
+            where
+                St: Setter<$to>,
+            {
+                type To = St::To;
+
+                /// the duration stays unchanged if the new value is out of range.
+                #[inline]
+                fn set(&self, source: &mut Duration, value: Self::To) {
+                    let mut component = $get(&*source);
+                    self.0.set(&mut component, value);
+                    if let Option::Some(duration) = $set(&*source, component) {
+                        *source = duration;
+                    }
+                }
+            }
+        };
+    }
+
+    impl_duration!(
+        _secs,
+        u64,
+        Duration::as_secs,
+        |d: &Duration, secs| Option::Some(Duration::new(secs, d.subsec_nanos()))
+    );
+    impl_duration!(_secs_f64, f64, Duration::as_secs_f64, |_, secs| {
+        Duration::try_from_secs_f64(secs).ok()
+    });
+    impl_duration!(
+        _millis,
+        u64,
+        |d: &Duration| d.as_millis() as u64,
+        |d: &Duration, millis| Duration::from_millis(millis)
+            .checked_add(Duration::from_nanos((d.subsec_nanos() % 1_000_000).into()))
+    );
+    impl_duration!(
+        _micros,
+        u64,
+        |d: &Duration| d.as_micros() as u64,
+        |d: &Duration, micros| Duration::from_micros(micros)
+            .checked_add(Duration::from_nanos((d.subsec_nanos() % 1_000).into()))
+    );
+
+    impl<Pv> Preview<SystemTime> for _since_epoch<Pv>
+    where
+        Pv: Preview<Duration>,
+    {
+        type To = Pv::To;
+
+        #[inline]
+        fn preview(&self, source: &SystemTime) -> Option<Self::To> {
+            source
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .and_then(|d| self.0.preview(&d))
+        }
+    }
+
+    impl<St> Setter<SystemTime> for _since_epoch<St>
+    where
+        St: Setter<Duration>,
+    {
+        type To = St::To;
+
+        /// the time stays unchanged if it is before the epoch or the new one is out of range.
+        #[inline]
+        fn set(&self, source: &mut SystemTime, value: Self::To) {
+            if let Result::Ok(mut d) = source.duration_since(UNIX_EPOCH) {
+                self.0.set(&mut d, value);
+                if let Option::Some(time) = UNIX_EPOCH.checked_add(d) {
+                    *source = time;
+                }
+            }
+        }
+    }
+}
+
+mod impl_net {
+    /***********************************************************
+     * impl for IpAddr and SocketAddr
+     ************************************************************/
+    use crate::*;
+    use std::net::*;
+
+    macro_rules! impl_variant {
+        ($data:ident, $var:ident => $optic:ident, $to:ty) => {
+            impl<Rv> Review<$data> for optics::$optic<Rv>
+            where
+                Rv: Review<$to>,
+            {
+                type From = Rv::From;
+
+                #[inline]
+                fn review(&self, from: Self::From) -> $data {
+                    $data::$var(self.0.review(from))
+                }
+            }
+
+            impl<Tr> TraversalRef<$data> for optics::$optic<Tr>
+            where
+                Tr: TraversalRef<$to>,
+            {
+                type To = Tr::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, source: &'a $data) -> Vec<&'a Self::To> {
+                    match source {
+                        $data::$var(x) => self.0.traverse_ref(x),
+                        _ => vec![],
+                    }
+                }
+
+                #[inline]
+                fn visit_ref<'a>(&self, source: &'a $data, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    match source {
+                        $data::$var(x) => self.0.visit_ref(x, f),
+                        _ => true,
+                    }
+                }
+            }
+
+            impl<Tr> TraversalMut<$data> for optics::$optic<Tr>
+            where
+                Tr: TraversalMut<$to>,
+            {
+                #[inline]
+                fn traverse_mut<'a>(&self, source: &'a mut $data) -> Vec<&'a mut Self::To> {
+                    match source {
+                        $data::$var(x) => self.0.traverse_mut(x),
+                        _ => vec![],
+                    }
+                }
+            }
+
+            impl<Tr> Traversal<$data> for optics::$optic<Tr>
+            where
+                Tr: Traversal<$to>,
+            {
+                #[inline]
+                fn traverse(&self, source: $data) -> Vec<Self::To> {
+                    match source {
+                        $data::$var(x) => self.0.traverse(x),
+                        _ => vec![],
+                    }
+                }
+            }
+
+            impl<Pm> PrismRef<$data> for optics::$optic<Pm>
+            where
+                Pm: PrismRef<$to>,
+            {
+                #[inline]
+                fn pm_ref<'a>(&self, source: &'a $data) -> Option<&'a Self::To> {
+                    match source {
+                        $data::$var(x) => self.0.pm_ref(x),
+                        _ => Option::None,
+                    }
+                }
+            }
+
+            impl<Pm> PrismMut<$data> for optics::$optic<Pm>
+            where
+                Pm: PrismMut<$to>,
+            {
+                #[inline]
+                fn pm_mut<'a>(&self, source: &'a mut $data) -> Option<&'a mut Self::To> {
+                    match source {
+                        $data::$var(x) => self.0.pm_mut(x),
+                        _ => Option::None,
+                    }
+                }
+            }
+
+            impl<Pm> Prism<$data> for optics::$optic<Pm>
+            where
+                Pm: Prism<$to>,
+            {
+                #[inline]
+                fn pm(&self, source: $data) -> Option<Self::To> {
+                    match source {
+                        $data::$var(x) => self.0.pm(x),
+                        _ => Option::None,
+                    }
+                }
+            }
+
+            impl<Pv> Preview<$data> for optics::$optic<Pv>
+            where
+                Pv: Preview<$to>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &$data) -> Option<Self::To> {
+                    match source {
+                        $data::$var(x) => self.0.preview(x),
+                        _ => Option::None,
+                    }
+                }
+            }
+
+            impl<St> Setter<$data> for optics::$optic<St>
+            where
+                St: Setter<$to>,
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut $data, value: Self::To) {
+                    if let $data::$var(x) = source {
+                        self.0.set(x, value)
+                    }
+                }
+            }
+        };
+    }
+
+    impl_variant!(IpAddr, V4 => _V4, Ipv4Addr);
+    impl_variant!(IpAddr, V6 => _V6, Ipv6Addr);
+    impl_variant!(SocketAddr, V4 => _V4, SocketAddrV4);
+    impl_variant!(SocketAddr, V6 => _V6, SocketAddrV6);
+
+    macro_rules! impl_component {
+        ($addr:ty, $optic:ident, $to:ty, $get:ident, $set:ident) => {
+            impl<Pv> Preview<$addr> for $optic<Pv>
+            where
+                Pv: Preview<$to>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &$addr) -> Option<Self::To> {
+                    self.0.preview(&source.$get())
+                }
+            }
+
+            impl<Gt> Getter<$addr> for $optic<Gt>
+            where
+                Gt: Getter<$to>,
+            {
+                #[inline]
+                fn get(&self, source: &$addr) -> Self::To {
+                    self.0.get(&source.$get())
+                }
+            }
+
+            impl<St> Setter<$addr> for $optic<St>
+            where
+                St: Setter<$to>,
+            {
+                type To = St::To;
+
+                #[inline]
+                fn set(&self, source: &mut $addr, value: Self::To) {
+                    let mut component = source.$get().to_owned();
+                    self.0.set(&mut component, value);
+                    source.$set(component);
+                }
+            }
+        };
+    }
+
+    impl_component!(SocketAddr, _ip, IpAddr, ip, set_ip);
+    impl_component!(SocketAddr, _port, u16, port, set_port);
+    impl_component!(SocketAddrV4, _ip, Ipv4Addr, ip, set_ip);
+    impl_component!(SocketAddrV4, _port, u16, port, set_port);
+    impl_component!(SocketAddrV6, _ip, Ipv6Addr, ip, set_ip);
+    impl_component!(SocketAddrV6, _port, u16, port, set_port);
+}
+
+mod impl_path {
+    /***********************************************************
+     * impl for PathBuf
+     ************************************************************/
+    use crate::*;
+    use std::ffi::{OsStr, OsString};
+    use std::path::{Path, PathBuf};
+
+    macro_rules! impl_path_part {
+        ($optic:ident, $get:expr, $set:expr) => {
+            impl<Pv> Preview<PathBuf> for $optic<Pv>
+            where
+                Pv: Preview<OsString>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &PathBuf) -> Option<Self::To> {
+                    $get(source.as_path()).and_then(|part: &OsStr| self.0.preview(&part.to_owned()))
+                }
+            }
+
+            impl<St> Setter<PathBuf> for $optic<St>
+            where
+                St: Setter<OsString>,
+            {
+                type To = St::To;
+
+                /// the path stays unchanged if it has no such part.
+                #[inline]
+                fn set(&self, source: &mut PathBuf, value: Self::To) {
+                    if let Option::Some(part) = $get(source.as_path()) {
+                        let mut part = part.to_owned();
+                        self.0.set(&mut part, value);
+                        $set(source, part);
+                    }
+                }
+            }
+        };
+    }
+
+    impl_path_part!(_extension, Path::extension, |path: &mut PathBuf, ext: OsString| {
+        path.set_extension(ext);
+    });
+    impl_path_part!(_file_name, Path::file_name, |path: &mut PathBuf, name: OsString| {
+        path.set_file_name(name);
+    });
+    impl_path_part!(_file_stem, Path::file_stem, |path: &mut PathBuf, mut stem: OsString| {
+        if let Option::Some(ext) = path.extension() {
+            stem.push(".");
+            stem.push(ext);
+        }
+        path.set_file_name(stem);
+    });
+
+    impl<Pv> Preview<PathBuf> for _component_list<Pv>
+    where
+        Pv: Preview<Vec<OsString>>,
     {
-        type From = Rv::From;
+        type To = Pv::To;
 
-        fn review(&self, from: Self::From) -> Rc<T> {
-            Rc::new(self.0.review(from))
+        #[inline]
+        fn preview(&self, source: &PathBuf) -> Option<Self::To> {
+            self.0.preview(&components(source))
         }
     }
 
-    impl<Rv, T> Review<Arc<T>> for _arc<Rv>
+    impl<Gt> Getter<PathBuf> for _component_list<Gt>
     where
-        Rv: Review<T>,
+        Gt: Getter<Vec<OsString>>,
     {
-        type From = Rv::From;
+        #[inline]
+        fn get(&self, source: &PathBuf) -> Self::To {
+            self.0.get(&components(source))
+        }
+    }
 
-        fn review(&self, from: Self::From) -> Arc<T> {
-            Arc::new(self.0.review(from))
+    impl<St> Setter<PathBuf> for _component_list<St>
+    where
+        St: Setter<Vec<OsString>>,
+    {
+        type To = St::To;
+
+        #[inline]
+        fn set(&self, source: &mut PathBuf, value: Self::To) {
+            let mut parts = components(source);
+            self.0.set(&mut parts, value);
+            *source = parts.into_iter().collect();
         }
     }
 
-    impl_ref!(<; T> Box<T>, _box);
-    impl_ref!(<; T> Rc<T>, _rc);
-    impl_ref!(<; T> Rc<T>, _arc);
-    impl_ref!(<'t; T> &'t mut T, _mut);
-    impl_ref!(<'t; T> &'t T, _ref);
+    fn components(path: &Path) -> Vec<OsString> {
+        path.components()
+            .map(|c| c.as_os_str().to_owned())
+            .collect()
+    }
+}
 
-    impl_mut!(<; T> Box<T>, _box);
-    impl_mut!(<'t; T> &'t mut T, _mut);
+#[cfg(feature = "codec")]
+mod impl_codec {
+    /***********************************************************
+     * impl for base64 and hex
+     ************************************************************/
+    use crate::*;
+
+    const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    fn encode_base64(bytes: &[u8]) -> String {
+        let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+        for chunk in bytes.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    fn decode_base64(s: &str) -> Option<Vec<u8>> {
+        let chunks = s.as_bytes().chunks_exact(4);
+        if !chunks.remainder().is_empty() {
+            return Option::None;
+        }
+        let count = chunks.len();
+        let mut out = Vec::with_capacity(count * 3);
+        for (k, chunk) in chunks.enumerate() {
+            let pad = chunk.iter().rev().take_while(|c| **c == b'=').count();
+            if pad > 2 || (pad > 0 && k + 1 != count) {
+                return Option::None;
+            }
+            let mut n = 0u32;
+            for (i, c) in chunk[..4 - pad].iter().enumerate() {
+                let v = BASE64.iter().position(|b| b == c)? as u32;
+                n |= v << (18 - 6 * i);
+            }
+            out.extend((0..3 - pad).map(|i| (n >> (16 - 8 * i)) as u8));
+        }
+        Option::Some(out)
+    }
+
+    fn encode_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn decode_hex(s: &str) -> Option<Vec<u8>> {
+        s.as_bytes()
+            .chunks(2)
+            .map(|pair| match pair {
+                [hi, lo] => Option::Some(
+                    (char::from(*hi).to_digit(16)? * 16 + char::from(*lo).to_digit(16)?) as u8,
+                ),
+                _ => Option::None,
+            })
+            .collect()
+    }
+
+    macro_rules! impl_codec {
+        ($optic:ident, $encode:ident, $decode:ident) => {
+            impl<Pv> Preview<String> for $optic<Pv>
+            where
+                Pv: Preview<Vec<u8>>,
+            {
+                type To = Pv::To;
+
+                #[inline]
+                fn preview(&self, source: &String) -> Option<Self::To> {
+                    $decode(source).and_then(|bytes| self.0.preview(&bytes))
+                }
+            }
+
+            impl<St> Setter<String> for $optic<St>
+            where
+                St: Setter<Vec<u8>>,
+            {
+                type To = St::To;
+
+                /// the string stays unchanged if it can't be decoded.
+                #[inline]
+                fn set(&self, source: &mut String, value: Self::To) {
+                    if let Option::Some(mut bytes) = $decode(source) {
+                        self.0.set(&mut bytes, value);
+                        *source = $encode(&bytes);
+                    }
+                }
+            }
+
+            impl<Rv> Review<String> for $optic<Rv>
+            where
+                Rv: Review<Vec<u8>>,
+            {
+                type From = Rv::From;
+
+                #[inline]
+                fn review(&self, from: Self::From) -> String {
+                    $encode(&self.0.review(from))
+                }
+            }
+        };
+    }
+
+    impl_codec!(_base64, encode_base64, decode_base64);
+    impl_codec!(_hex, encode_hex, decode_hex);
+}
+
+mod impl_fetch {
+    /***********************************************************
+     * impl for Fetch
+     ************************************************************/
+    use crate::*;
+
+    impl<Ls, T> LensAsyncRef<T> for _fetch<Ls>
+    where
+        T: Fetch,
+        Ls: LensAsyncRef<T::Output>,
+    {
+        type To = Ls::To;
+
+        #[inline]
+        fn view_async_ref<'a>(&'a self, source: &'a T) -> BoxFuture<'a, &'a Self::To> {
+            Box::pin(async move { self.0.view_async_ref(source.fetch().await).await })
+        }
+    }
+
+    impl<Ls, T> LensAsyncMut<T> for _fetch<Ls>
+    where
+        T: Fetch,
+        Ls: LensAsyncMut<T::Output>,
+    {
+        #[inline]
+        fn view_async_mut<'a>(&'a self, source: &'a mut T) -> BoxFuture<'a, &'a mut Self::To> {
+            Box::pin(async move { self.0.view_async_mut(source.fetch_mut().await).await })
+        }
+    }
+}
+
+// let an optic go through `&S` and `&mut S` as it goes through `S`,
+// the optics generated for the fields and variants get it as well.
+// The markers are in scope wherever this expands, hence the `__` names.
+// `_range` and `_downcast` are left out, their marker carries a type besides the optic.
+macro_rules! impl_through_ref {
+    ($($optic:ident),* $(,)?) => {
+        $(
+            impl<'r, Tr, S> crate::TraversalRef<&'r S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::TraversalRef<S>,
+            {
+                type To = <$optic<Tr> as crate::TraversalRef<S>>::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, __source: &'a &'r S) -> Vec<&'a Self::To> {
+                    crate::TraversalRef::<S>::traverse_ref(self, *__source)
+                }
+
+                #[inline]
+                fn visit_ref<'a>(&self, __source: &'a &'r S, __f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    crate::TraversalRef::<S>::visit_ref(self, *__source, __f)
+                }
+            }
+
+            impl<'r, Tr, S> crate::PrismRef<&'r S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::PrismRef<S>,
+            {
+                #[inline]
+                fn pm_ref<'a>(&self, __source: &'a &'r S) -> Option<&'a Self::To> {
+                    crate::PrismRef::<S>::pm_ref(self, *__source)
+                }
+            }
+
+            impl<'r, Tr, S> crate::LensRef<&'r S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::LensRef<S>,
+            {
+                #[inline]
+                fn view_ref<'a>(&self, __source: &'a &'r S) -> &'a Self::To {
+                    crate::LensRef::<S>::view_ref(self, *__source)
+                }
+            }
+
+            impl<'r, Pv, S> crate::Preview<&'r S> for $optic<Pv>
+            where
+                $optic<Pv>: crate::Preview<S>,
+            {
+                type To = <$optic<Pv> as crate::Preview<S>>::To;
+
+                #[inline]
+                fn preview(&self, __source: &&'r S) -> Option<Self::To> {
+                    crate::Preview::<S>::preview(self, *__source)
+                }
+            }
+
+            impl<'r, Gt, S> crate::Getter<&'r S> for $optic<Gt>
+            where
+                $optic<Gt>: crate::Getter<S>,
+            {
+                #[inline]
+                fn get(&self, __source: &&'r S) -> Self::To {
+                    crate::Getter::<S>::get(self, *__source)
+                }
+            }
+
+            impl<'r, Tr, S> crate::TraversalRef<&'r mut S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::TraversalRef<S>,
+            {
+                type To = <$optic<Tr> as crate::TraversalRef<S>>::To;
+
+                #[inline]
+                fn traverse_ref<'a>(&self, __source: &'a &'r mut S) -> Vec<&'a Self::To> {
+                    crate::TraversalRef::<S>::traverse_ref(self, &**__source)
+                }
+
+                #[inline]
+                fn visit_ref<'a>(&self, __source: &'a &'r mut S, __f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+                    crate::TraversalRef::<S>::visit_ref(self, &**__source, __f)
+                }
+            }
+
+            impl<'r, Tr, S> crate::TraversalMut<&'r mut S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::TraversalMut<S>,
+            {
+                #[inline]
+                fn traverse_mut<'a>(&self, __source: &'a mut &'r mut S) -> Vec<&'a mut Self::To> {
+                    crate::TraversalMut::<S>::traverse_mut(self, &mut **__source)
+                }
+            }
+
+            impl<'r, Tr, S> crate::PrismRef<&'r mut S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::PrismRef<S>,
+            {
+                #[inline]
+                fn pm_ref<'a>(&self, __source: &'a &'r mut S) -> Option<&'a Self::To> {
+                    crate::PrismRef::<S>::pm_ref(self, &**__source)
+                }
+            }
+
+            impl<'r, Tr, S> crate::PrismMut<&'r mut S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::PrismMut<S>,
+            {
+                #[inline]
+                fn pm_mut<'a>(&self, __source: &'a mut &'r mut S) -> Option<&'a mut Self::To> {
+                    crate::PrismMut::<S>::pm_mut(self, &mut **__source)
+                }
+            }
+
+            impl<'r, Tr, S> crate::LensRef<&'r mut S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::LensRef<S>,
+            {
+                #[inline]
+                fn view_ref<'a>(&self, __source: &'a &'r mut S) -> &'a Self::To {
+                    crate::LensRef::<S>::view_ref(self, &**__source)
+                }
+            }
+
+            impl<'r, Tr, S> crate::LensMut<&'r mut S> for $optic<Tr>
+            where
+                $optic<Tr>: crate::LensMut<S>,
+            {
+                #[inline]
+                fn view_mut<'a>(&self, __source: &'a mut &'r mut S) -> &'a mut Self::To {
+                    crate::LensMut::<S>::view_mut(self, &mut **__source)
+                }
+            }
+
+            impl<'r, Pv, S> crate::Preview<&'r mut S> for $optic<Pv>
+            where
+                $optic<Pv>: crate::Preview<S>,
+            {
+                type To = <$optic<Pv> as crate::Preview<S>>::To;
+
+                #[inline]
+                fn preview(&self, __source: &&'r mut S) -> Option<Self::To> {
+                    crate::Preview::<S>::preview(self, &**__source)
+                }
+            }
+
+            impl<'r, Gt, S> crate::Getter<&'r mut S> for $optic<Gt>
+            where
+                $optic<Gt>: crate::Getter<S>,
+            {
+                #[inline]
+                fn get(&self, __source: &&'r mut S) -> Self::To {
+                    crate::Getter::<S>::get(self, &**__source)
+                }
+            }
+
+            impl<'r, St, S> crate::Setter<&'r mut S> for $optic<St>
+            where
+                $optic<St>: crate::Setter<S>,
+            {
+                type To = <$optic<St> as crate::Setter<S>>::To;
+
+                #[inline]
+                fn set(&self, __source: &mut &'r mut S, __value: Self::To) {
+                    crate::Setter::<S>::set(self, &mut **__source, __value)
+                }
+            }
+
+            impl<'r, Ov, S> crate::Over<&'r mut S> for $optic<Ov>
+            where
+                $optic<Ov>: crate::Over<S>,
+            {
+                #[inline]
+                fn over(&self, __source: &mut &'r mut S, __f: &mut dyn FnMut(&mut Self::To)) {
+                    crate::Over::<S>::over(self, &mut **__source, __f)
+                }
+            }
+        )*
+    };
+}
+
+// `a >> b` puts `b` at the end of the path of `a`, in place of its `__`.
+macro_rules! impl_compose {
+    ($($optic:ident),* $(,)?) => {
+        $(
+            impl<Optic, Next> crate::Compose<Next> for $optic<Optic>
+            where
+                Optic: crate::Compose<Next>,
+            {
+                type Output = $optic<Optic::Output>;
+
+                #[inline]
+                fn compose(self, __next: Next) -> Self::Output {
+                    $optic(self.0.compose(__next))
+                }
+            }
+
+            impl<Optic, Next> std::ops::Shr<Next> for $optic<Optic>
+            where
+                Optic: crate::Compose<Next>,
+            {
+                type Output = $optic<Optic::Output>;
+
+                #[inline]
+                fn shr(self, __next: Next) -> Self::Output {
+                    crate::Compose::compose(self, __next)
+                }
+            }
+
+            impl<Optic: crate::ConstOptic> crate::ConstOptic for $optic<Optic> {
+                const OPTIC: Self = $optic(Optic::OPTIC);
+            }
+        )*
+    };
+    // the optics carrying a value, e.g. the index of `_index`, before the rest of the path.
+    ($($optic:ident<$($param:ident),*>),* $(,)?) => {
+        $(
+            impl<$($param,)* Optic, Next> crate::Compose<Next> for $optic<$($param,)* Optic>
+            where
+                Optic: crate::Compose<Next>,
+            {
+                type Output = $optic<$($param,)* Optic::Output>;
+
+                #[inline]
+                fn compose(self, __next: Next) -> Self::Output {
+                    $optic(self.0, self.1.compose(__next))
+                }
+            }
+
+            impl<$($param,)* Optic, Next> std::ops::Shr<Next> for $optic<$($param,)* Optic>
+            where
+                Optic: crate::Compose<Next>,
+            {
+                type Output = $optic<$($param,)* Optic::Output>;
+
+                #[inline]
+                fn shr(self, __next: Next) -> Self::Output {
+                    crate::Compose::compose(self, __next)
+                }
+            }
+        )*
+    };
+}
+
+mod impl_compose {
+    /***********************************************************
+     * impl for composing the optic values
+     ************************************************************/
+    use crate::optics::*;
+
+    impl_compose!(
+        Ok, Err, Some, None, _or_default, _flattened, _V4, _V6, _0, _1, _2, _3, _4, _5, _6, _both,
+        _mapped, _iterated, _rev, _sequence, _box, _ref, _mut, _rc, _arc, _upgraded, _fetch, _utf8,
+        _secs, _secs_f64, _millis, _micros, _since_epoch, _ip, _port, _extension, _file_name,
+        _file_stem, _component_list, _nonzero, _start, _end, _bool, _number, _integer, _float, _string,
+        _datetime, _array, _object
+    );
+    impl_compose!(_key<>, _index<>, _range<R>, _downcast<T>);
+    #[cfg(feature = "tuple16")]
+    impl_compose!(_7, _8, _9, _10, _11, _12, _13, _14, _15);
+    #[cfg(feature = "tuple32")]
+    impl_compose!(
+        _16, _17, _18, _19, _20, _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31
+    );
+    #[cfg(feature = "codec")]
+    impl_compose!(_base64, _hex);
+}
+
+mod impl_through_ref {
+    /***********************************************************
+     * impl for the references of the sources
+     ************************************************************/
+    use crate::optics::*;
+
+    impl_through_ref!(
+        Ok, Err, Some, None, _mapped, _0, _1, _2, _3, _4, _5, _6, _box, _rc, _arc, _index, _key
+    );
+    #[cfg(feature = "tuple16")]
+    impl_through_ref!(_7, _8, _9, _10, _11, _12, _13, _14, _15);
+    #[cfg(feature = "tuple32")]
+    impl_through_ref!(
+        _16, _17, _18, _19, _20, _21, _22, _23, _24, _25, _26, _27, _28, _29, _30, _31
+    );
 }
 
-include!(concat!(env!("OUT_DIR"), "/optics.rs"));
\ No newline at end of file
+/// the path of the generated `optics.rs`, it lists the scanned files grouped by crate,
+/// and the doc of each generated optic tells where its name is found.
+pub const OPTICS_FILE: &str = env!("LENS_RS_OPTICS_FILE");
+
+include!(env!("LENS_RS_OPTICS_FILE"));
\ No newline at end of file
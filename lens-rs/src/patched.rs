@@ -0,0 +1,111 @@
+use crate::*;
+use std::cell::OnceCell;
+use std::ops::Deref;
+use std::rc::Rc;
+
+// shared, so a `patch` of a `Patched` keeps the overrides without running them.
+type Override<S> = Rc<dyn Fn(&mut S)>;
+
+/**
+* A base value with some foci overridden through optics, e.g. the production config of a test
+* with one deep field changed. The overrides are applied to a copy of the base the first time it's read.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let production = ("db.internal".to_string(), (5432u16, vec![Option::Some(30u32), Option::None]));
+* let config = Patched::new(production.clone())
+*     .with(optics!(_0), "localhost".to_string())
+*     .with_over(optics!(_1._1), |timeouts: &mut Vec<Option<u32>>| timeouts.push(Option::Some(1)));
+*
+* assert_eq!(config.0, "localhost");
+* assert_eq!(config.1, (5432, vec![Option::Some(30), Option::None, Option::Some(1)]));
+* assert_eq!(config.base(), &production);
+*
+* let replica = config.patch(optics!(_1._0), 5433).build();
+* assert_eq!((replica.0.as_str(), replica.1 .0), ("localhost", 5433));
+* assert_eq!(config.1 .0, 5432);
+* ```
+*/
+pub struct Patched<S> {
+    base: S,
+    overrides: Vec<Override<S>>,
+    patched: OnceCell<S>,
+}
+
+impl<S> Patched<S> {
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            overrides: vec![],
+            patched: OnceCell::new(),
+        }
+    }
+
+    /// override the focus with `value`, the later overrides win.
+    pub fn with<St>(self, optic: St, value: St::To) -> Self
+    where
+        St: Setter<S> + 'static,
+        St::To: Clone + 'static,
+    {
+        self.push(move |source| optic.set(source, value.clone()))
+    }
+
+    /// like `with`, but modify the focus in place.
+    pub fn with_over<Ov, F>(self, optic: Ov, f: F) -> Self
+    where
+        Ov: Over<S> + 'static,
+        F: Fn(&mut Ov::To) + 'static,
+    {
+        self.push(move |source| optic.over(source, &mut |focus| f(focus)))
+    }
+
+    fn push(mut self, f: impl Fn(&mut S) + 'static) -> Self {
+        self.overrides.push(Rc::new(f));
+        self.patched = OnceCell::new();
+        self
+    }
+
+    /// the value without the overrides.
+    pub fn base(&self) -> &S {
+        &self.base
+    }
+}
+
+impl<S: Clone> Patched<S> {
+    /// the base with the overrides applied.
+    pub fn get(&self) -> &S {
+        self.patched.get_or_init(|| {
+            let mut value = self.base.clone();
+            self.overrides.iter().for_each(|f| f(&mut value));
+            value
+        })
+    }
+
+    /// a copy of the patched value, e.g. to hand over to the code under test.
+    pub fn build(&self) -> S {
+        self.get().clone()
+    }
+
+    /// another `Patched` with the same base and overrides plus one more.
+    pub fn patch<St>(&self, optic: St, value: St::To) -> Patched<S>
+    where
+        St: Setter<S> + 'static,
+        St::To: Clone + 'static,
+    {
+        Patched {
+            base: self.base.clone(),
+            overrides: self.overrides.clone(),
+            patched: OnceCell::new(),
+        }
+        .with(optic, value)
+    }
+}
+
+impl<S: Clone> Deref for Patched<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
@@ -0,0 +1,40 @@
+/*!
+The traits, macros and helpers most code needs, in one import.
+The optic markers are left out, `optics!` and `field!` name them through `lens_rs::optics`,
+so they never shadow `Ok`, `Err`, `Some` or `None`.
+## Example
+```
+use lens_rs::prelude::*;
+
+let mut x = (1, vec![Some(2), None]);
+optics!(_1._mapped.Some).traverse_mut(&mut x).into_iter().for_each(|i| *i += 1);
+assert_eq!(fold_of(optics!(_1._mapped.Some), &x, 0, |sum, i| sum + i), 3);
+assert_eq!(x.1, vec![Some(3), None]);
+```
+The constructor segments of `optics!` are reached through the crate, not the prelude.
+```
+use lens_rs::prelude::*;
+use std::collections::BTreeMap;
+
+let x = ((), BTreeMap::from([(1, "a"), (2, "b"), (3, "c")]));
+assert_eq!(optics!(_1.range(1..3)).traverse_ref(&x), vec![&"a", &"b"]);
+```
+*/
+
+pub use crate::traits::{
+    Fetch, Getter, IntoTraversal, LensAsyncMut, LensAsyncRef, LensMut, LensRef, Over, Preview,
+    PrismMut, PrismRef, Setter, Traversal, TraversalMut, TraversalRef,
+};
+
+// the traits and the derives of the same name.
+pub use crate::{Lens, Optic, Prism, Project, Review};
+
+// the `optics!` macro and the `optics` module of the markers share the name,
+// `__` ends every path `optics!` builds.
+pub use crate::{__, field, match_optics, optics};
+
+pub use crate::builder::Builder;
+pub use crate::combinator::{preview_or, preview_or_else, review_of, try_over, try_traverse_mut};
+pub use crate::defaults::Defaults;
+pub use crate::fold::{find_of, fold_mut_of, fold_of, is_empty_of, length_of, position_of};
+pub use crate::store::Store;
@@ -0,0 +1,193 @@
+use crate::*;
+use std::marker::PhantomData;
+
+/**
+* A lens addressing a field by its byte offset, built by `raw_lens!` from `offset_of!`.
+* It compiles down to a pointer addition, and over a `#[repr(C)]` type it can also read
+* and write the field straight out of a byte buffer, e.g. a FFI buffer or a mmap'd file.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* #[repr(C)]
+* struct Header {
+*     magic: u32,
+*     len: u16,
+* }
+*
+* let len = raw_lens!(Header, len);
+* let mut header = Header { magic: 0xfeed, len: 3 };
+* *len.view_mut(&mut header) += 1;
+* assert_eq!(*len.view_ref(&header), 4);
+*
+* let mut bytes = [0u8; 8];
+* len.write_bytes(&mut bytes, 0x0102).unwrap();
+* assert_eq!(len.read_bytes(&bytes), Option::Some(0x0102));
+* assert_eq!(len.read_bytes(&bytes[..4]), Option::None); // shorter than `Header`
+* ```
+*/
+pub struct RawLens<S, A> {
+    offset: usize,
+    _marker: PhantomData<fn(&S) -> &A>,
+}
+
+impl<S, A> Clone for RawLens<S, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, A> Copy for RawLens<S, A> {}
+
+impl<S, A> std::fmt::Debug for RawLens<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawLens")
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+/// a lens to the field of a type by its offset, `raw_lens!(Type, field)`.
+#[macro_export]
+macro_rules! raw_lens {
+    ($data:ty, $field:tt) => {
+        // safety: the offset comes from `offset_of!`, the accessor pins the type of the field.
+        unsafe {
+            $crate::raw::RawLens::<$data, _>::new(
+                ::core::mem::offset_of!($data, $field),
+                |source: &$data| &source.$field,
+            )
+        }
+    };
+}
+
+impl<S, A> RawLens<S, A> {
+    /// # Safety
+    /// `offset` must be the offset of a field of `S` whose type is `A`,
+    /// `field` only infers `A` and is never called.
+    pub const unsafe fn new(offset: usize, field: fn(&S) -> &A) -> Self {
+        let _ = field;
+        Self {
+            offset,
+            _marker: PhantomData,
+        }
+    }
+
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/**
+* The types which are valid for any bit pattern, so they can be read out of arbitrary bytes.
+* # Safety
+* The type must have no padding, no invalid bit pattern and no pointer.
+*/
+pub unsafe trait Plain: Copy {}
+
+macro_rules! impl_plain {
+    ($($ty:ty),*) => {
+        $(unsafe impl Plain for $ty {})*
+    };
+}
+
+impl_plain!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Plain, const N: usize> Plain for [T; N] {}
+
+impl<S, A: Plain> RawLens<S, A> {
+    /// read the field out of the bytes of a `S`, `None` if they are too short.
+    pub fn read_bytes(&self, bytes: &[u8]) -> Option<A> {
+        if bytes.len() < std::mem::size_of::<S>() {
+            return Option::None;
+        }
+        // safety: the bytes hold the whole `S`, any bit pattern is a valid `A`.
+        Option::Some(unsafe { (bytes.as_ptr().add(self.offset) as *const A).read_unaligned() })
+    }
+
+    /// write the field into the bytes of a `S`, `None` if they are too short.
+    pub fn write_bytes(&self, bytes: &mut [u8], value: A) -> Option<()> {
+        if bytes.len() < std::mem::size_of::<S>() {
+            return Option::None;
+        }
+        // safety: the bytes hold the whole `S`.
+        unsafe { (bytes.as_mut_ptr().add(self.offset) as *mut A).write_unaligned(value) };
+        Option::Some(())
+    }
+}
+
+impl<S, A> TraversalRef<S> for RawLens<S, A> {
+    type To = A;
+
+    #[inline]
+    fn traverse_ref<'a>(&self, source: &'a S) -> Vec<&'a Self::To> {
+        vec![self.view_ref(source)]
+    }
+
+    #[inline]
+    fn visit_ref<'a>(&self, source: &'a S, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+        f(self.view_ref(source))
+    }
+}
+
+impl<S, A> TraversalMut<S> for RawLens<S, A> {
+    #[inline]
+    fn traverse_mut<'a>(&self, source: &'a mut S) -> Vec<&'a mut Self::To> {
+        vec![self.view_mut(source)]
+    }
+}
+
+impl<S, A> PrismRef<S> for RawLens<S, A> {
+    #[inline]
+    fn pm_ref<'a>(&self, source: &'a S) -> Option<&'a Self::To> {
+        Option::Some(self.view_ref(source))
+    }
+}
+
+impl<S, A> PrismMut<S> for RawLens<S, A> {
+    #[inline]
+    fn pm_mut<'a>(&self, source: &'a mut S) -> Option<&'a mut Self::To> {
+        Option::Some(self.view_mut(source))
+    }
+}
+
+impl<S, A> LensRef<S> for RawLens<S, A> {
+    #[inline]
+    fn view_ref<'a>(&self, source: &'a S) -> &'a Self::To {
+        // safety: `new` guarantees a field of type `A` at the offset.
+        unsafe { &*((source as *const S as *const u8).add(self.offset) as *const A) }
+    }
+}
+
+impl<S, A> LensMut<S> for RawLens<S, A> {
+    #[inline]
+    fn view_mut<'a>(&self, source: &'a mut S) -> &'a mut Self::To {
+        // safety: `new` guarantees a field of type `A` at the offset.
+        unsafe { &mut *((source as *mut S as *mut u8).add(self.offset) as *mut A) }
+    }
+}
+
+impl<S, A: Clone> Preview<S> for RawLens<S, A> {
+    type To = A;
+
+    #[inline]
+    fn preview(&self, source: &S) -> Option<Self::To> {
+        Option::Some(self.get(source))
+    }
+}
+
+impl<S, A: Clone> Getter<S> for RawLens<S, A> {
+    #[inline]
+    fn get(&self, source: &S) -> Self::To {
+        self.view_ref(source).clone()
+    }
+}
+
+impl<S, A> Setter<S> for RawLens<S, A> {
+    type To = A;
+
+    #[inline]
+    fn set(&self, source: &mut S, value: Self::To) {
+        *self.view_mut(source) = value;
+    }
+}
@@ -0,0 +1,89 @@
+use crate::*;
+use std::fmt;
+use std::ops::Deref;
+
+type Mask<S> = Box<dyn Fn(&mut S)>;
+
+/**
+* A wrapper whose `Debug` and `Display` mask the foci of some optics, e.g. tokens and passwords.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let login = Redacted::new(("alice".to_string(), "hunter2".to_string(), vec![1234, 5678]))
+*     .redact(optics!(_1))
+*     .mask(optics!(_2._mapped), 0);
+* assert_eq!(format!("{:?}", login), r#"("alice", "<redacted>", [0, 0])"#);
+* assert_eq!(login.1, "hunter2");
+* ```
+*/
+pub struct Redacted<S> {
+    value: S,
+    masks: Vec<Mask<S>>,
+}
+
+impl<S> Redacted<S> {
+    pub fn new(value: S) -> Self {
+        Self {
+            value,
+            masks: vec![],
+        }
+    }
+
+    /// print every focus of the optic as `mask`.
+    pub fn mask<Tr>(mut self, optic: Tr, mask: Tr::To) -> Self
+    where
+        Tr: TraversalMut<S> + 'static,
+        Tr::To: Clone + 'static,
+    {
+        self.masks.push(Box::new(move |source| {
+            optic
+                .traverse_mut(source)
+                .into_iter()
+                .for_each(|x| *x = mask.clone())
+        }));
+        self
+    }
+
+    /// print every focus of the optic as `"<redacted>"`.
+    pub fn redact<Tr>(self, optic: Tr) -> Self
+    where
+        Tr: TraversalMut<S> + 'static,
+        Tr::To: From<&'static str> + Clone + 'static,
+    {
+        self.mask(optic, "<redacted>".into())
+    }
+
+    pub fn into_inner(self) -> S {
+        self.value
+    }
+
+    fn masked(&self) -> S
+    where
+        S: Clone,
+    {
+        let mut value = self.value.clone();
+        self.masks.iter().for_each(|mask| mask(&mut value));
+        value
+    }
+}
+
+impl<S> Deref for Redacted<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.value
+    }
+}
+
+impl<S: Clone + fmt::Debug> fmt::Debug for Redacted<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.masked(), f)
+    }
+}
+
+impl<S: Clone + fmt::Display> fmt::Display for Redacted<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.masked(), f)
+    }
+}
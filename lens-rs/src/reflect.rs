@@ -0,0 +1,439 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{OnceLock, RwLock};
+
+/**
+* A type-erased description of a derived optic, `#[derive(Lens)]` lists them in `T::OPTICS`
+* so a settings editor or a debug inspector can walk the fields at runtime.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* #[derive(Lens)]
+* struct Point(#[optic] i32, #[optic(ref)] String);
+*
+* let mut p = Point(1, "origin".to_string());
+* let names: Vec<_> = Point::OPTICS.iter().map(|o| (o.name, o.type_name)).collect();
+* assert_eq!(names, vec![("_0", "i32"), ("_1", "String")]);
+*
+* *Point::OPTICS[0].view_mut::<i32>(&mut p).unwrap() += 1;
+* assert_eq!(Point::OPTICS[0].view_ref::<i32>(&p), Option::Some(&2));
+* assert!(Point::OPTICS[1].view_mut::<String>(&mut p).is_none()); // `#[optic(ref)]`
+*
+* let watched: std::collections::HashSet<_> = vec![Point::OPTICS[0], Point::OPTICS[0]].into_iter().collect();
+* assert!(watched.len() == 1 && watched.contains(&Point::OPTICS[0]));
+* ```
+*
+* `#[optic(fields)]` on the struct also generates the enum `{Struct}Field`, one variant per entry of `OPTICS`,
+* `info()` is its entry.
+* ```
+* use lens_rs::*;
+*
+* #[derive(Lens)]
+* #[optic(fields)]
+* struct Size(#[optic] u32, #[optic(ref)] u32);
+*
+* let mut size = Size(640, 480);
+* assert_eq!(SizeField::ALL, &[SizeField::Field0, SizeField::Field1]);
+* assert_eq!(SizeField::from_name("_1"), Option::Some(SizeField::Field1));
+* *SizeField::Field0.info().view_mut::<u32>(&mut size).unwrap() = 800;
+* assert_eq!(size.0, 800);
+* assert_eq!(SizeField::Field1.name(), "_1");
+* ```
+*
+* A field with a restricted `vis` is left out of `OPTICS`, its optic is the marker in the module `{type}_optics`
+* next to the type, as visible as `vis`.
+* ```
+* mod bank {
+*     use lens_rs::*;
+*
+*     #[derive(Lens)]
+*     pub struct Account(#[optic(ref)] pub String, #[optic(mut, vis = "pub(self)")] u64);
+*
+*     impl Account {
+*         pub fn open(owner: &str) -> Self {
+*             Account(owner.to_string(), 0)
+*         }
+*
+*         pub fn deposit(&mut self, amount: u64) {
+*             *account_optics::_1(__).view_mut(self) += amount;
+*         }
+*
+*         pub fn balance(&self) -> u64 {
+*             *account_optics::_1(__).view_ref(self)
+*         }
+*     }
+* }
+*
+* let mut account = bank::Account::open("ada");
+* account.deposit(5);
+* assert_eq!(account.balance(), 5);
+* assert_eq!(bank::Account::OPTICS.len(), 1);
+* ```
+* ```compile_fail
+* mod bank {
+*     use lens_rs::*;
+*
+*     #[derive(Lens)]
+*     pub struct Account(#[optic(ref)] pub String, #[optic(mut, vis = "pub(self)")] u64);
+*
+*     impl Account {
+*         pub fn open(owner: &str) -> Self {
+*             Account(owner.to_string(), 0)
+*         }
+*
+*         pub fn deposit(&mut self, amount: u64) {
+*             *account_optics::_1(__).view_mut(self) += amount;
+*         }
+*
+*         pub fn balance(&self) -> u64 {
+*             *account_optics::_1(__).view_ref(self)
+*         }
+*     }
+* }
+*
+* use lens_rs::*;
+* let account = bank::Account::open("ada");
+* optics!(_1).view_ref(&account);
+* ```
+* ```compile_fail
+* mod bank {
+*     use lens_rs::*;
+*
+*     #[derive(Lens)]
+*     pub struct Account(#[optic(ref)] pub String, #[optic(mut, vis = "pub(self)")] u64);
+*
+*     impl Account {
+*         pub fn open(owner: &str) -> Self {
+*             Account(owner.to_string(), 0)
+*         }
+*
+*         pub fn deposit(&mut self, amount: u64) {
+*             *account_optics::_1(__).view_mut(self) += amount;
+*         }
+*
+*         pub fn balance(&self) -> u64 {
+*             *account_optics::_1(__).view_ref(self)
+*         }
+*     }
+* }
+*
+* use lens_rs::*;
+* let account = bank::Account::open("ada");
+* bank::account_optics::_1(__).view_ref(&account);
+* ```
+*/
+pub struct OpticInfo<T> {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    /// the doc comment of the field, empty if it has none.
+    pub description: &'static str,
+    pub get: fn(&T) -> &dyn Any,
+    /// `None` for the fields marked `#[optic(ref)]`.
+    pub get_mut: Option<fn(&mut T) -> &mut dyn Any>,
+    /// a hash of the names and types of the `#[optic]` fields of `T`, the same for all its optics,
+    /// it changes when the fields do.
+    pub version: u64,
+}
+
+impl<T> OpticInfo<T> {
+    pub fn view_ref<'a, A: Any>(&self, source: &'a T) -> Option<&'a A> {
+        (self.get)(source).downcast_ref()
+    }
+
+    pub fn view_mut<'a, A: Any>(&self, source: &'a mut T) -> Option<&'a mut A> {
+        self.get_mut?(source).downcast_mut()
+    }
+}
+
+impl<T> Clone for OpticInfo<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for OpticInfo<T> {}
+
+// the name is the path of the optic in `T`, the accessors of the same field needn't be the same function pointers.
+impl<T> PartialEq for OpticInfo<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl<T> Eq for OpticInfo<T> {}
+
+impl<T> Hash for OpticInfo<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state)
+    }
+}
+
+/**
+* The doc comment and the type of the field an optic focuses in `T`, implemented by `#[derive(Lens)]`
+* for the same fields as `T::OPTICS`, so settings editors can label them.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* #[derive(Lens)]
+* struct Window(
+*     /// the width in pixels
+*     #[optic]
+*     u32,
+* );
+*
+* assert_eq!(<field![_0] as OpticDoc<Window>>::DESCRIPTION, "the width in pixels");
+* assert_eq!(<field![_0] as OpticDoc<Window>>::FOCUS_TYPE_NAME, "u32");
+* assert_eq!(Window::OPTICS[0].description, "the width in pixels");
+* ```
+*/
+pub trait OpticDoc<T> {
+    const DESCRIPTION: &'static str;
+    const FOCUS_TYPE_NAME: &'static str;
+}
+
+type Get = Box<dyn for<'a> Fn(&'a dyn Any) -> Option<&'a dyn Any> + Send + Sync>;
+type GetMut = Box<dyn for<'a> Fn(&'a mut dyn Any) -> Option<&'a mut dyn Any> + Send + Sync>;
+
+struct Entry {
+    name: &'static str,
+    type_name: &'static str,
+    version: u64,
+    get: Get,
+    get_mut: GetMut,
+}
+
+/**
+* The `OPTICS` of the registered types by name, so a scripting language or a debug console
+* can read and write `"TypeName.field.path"`s at runtime.
+* A path goes into a field only if the type of the field is registered as well.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* #[derive(Lens)]
+* struct Server(#[optic] u16);
+*
+* #[derive(Lens)]
+* struct Config(#[optic] Server, #[optic(ref)] String);
+*
+* Registry::global()
+*     .write()
+*     .unwrap()
+*     .register("Config", Config::OPTICS)
+*     .register("Server", Server::OPTICS);
+*
+* let mut config = Config(Server(80), "prod".to_string());
+* let registry = Registry::global().read().unwrap();
+* let port = registry.view_mut("Config._0._0", &mut config).unwrap();
+* *port.downcast_mut::<u16>().unwrap() = 8080;
+* assert_eq!(config.0 .0, 8080);
+*
+* let name = registry.view_ref("Config._1", &config).unwrap();
+* assert_eq!(name.downcast_ref::<String>().unwrap(), "prod");
+* assert!(registry.view_mut("Config._1", &mut config).is_none()); // `#[optic(ref)]`
+* assert!(registry.view_ref("Server._0", &config).is_none()); // not a `Server`
+* ```
+*/
+#[derive(Default)]
+pub struct Registry {
+    names: HashMap<&'static str, TypeId>,
+    optics: HashMap<TypeId, Vec<Entry>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the registry shared by the whole program, it's empty until some types are registered.
+    pub fn global() -> &'static RwLock<Registry> {
+        static GLOBAL: OnceLock<RwLock<Registry>> = OnceLock::new();
+        GLOBAL.get_or_init(|| RwLock::new(Registry::new()))
+    }
+
+    /// register the optics of `T` under `name`, replacing the ones registered before.
+    pub fn register<T: Any>(
+        &mut self,
+        name: &'static str,
+        optics: &'static [OpticInfo<T>],
+    ) -> &mut Self {
+        let entries = optics
+            .iter()
+            .map(|info| Entry {
+                name: info.name,
+                type_name: info.type_name,
+                version: info.version,
+                get: Box::new(move |source: &dyn Any| source.downcast_ref::<T>().map(info.get)),
+                get_mut: Box::new(move |source: &mut dyn Any| {
+                    let get_mut = info.get_mut?;
+                    source.downcast_mut::<T>().map(get_mut)
+                }),
+            })
+            .collect();
+        self.names.insert(name, TypeId::of::<T>());
+        self.optics.insert(TypeId::of::<T>(), entries);
+        self
+    }
+
+    fn entry(&self, ty: TypeId, field: &str) -> Option<&Entry> {
+        self.optics
+            .get(&ty)?
+            .iter()
+            .find(|entry| entry.name == field)
+    }
+
+    // the fields of the path, if it starts with the name of the type of the source.
+    fn fields<'p>(&self, path: &'p str, ty: TypeId) -> Option<std::str::Split<'p, char>> {
+        let mut segments = path.split('.');
+        let name = segments.next()?;
+        (self.names.get(name) == Option::Some(&ty)).then_some(segments)
+    }
+
+    pub fn view_ref<'a>(&self, path: &str, source: &'a dyn Any) -> Option<&'a dyn Any> {
+        self.fields(path, source.type_id())?
+            .try_fold(source, |focus, field| {
+                (self.entry(focus.type_id(), field)?.get)(focus)
+            })
+    }
+
+    pub fn view_mut<'a>(&self, path: &str, source: &'a mut dyn Any) -> Option<&'a mut dyn Any> {
+        self.fields(path, (*source).type_id())?
+            .try_fold(source, |focus, field| {
+                (self.entry((*focus).type_id(), field)?.get_mut)(focus)
+            })
+    }
+
+    /// the type of the focus of the path in the source, to declare in a patch of it.
+    pub fn focus_type(&self, path: &str, source: &dyn Any) -> Option<FocusType> {
+        let (entry, _) = self.fields(path, source.type_id())?.try_fold(
+            (Option::None, source),
+            |(_, focus), field| {
+                let entry = self.entry(focus.type_id(), field)?;
+                Option::Some((Option::Some(entry), (entry.get)(focus)?))
+            },
+        )?;
+        entry.map(|entry| FocusType {
+            type_name: entry.type_name.to_string(),
+            version: entry.version,
+        })
+    }
+
+    /// check a patch made for the `declared` focus still fits the types of this program.
+    pub fn check_patch(
+        &self,
+        path: &str,
+        declared: &FocusType,
+        source: &dyn Any,
+    ) -> Result<(), PatchError> {
+        let found = self
+            .focus_type(path, source)
+            .ok_or_else(|| PatchError::NoSuchPath {
+                path: path.to_string(),
+            })?;
+        if found == *declared {
+            Result::Ok(())
+        } else {
+            Result::Err(PatchError::TypeMismatch {
+                path: path.to_string(),
+                declared: declared.clone(),
+                found,
+            })
+        }
+    }
+
+    /// the focus to apply the patch to, after `check_patch`.
+    pub fn patch_mut<'a>(
+        &self,
+        path: &str,
+        declared: &FocusType,
+        source: &'a mut dyn Any,
+    ) -> Result<&'a mut dyn Any, PatchError> {
+        self.check_patch(path, declared, &*source)?;
+        self.view_mut(path, source)
+            .ok_or_else(|| PatchError::ReadOnly {
+                path: path.to_string(),
+            })
+    }
+}
+
+/**
+* The type of the focus a serialized patch was made for, so a patch from an older build
+* is rejected by `Registry::check_patch` instead of being applied to a field that has changed.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* #[derive(Lens)]
+* struct Server(#[optic] u16, #[optic(ref)] String);
+*
+* let mut registry = Registry::new();
+* registry.register("Server", Server::OPTICS);
+* let mut server = Server(80, "web".to_string());
+*
+* let port = registry.focus_type("Server._0", &server).unwrap();
+* assert_eq!(port, FocusType { type_name: "u16".to_string(), version: Server::OPTICS[0].version });
+* *registry.patch_mut("Server._0", &port, &mut server).unwrap().downcast_mut::<u16>().unwrap() = 8080;
+* assert_eq!(server.0, 8080);
+*
+* // made when the port was a `u32`
+* let stale = FocusType { type_name: "u32".to_string(), ..port.clone() };
+* let error = registry.patch_mut("Server._0", &stale, &mut server).unwrap_err();
+* assert!(matches!(error, PatchError::TypeMismatch { .. }));
+* assert_eq!(error.to_string(), "`Server._0` is a `u16`, the patch is for a `u32`");
+*
+* let name = registry.focus_type("Server._1", &server).unwrap();
+* assert!(matches!(registry.patch_mut("Server._1", &name, &mut server), Result::Err(PatchError::ReadOnly { .. })));
+* assert!(matches!(registry.check_patch("Server._2", &port, &server), Result::Err(PatchError::NoSuchPath { .. })));
+* ```
+*/
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FocusType {
+    pub type_name: String,
+    /// the `OpticInfo::version` of the type holding the focus.
+    pub version: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PatchError {
+    /// the path isn't a path of registered types from the source.
+    NoSuchPath { path: String },
+    /// the focus has another type, or the type holding it has changed since the patch was made.
+    TypeMismatch {
+        path: String,
+        declared: FocusType,
+        found: FocusType,
+    },
+    /// the path goes through a field marked `#[optic(ref)]`.
+    ReadOnly { path: String },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::NoSuchPath { path } => write!(f, "no `{}` in the source", path),
+            PatchError::TypeMismatch {
+                path,
+                declared,
+                found,
+            } if declared.type_name != found.type_name => write!(
+                f,
+                "`{}` is a `{}`, the patch is for a `{}`",
+                path, found.type_name, declared.type_name
+            ),
+            PatchError::TypeMismatch { path, .. } => {
+                write!(
+                    f,
+                    "the type holding `{}` has changed since the patch was made",
+                    path
+                )
+            }
+            PatchError::ReadOnly { path } => write!(f, "`{}` is read-only", path),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
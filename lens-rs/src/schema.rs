@@ -0,0 +1,195 @@
+/*!
+* Check a serialized patch, a JSON Pointer and a value, against the JSON Schema of the patched type
+* before applying it through `JsonPointer`.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::json::JsonPointer;
+* use lens_rs::schema::{check_patch, schema_pointer};
+* use schemars::{schema_for, JsonSchema};
+* use serde_json::json;
+*
+* #[derive(JsonSchema)]
+* struct Server {
+*     host: String,
+*     ports: (u16, Option<u16>),
+* }
+*
+* let schema = schema_for!(Server);
+* let port = JsonPointer::from_optic_path("ports._1.Some");
+* assert_eq!(port.to_string(), "/ports/1");
+* assert!(schema_pointer(&schema, &port).is_some());
+* assert!(check_patch(&schema, &port, &json!(8080)).is_ok());
+* assert!(check_patch(&schema, &port, &json!("8080")).is_err());
+* assert!(check_patch(&schema, &JsonPointer::from_optic_path("name"), &json!("a")).is_err());
+* ```
+*/
+use crate::json::JsonPointer;
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaError {
+    /// the pointer of the patch.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.pointer)
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+// a schema the focus may match, with its pointer in the schema document, `None` accepts anything.
+type Candidate<'s> = (String, Option<&'s SchemaObject>);
+
+fn expand_schema<'s>(
+    root: &'s RootSchema,
+    pointer: String,
+    schema: &'s Schema,
+    out: &mut Vec<Candidate<'s>>,
+) {
+    match schema {
+        Schema::Bool(true) => out.push((pointer, Option::None)),
+        Schema::Bool(false) => (),
+        Schema::Object(object) => expand(root, pointer, object, out),
+    }
+}
+
+// follow `$ref` into the definitions and split `anyOf`/`oneOf`, e.g. of an `Option`, into the alternatives.
+fn expand<'s>(
+    root: &'s RootSchema,
+    pointer: String,
+    object: &'s SchemaObject,
+    out: &mut Vec<Candidate<'s>>,
+) {
+    if let Option::Some(name) = object
+        .reference
+        .as_deref()
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+    {
+        if let Option::Some(definition) = root.definitions.get(name) {
+            expand_schema(root, format!("#/definitions/{}", name), definition, out);
+        }
+        return;
+    }
+    let alternatives = object.subschemas.as_ref().and_then(|sub| {
+        (sub.any_of.as_ref().map(|any_of| ("anyOf", any_of)))
+            .or_else(|| sub.one_of.as_ref().map(|one_of| ("oneOf", one_of)))
+    });
+    match alternatives {
+        Option::Some((keyword, alternatives)) => {
+            alternatives
+                .iter()
+                .enumerate()
+                .for_each(|(i, alternative)| {
+                    expand_schema(
+                        root,
+                        format!("{}/{}/{}", pointer, keyword, i),
+                        alternative,
+                        out,
+                    )
+                })
+        }
+        Option::None => out.push((pointer, Option::Some(object))),
+    }
+}
+
+fn step<'s>(
+    root: &'s RootSchema,
+    (pointer, object): Candidate<'s>,
+    token: &str,
+    out: &mut Vec<Candidate<'s>>,
+) {
+    let object = match object {
+        Option::Some(object) => object,
+        Option::None => return out.push((format!("{}/{}", pointer, token), Option::None)),
+    };
+    if let Option::Some(property) = object.object.as_ref().and_then(|o| o.properties.get(token)) {
+        expand_schema(
+            root,
+            format!("{}/properties/{}", pointer, token),
+            property,
+            out,
+        );
+    }
+    match (
+        object.array.as_ref().and_then(|a| a.items.as_ref()),
+        token.parse::<usize>(),
+    ) {
+        (Option::Some(SingleOrVec::Single(item)), Result::Ok(_)) => {
+            expand_schema(root, format!("{}/items", pointer), item, out)
+        }
+        (Option::Some(SingleOrVec::Vec(items)), Result::Ok(i)) if i < items.len() => {
+            expand_schema(root, format!("{}/items/{}", pointer, i), &items[i], out)
+        }
+        _ => (),
+    }
+}
+
+fn candidates<'s>(schema: &'s RootSchema, pointer: &JsonPointer) -> Vec<Candidate<'s>> {
+    let mut root = vec![];
+    expand(schema, "#".to_string(), &schema.schema, &mut root);
+    pointer.tokens().iter().fold(root, |current, token| {
+        let mut next = vec![];
+        current
+            .into_iter()
+            .for_each(|candidate| step(schema, candidate, token, &mut next));
+        next
+    })
+}
+
+/// the pointer into the schema document of the schema of the patched focus, if the type has it.
+pub fn schema_pointer(schema: &RootSchema, pointer: &JsonPointer) -> Option<String> {
+    candidates(schema, pointer)
+        .into_iter()
+        .next()
+        .map(|(pointer, _)| pointer)
+}
+
+fn instance_type(value: &Value) -> InstanceType {
+    match value {
+        Value::Null => InstanceType::Null,
+        Value::Bool(_) => InstanceType::Boolean,
+        Value::Number(n) if n.is_i64() || n.is_u64() => InstanceType::Integer,
+        Value::Number(_) => InstanceType::Number,
+        Value::String(_) => InstanceType::String,
+        Value::Array(_) => InstanceType::Array,
+        Value::Object(_) => InstanceType::Object,
+    }
+}
+
+fn accepts(object: Option<&SchemaObject>, value: &Value) -> bool {
+    let types = match object.and_then(|object| object.instance_type.as_ref()) {
+        Option::Some(types) => types,
+        Option::None => return true,
+    };
+    let ty = instance_type(value);
+    // an integer is a number as well
+    types.contains(&ty) || (ty == InstanceType::Integer && types.contains(&InstanceType::Number))
+}
+
+/// check the patch addresses a focus of the schema, and the value has the type of the focus.
+pub fn check_patch(
+    schema: &RootSchema,
+    pointer: &JsonPointer,
+    value: &Value,
+) -> Result<(), SchemaError> {
+    let error = |message: &str| SchemaError {
+        pointer: pointer.to_string(),
+        message: message.to_string(),
+    };
+    let candidates = candidates(schema, pointer);
+    if candidates.is_empty() {
+        return Result::Err(error("no such field in the schema"));
+    }
+    if candidates.iter().any(|(_, object)| accepts(*object, value)) {
+        Result::Ok(())
+    } else {
+        Result::Err(error("the value doesn't have the type of the field"))
+    }
+}
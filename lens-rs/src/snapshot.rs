@@ -0,0 +1,129 @@
+use crate::*;
+use std::panic::{self, AssertUnwindSafe};
+
+/**
+* Checkpoint the foci of an optic, cloning only them, to roll them back later.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut state = (vec![1, 2, 3], "a large part left alone".to_string());
+* let snapshot = snapshot_of(optics!(_0._mapped), &state);
+*
+* optics!(_0._mapped).traverse_mut(&mut state).into_iter().for_each(|x| *x *= 10);
+* state.1.push('!');
+* restore(snapshot, &mut state);
+* assert_eq!(state, (vec![1, 2, 3], "a large part left alone!".to_string()));
+* ```
+*/
+pub fn snapshot_of<Tr, T>(optic: Tr, source: &T) -> Snapshot<Tr, Tr::To>
+where
+    Tr: TraversalRef<T>,
+    Tr::To: Clone,
+{
+    let foci = optic.traverse_ref(source).into_iter().cloned().collect();
+    Snapshot { optic, foci }
+}
+
+/// write the foci of the snapshot back, in traversal order.
+pub fn restore<Tr, T>(snapshot: Snapshot<Tr, Tr::To>, source: &mut T)
+where
+    Tr: TraversalMut<T>,
+{
+    snapshot.restore(source)
+}
+
+/**
+* Clone the source sharing everything but the foci, for persistent updates of a large state:
+* the pointees behind `_rc`/`_arc` on the way to a focus are cloned, the other `Rc`s and `Arc`s are only shared.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::rc::Rc;
+*
+* let state = (Rc::new((vec![1, 2], "a".to_string())), Rc::new(vec![0u8; 1 << 20]));
+* let mut next = clone_of(optics!(_0._rc._0), &state);
+* optics!(_0._rc._0).view_mut(&mut next).push(3);
+*
+* assert_eq!(state.0 .0, vec![1, 2]);
+* assert_eq!(next.0 .0, vec![1, 2, 3]);
+* assert!(Rc::ptr_eq(&state.1, &next.1));
+* ```
+*/
+pub fn clone_of<Tr, T>(optic: Tr, source: &T) -> T
+where
+    Tr: TraversalMut<T>,
+    T: Clone,
+{
+    let mut cloned = source.clone();
+    optic.traverse_mut(&mut cloned);
+    cloned
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot<Tr, A> {
+    optic: Tr,
+    foci: Vec<A>,
+}
+
+impl<Tr, A> Snapshot<Tr, A> {
+    pub fn foci(&self) -> &[A] {
+        &self.foci
+    }
+
+    /// the foci missing from the source now are dropped, the new ones are left alone.
+    pub fn restore<T>(self, source: &mut T)
+    where
+        Tr: TraversalMut<T, To = A>,
+    {
+        self.optic
+            .traverse_mut(source)
+            .into_iter()
+            .zip(self.foci)
+            .for_each(|(x, focus)| *x = focus)
+    }
+}
+
+/**
+* Edit the focus of a lens all or nothing: the focus is cloned first
+* and restored if `f` returns an error or panics, the rest of the source is never cloned.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut account = ("alice", (100, vec!["open"]));
+* let r = transact(optics!(_1), &mut account, |(balance, log)| {
+*     *balance -= 150;
+*     log.push("withdraw 150");
+*     if *balance < 0 { Result::Err("overdrawn") } else { Result::Ok(*balance) }
+* });
+* assert_eq!(r, Result::Err("overdrawn"));
+* assert_eq!(account.1, (100, vec!["open"]));
+*
+* let r = transact(optics!(_1._0), &mut account, |balance| -> Result<_, ()> {
+*     *balance -= 30;
+*     Result::Ok(*balance)
+* });
+* assert_eq!(r, Result::Ok(70));
+* ```
+*/
+pub fn transact<Ls, T, R, E, F>(optic: Ls, source: &mut T, f: F) -> Result<R, E>
+where
+    Ls: LensMut<T>,
+    Ls::To: Clone,
+    F: FnOnce(&mut Ls::To) -> Result<R, E>,
+{
+    let focus = optic.view_mut(source);
+    let backup = focus.clone();
+    match panic::catch_unwind(AssertUnwindSafe(|| f(&mut *focus))) {
+        Result::Ok(Result::Ok(r)) => Result::Ok(r),
+        Result::Ok(Result::Err(e)) => {
+            *focus = backup;
+            Result::Err(e)
+        }
+        Result::Err(payload) => {
+            *focus = backup;
+            panic::resume_unwind(payload)
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::*;
+
+type Listener<S> = Box<dyn FnMut(&S)>;
+
+/**
+* A state container whose reads and writes are addressed by optics.
+* Subscribers watch a focus and are only notified when it actually changes.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::{cell::Cell, rc::Rc};
+*
+* let mut store = Store::new((1, (2, 3)));
+* let hits = Rc::new(Cell::new(0));
+* let counter = hits.clone();
+* store.subscribe(optics!(_1._0), move |_: &i32| counter.set(counter.get() + 1));
+*
+* store.dispatch(optics!(_0), |x| *x += 1);  // `_1._0` unchanged, no notification
+* store.dispatch(optics!(_1._0), |x| *x *= 2);
+* assert_eq!(*store.select(optics!(_1._0)), 4);
+* assert_eq!(hits.get(), 1);
+* ```
+*/
+pub struct Store<S> {
+    state: S,
+    listeners: Vec<Listener<S>>,
+}
+
+impl<S> Store<S> {
+    pub fn new(state: S) -> Self {
+        Self {
+            state,
+            listeners: vec![],
+        }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
+    pub fn select<Ls>(&self, optic: Ls) -> &Ls::To
+    where
+        Ls: LensRef<S>,
+    {
+        optic.view_ref(&self.state)
+    }
+
+    /// update the focus, then notify every subscriber whose focus changed.
+    pub fn dispatch<Ls, F>(&mut self, optic: Ls, f: F)
+    where
+        Ls: LensMut<S>,
+        F: FnOnce(&mut Ls::To),
+    {
+        f(optic.view_mut(&mut self.state));
+        let state = &self.state;
+        self.listeners
+            .iter_mut()
+            .for_each(|listener| listener(state));
+    }
+
+    pub fn subscribe<Ls, F>(&mut self, optic: Ls, mut f: F)
+    where
+        Ls: LensRef<S> + 'static,
+        Ls::To: Clone + PartialEq + 'static,
+        F: FnMut(&Ls::To) + 'static,
+    {
+        let mut last = optic.view_ref(&self.state).clone();
+        self.listeners.push(Box::new(move |state: &S| {
+            let current = optic.view_ref(state);
+            if *current != last {
+                last = current.clone();
+                f(current);
+            }
+        }));
+    }
+}
+
+/**
+* A derived view over a focus of a `Store`, recomputed only when the focus changes.
+* ## Example
+* ```
+* use lens_rs::*;
+*
+* let mut store = Store::new((vec![1, 2, 3], 0));
+* let mut total = Selector::new(optics!(_0), |xs: &Vec<i32>| xs.iter().sum::<i32>());
+* assert_eq!(*total.get(&store), 6);
+*
+* store.dispatch(optics!(_1), |x| *x += 1);  // cached
+* store.dispatch(optics!(_0), |xs| xs.push(4));
+* assert_eq!(*total.get(&store), 10);
+* ```
+*/
+pub struct Selector<Ls, A, B, F> {
+    optic: Ls,
+    compute: F,
+    cache: Option<(A, B)>,
+}
+
+impl<Ls, A, B, F> Selector<Ls, A, B, F>
+where
+    A: Clone + PartialEq,
+    F: FnMut(&A) -> B,
+{
+    pub fn new(optic: Ls, compute: F) -> Self {
+        Self {
+            optic,
+            compute,
+            cache: Option::None,
+        }
+    }
+
+    pub fn get<S>(&mut self, store: &Store<S>) -> &B
+    where
+        Ls: LensRef<S, To = A>,
+    {
+        let focus = self.optic.view_ref(store.state());
+        let fresh = match &self.cache {
+            Option::Some((last, _)) => last == focus,
+            Option::None => false,
+        };
+        if !fresh {
+            self.cache = Option::Some((focus.clone(), (self.compute)(focus)));
+        }
+        &self.cache.as_ref().unwrap().1
+    }
+}
@@ -0,0 +1,78 @@
+use crate::*;
+use std::sync::{PoisonError, RwLock};
+
+/**
+* Read the focus of a lens under the read lock, the lock is held only while `f` runs.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::sync::{Arc, RwLock};
+*
+* let state = Arc::new(RwLock::new((1, (2, 3))));
+* assert_eq!(view_via(&state, optics!(_1._0), |x| *x * 10).unwrap(), 20);
+* ```
+*/
+pub fn view_via<Ls, S, R, F>(lock: &RwLock<S>, optic: Ls, f: F) -> Result<R, PoisonError<()>>
+where
+    Ls: LensRef<S>,
+    F: FnOnce(&Ls::To) -> R,
+{
+    let guard = lock.read().map_err(|_| PoisonError::new(()))?;
+    Result::Ok(f(optic.view_ref(&guard)))
+}
+
+/**
+* Update every focus of the optic under the write lock, the lock is held only while the foci are updated.
+* ## Example
+* ```
+* use lens_rs::*;
+* use std::sync::{Arc, RwLock};
+* use std::thread;
+*
+* let state = Arc::new(RwLock::new((0, vec![1, 2])));
+* let handles: Vec<_> = (0..4)
+*     .map(|_| {
+*         let state = state.clone();
+*         thread::spawn(move || update_via(&state, optics!(_1._mapped), |x| *x += 1))
+*     })
+*     .collect();
+* handles.into_iter().for_each(|h| h.join().unwrap().unwrap());
+* assert_eq!(state.read().unwrap().1, vec![5, 6]);
+* ```
+*/
+pub fn update_via<Tr, S, F>(lock: &RwLock<S>, optic: Tr, f: F) -> Result<(), PoisonError<()>>
+where
+    Tr: TraversalMut<S>,
+    F: FnMut(&mut Tr::To),
+{
+    let mut guard = lock.write().map_err(|_| PoisonError::new(()))?;
+    optic.traverse_mut(&mut guard).into_iter().for_each(f);
+    Result::Ok(())
+}
+
+/**
+* Update every focus of the optic on a copy of the current state and swap it in,
+* retrying from the new state if another writer swapped first, so `f` may run more than once.
+* ## Example
+* ```
+* use lens_rs::*;
+* use arc_swap::ArcSwap;
+*
+* let state = ArcSwap::from_pointee((0, vec![1, 2]));
+* update_swap(&state, optics!(_1._mapped), |x| *x *= 10);
+* assert_eq!(state.load().1, vec![10, 20]);
+* ```
+*/
+#[cfg(feature = "arc-swap")]
+pub fn update_swap<Tr, S, F>(swap: &arc_swap::ArcSwap<S>, optic: Tr, mut f: F)
+where
+    Tr: TraversalMut<S>,
+    S: Clone,
+    F: FnMut(&mut Tr::To),
+{
+    swap.rcu(|current| {
+        let mut next = S::clone(current);
+        optic.traverse_mut(&mut next).into_iter().for_each(&mut f);
+        next
+    });
+}
@@ -0,0 +1,69 @@
+/*!
+* Optics over `toml::Value`, the same `key`/`index` and type prisms as the other formats.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::value::key;
+*
+* let mut config: toml::Value = toml::from_str("[server]\nport = 80").unwrap();
+* let port = key("server", key("port", optics!(_integer)));
+* *port.pm_mut(&mut config).unwrap() = 8080;
+* assert_eq!(config["server"]["port"].as_integer(), Option::Some(8080));
+* ```
+*/
+use crate::value::{impl_value_child, impl_value_variant};
+use crate::*;
+use ::toml::value::{Array, Datetime, Table, Value};
+
+fn key_ref<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Table(table) => table.get(key),
+        _ => Option::None,
+    }
+}
+
+fn key_mut<'a>(value: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Table(table) => table.get_mut(key),
+        _ => Option::None,
+    }
+}
+
+fn key_take(value: Value, key: &str) -> Option<Value> {
+    match value {
+        Value::Table(mut table) => table.remove(key),
+        _ => Option::None,
+    }
+}
+
+fn index_ref<'a>(value: &'a Value, index: &usize) -> Option<&'a Value> {
+    match value {
+        Value::Array(vec) => vec.get(*index),
+        _ => Option::None,
+    }
+}
+
+fn index_mut<'a>(value: &'a mut Value, index: &usize) -> Option<&'a mut Value> {
+    match value {
+        Value::Array(vec) => vec.get_mut(*index),
+        _ => Option::None,
+    }
+}
+
+fn index_take(value: Value, index: &usize) -> Option<Value> {
+    match value {
+        Value::Array(vec) => vec.into_iter().nth(*index),
+        _ => Option::None,
+    }
+}
+
+impl_value_child!(Value, _key, key_ref, key_mut, key_take);
+impl_value_child!(Value, _index, index_ref, index_mut, index_take);
+
+impl_value_variant!(Value, _bool, Value::Boolean, bool);
+impl_value_variant!(Value, _integer, Value::Integer, i64);
+impl_value_variant!(Value, _float, Value::Float, f64);
+impl_value_variant!(Value, _string, Value::String, String);
+impl_value_variant!(Value, _array, Value::Array, Array);
+impl_value_variant!(Value, _object, Value::Table, Table);
+impl_value_variant!(Value, _datetime, Value::Datetime, Datetime);
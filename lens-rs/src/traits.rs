@@ -8,6 +8,10 @@
 * assert_eq!(nested, Ok(Err(Some((1,2,3)))));
 * ```
 */
+#[diagnostic::on_unimplemented(
+    message = "`{T}` can't be constructed by the optic `{Self}`",
+    note = "only the variants marked with `#[optic]` and deriving `Review` can be constructed, `#[non_exhaustive]` variants are preview-only"
+)]
 pub trait Review<T> {
     type From;
     fn review(&self, from: Self::From) -> T;
@@ -26,19 +30,66 @@ optics!(_1.Mapped._Some._0)
 assert_eq!(optics!(_1.Mapped._Some._0).traverse(x), vec![3]);
 ```
 */
+#[diagnostic::on_unimplemented(
+    message = "`{T}` has no optic `{Self}`",
+    label = "check each segment of the `optics!` path against `{T}`",
+    note = "only the fields and variants marked with `#[optic]` have optics"
+)]
 pub trait TraversalRef<T> {
     type To;
     fn traverse_ref<'a>(&self, source: &'a T) -> Vec<&'a Self::To>;
+
+    /// visit the foci in order until `f` returns `false`,
+    /// returns `false` if the visiting was stopped early.
+    fn visit_ref<'a>(&self, source: &'a T, f: &mut dyn FnMut(&'a Self::To) -> bool) -> bool {
+        self.traverse_ref(source).into_iter().all(f)
+    }
 }
 
+#[diagnostic::on_unimplemented(
+    message = "the optic `{Self}` can't access `{T}` mutably",
+    note = "a field or variant marked `#[optic(ref)]` is read-only, use `#[optic(mut)]` or `#[optic]`"
+)]
 pub trait TraversalMut<T>: TraversalRef<T> {
     fn traverse_mut<'a>(&self, source: &'a mut T) -> Vec<&'a mut Self::To>;
 }
 
+#[diagnostic::on_unimplemented(
+    message = "the optic `{Self}` can't move out of `{T}`",
+    note = "only a field or variant marked `#[optic]` can be moved out"
+)]
 pub trait Traversal<T>: TraversalMut<T> {
     fn traverse(&self, source: T) -> Vec<Self::To>;
 }
 
+/**
+Consume a structure, moving the foci of an optic out of it, as `into_iter` to `iter`.
+## Example
+```
+use lens_rs::*;
+let x = (vec![String::from("a"), String::from("b")], 0);
+let names: Vec<String> = x.into_foci_iter(optics!(_0._mapped)).rev().collect();
+assert_eq!(names, vec!["b", "a"]);
+```
+*/
+pub trait IntoTraversal: Sized {
+    fn into_foci<Tr>(self, optic: Tr) -> Vec<Tr::To>
+    where
+        Tr: Traversal<Self>,
+    {
+        optic.traverse(self)
+    }
+
+    fn into_foci_iter<Tr>(self, optic: Tr) -> std::vec::IntoIter<Tr::To>
+    where
+        Tr: Traversal<Self>,
+    {
+        self.into_foci(optic).into_iter()
+    }
+}
+
+impl<T> IntoTraversal for T {}
+
 /**
 A trait representing the optics behaves as the first-class pattern.
 A `Prism` can access the substructure may exist.
@@ -48,6 +99,10 @@ let mut x: (_, Result<_, ()>) = (1, Ok((2, 3)));
 assert_eq!(optics!(_1._Ok._1).pm(x)?, 6);
 ```
 */
+#[diagnostic::on_unimplemented(
+    message = "the optic `{Self}` is not a prism of `{T}`",
+    note = "the `optics!` path may reach many substructures, e.g. through `_mapped`, use `traverse_ref` instead"
+)]
 pub trait PrismRef<T>: TraversalRef<T> {
     fn pm_ref<'a>(&self, source: &'a T) -> Option<&'a Self::To>;
 }
@@ -70,6 +125,10 @@ let mut x = (1, (2, (3, 4)));
 assert_eq!(optics!(_1._1._1).view(x), 8);
 ```
 */
+#[diagnostic::on_unimplemented(
+    message = "the optic `{Self}` is not a lens of `{T}`",
+    note = "the `optics!` path may miss the substructure, e.g. through `Some` or an enum variant, use `pm_ref` instead"
+)]
 pub trait LensRef<T>: PrismRef<T> {
     fn view_ref<'a>(&self, source: &'a T) -> &'a Self::To;
 }
@@ -81,3 +140,173 @@ pub trait LensMut<T>: LensRef<T> + PrismMut<T> {
 pub trait Lens<T>: LensMut<T> + Prism<T> {
     fn view(&self, source: T) -> Self::To;
 }
+
+pub type BoxFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + 'a>>;
+
+/**
+A trait representing the lens whose focus may only be reached asynchronously.
+Every synchronous segment (fields, tuples, pointers) also implements it,
+so a path only needs one `_fetch` where the data is loaded lazily.
+## Example
+```
+use lens_rs::*;
+use std::future::Future;
+use std::task::{Context, Poll, Waker};
+
+// a user loaded once, the future is ready right away
+struct Remote((String, u32));
+
+impl Fetch for Remote {
+    type Output = (String, u32);
+    fn fetch<'a>(&'a self) -> BoxFuture<'a, &'a Self::Output> {
+        Box::pin(std::future::ready(&self.0))
+    }
+    fn fetch_mut<'a>(&'a mut self) -> BoxFuture<'a, &'a mut Self::Output> {
+        Box::pin(std::future::ready(&mut self.0))
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+let mut session = (7, Remote(("ann".to_string(), 30)));
+let name = block_on(optics!(_1._fetch._0).view_async_ref(&session));
+assert_eq!(name, "ann");
+*block_on(optics!(_1._fetch._1).view_async_mut(&mut session)) += 1;
+assert_eq!((session.1).0 .1, 31);
+```
+*/
+pub trait LensAsyncRef<T> {
+    type To;
+    fn view_async_ref<'a>(&'a self, source: &'a T) -> BoxFuture<'a, &'a Self::To>;
+}
+
+pub trait LensAsyncMut<T>: LensAsyncRef<T> {
+    fn view_async_mut<'a>(&'a self, source: &'a mut T) -> BoxFuture<'a, &'a mut Self::To>;
+}
+
+/**
+A trait representing the optic values which can be extended with another optic at the end of their path,
+for composing where the path can't be written in `optics!`, e.g. in generic code.
+`a >> b` is `a.compose(b)` for the concrete optics.
+## Example
+```
+use lens_rs::*;
+fn then_first<A: Compose<_0<__>>>(optic: A) -> A::Output {
+    optic.compose(optics!(_0))
+}
+
+let x = (1, (2, 'a'), Option::Some((3, 'b')));
+assert_eq!(then_first(optics!(_1)).view_ref(&x), &2);
+assert_eq!((optics!(_2.Some) >> then_first(__)).preview(&x), Option::Some(3));
+assert_eq!(optics!(_2.Some) >> optics!(_1), optics!(_2.Some._1));
+```
+*/
+pub trait Compose<Next> {
+    type Output;
+    fn compose(self, next: Next) -> Self::Output;
+}
+
+/**
+A trait representing the optic values known from their type alone, i.e. the paths carrying no key or index,
+so a path named with `field!` has its `const`.
+## Example
+```
+use lens_rs::*;
+type Port = field![_1._0];
+const PORT: Port = Port::OPTIC;
+assert_eq!(PORT, optics!(_1._0));
+```
+*/
+pub trait ConstOptic {
+    const OPTIC: Self;
+}
+
+/**
+A value which must be loaded before it can be accessed, e.g. a remote or database-backed field.
+It is the focus of the `_fetch` optic.
+*/
+pub trait Fetch {
+    type Output;
+    fn fetch<'a>(&'a self) -> BoxFuture<'a, &'a Self::Output>;
+    fn fetch_mut<'a>(&'a mut self) -> BoxFuture<'a, &'a mut Self::Output>;
+}
+
+/**
+A trait representing the optics whose focus is computed from the source instead of borrowed from it,
+e.g. decoding or unit conversion.
+A `Preview` can produce the focus may exist.
+## Example
+```
+use lens_rs::*;
+let x = (1, vec![0x68, 0x69]);
+assert_eq!(optics!(_1._utf8).preview(&x), Option::Some("hi".to_string()));
+```
+*/
+pub trait Preview<T> {
+    type To;
+    fn preview(&self, source: &T) -> Option<Self::To>;
+}
+
+/// A `Getter` can produce the focus must exist.
+pub trait Getter<T>: Preview<T> {
+    fn get(&self, source: &T) -> Self::To;
+}
+
+/**
+A trait representing the optics can write the computed focus back into the source.
+## Example
+```
+use lens_rs::*;
+let mut x = (1, vec![]);
+optics!(_1._utf8).set(&mut x, "hi".to_string());
+assert_eq!(x.1, vec![0x68, 0x69]);
+```
+
+A `#[optic(setter_only)]` field derives only `Setter` and `Over`, it can be written but never viewed.
+```
+use lens_rs::*;
+
+#[derive(Lens)]
+struct User(#[optic(ref)] String, #[optic(setter_only)] u64);
+
+let mut user = User("ann".to_string(), 0);
+optics!(_1).set(&mut user, 41);
+optics!(_1).over(&mut user, &mut |hash| *hash += 1);
+assert_eq!(user.1, 42);
+```
+```compile_fail
+use lens_rs::*;
+
+#[derive(Lens)]
+struct User(#[optic(ref)] String, #[optic(setter_only)] u64);
+
+let user = User("ann".to_string(), 42);
+optics!(_1).view_ref(&user);
+```
+*/
+pub trait Setter<T> {
+    type To;
+    fn set(&self, source: &mut T, value: Self::To);
+}
+
+/**
+A `Setter` can also modify the focus in place, without a way to view it out of the source.
+## Example
+```
+use lens_rs::*;
+let mut x = (1, (2, 3));
+optics!(_1._0).over(&mut x, &mut |n| *n *= 10);
+assert_eq!(x, (1, (20, 3)));
+```
+*/
+pub trait Over<T>: Setter<T> {
+    fn over(&self, source: &mut T, f: &mut dyn FnMut(&mut Self::To));
+}
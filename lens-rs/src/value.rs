@@ -0,0 +1,275 @@
+/*!
+* The optics shared by the self-describing values of `serde_json`, `toml` and `serde_yaml`:
+* `key`/`index` to walk into them and the type prisms, e.g. `_string` or `_object`.
+*/
+use crate::*;
+
+/// focus on the value under a key of an object, table or mapping.
+pub fn key<Optic>(key: &str, optic: Optic) -> optics::_key<Optic> {
+    optics::_key(key.to_string(), optic)
+}
+
+/// focus on the element at an index of an array.
+pub const fn index<Optic>(index: usize, optic: Optic) -> optics::_index<Optic> {
+    optics::_index(index, optic)
+}
+
+// the full prism family of a variant, named by `$marker` instead of the variant.
+#[allow(unused_macros)]
+macro_rules! impl_value_variant {
+    ($data:ty, $marker:ident, $var:path, $to:ty) => {
+        impl<Rv> Review<$data> for optics::$marker<Rv>
+        where
+            Rv: Review<$to>,
+        {
+            type From = Rv::From;
+
+            #[inline]
+            fn review(&self, from: Self::From) -> $data {
+                $var(self.0.review(from))
+            }
+        }
+
+        impl<Tr> TraversalRef<$data> for optics::$marker<Tr>
+        where
+            Tr: TraversalRef<$to>,
+        {
+            type To = Tr::To;
+
+            #[inline]
+            fn traverse_ref<'a>(&self, source: &'a $data) -> Vec<&'a Self::To> {
+                match source {
+                    $var(x) => self.0.traverse_ref(x),
+                    _ => vec![],
+                }
+            }
+
+            #[inline]
+            fn visit_ref<'a>(
+                &self,
+                source: &'a $data,
+                f: &mut dyn FnMut(&'a Self::To) -> bool,
+            ) -> bool {
+                match source {
+                    $var(x) => self.0.visit_ref(x, f),
+                    _ => true,
+                }
+            }
+        }
+
+        impl<Tr> TraversalMut<$data> for optics::$marker<Tr>
+        where
+            Tr: TraversalMut<$to>,
+        {
+            #[inline]
+            fn traverse_mut<'a>(&self, source: &'a mut $data) -> Vec<&'a mut Self::To> {
+                match source {
+                    $var(x) => self.0.traverse_mut(x),
+                    _ => vec![],
+                }
+            }
+        }
+
+        impl<Tr> Traversal<$data> for optics::$marker<Tr>
+        where
+            Tr: Traversal<$to>,
+        {
+            #[inline]
+            fn traverse(&self, source: $data) -> Vec<Self::To> {
+                match source {
+                    $var(x) => self.0.traverse(x),
+                    _ => vec![],
+                }
+            }
+        }
+
+        impl<Pm> PrismRef<$data> for optics::$marker<Pm>
+        where
+            Pm: PrismRef<$to>,
+        {
+            #[inline]
+            fn pm_ref<'a>(&self, source: &'a $data) -> Option<&'a Self::To> {
+                match source {
+                    $var(x) => self.0.pm_ref(x),
+                    _ => Option::None,
+                }
+            }
+        }
+
+        impl<Pm> PrismMut<$data> for optics::$marker<Pm>
+        where
+            Pm: PrismMut<$to>,
+        {
+            #[inline]
+            fn pm_mut<'a>(&self, source: &'a mut $data) -> Option<&'a mut Self::To> {
+                match source {
+                    $var(x) => self.0.pm_mut(x),
+                    _ => Option::None,
+                }
+            }
+        }
+
+        impl<Pm> Prism<$data> for optics::$marker<Pm>
+        where
+            Pm: Prism<$to>,
+        {
+            #[inline]
+            fn pm(&self, source: $data) -> Option<Self::To> {
+                match source {
+                    $var(x) => self.0.pm(x),
+                    _ => Option::None,
+                }
+            }
+        }
+
+        impl<Pv> Preview<$data> for optics::$marker<Pv>
+        where
+            Pv: Preview<$to>,
+        {
+            type To = Pv::To;
+
+            #[inline]
+            fn preview(&self, source: &$data) -> Option<Self::To> {
+                match source {
+                    $var(x) => self.0.preview(x),
+                    _ => Option::None,
+                }
+            }
+        }
+
+        impl<St> Setter<$data> for optics::$marker<St>
+        where
+            St: Setter<$to>,
+        {
+            type To = St::To;
+
+            #[inline]
+            fn set(&self, source: &mut $data, value: Self::To) {
+                if let $var(x) = source {
+                    self.0.set(x, value)
+                }
+            }
+        }
+    };
+}
+
+/*
+ * `_key` and `_index` over a value, `$get`, `$get_mut` and `$take` look the child up,
+ * the last one consumes the value.
+ */
+#[allow(unused_macros)]
+macro_rules! impl_value_child {
+    ($data:ty, $optic:ident, $get:path, $get_mut:path, $take:path) => {
+        impl<Tr> TraversalRef<$data> for optics::$optic<Tr>
+        where
+            Tr: TraversalRef<$data>,
+        {
+            type To = Tr::To;
+
+            #[inline]
+            fn traverse_ref<'a>(&self, source: &'a $data) -> Vec<&'a Self::To> {
+                match $get(source, &self.0) {
+                    Option::Some(x) => self.1.traverse_ref(x),
+                    Option::None => vec![],
+                }
+            }
+
+            #[inline]
+            fn visit_ref<'a>(
+                &self,
+                source: &'a $data,
+                f: &mut dyn FnMut(&'a Self::To) -> bool,
+            ) -> bool {
+                match $get(source, &self.0) {
+                    Option::Some(x) => self.1.visit_ref(x, f),
+                    Option::None => true,
+                }
+            }
+        }
+
+        impl<Tr> TraversalMut<$data> for optics::$optic<Tr>
+        where
+            Tr: TraversalMut<$data>,
+        {
+            #[inline]
+            fn traverse_mut<'a>(&self, source: &'a mut $data) -> Vec<&'a mut Self::To> {
+                match $get_mut(source, &self.0) {
+                    Option::Some(x) => self.1.traverse_mut(x),
+                    Option::None => vec![],
+                }
+            }
+        }
+
+        impl<Tr> Traversal<$data> for optics::$optic<Tr>
+        where
+            Tr: Traversal<$data>,
+        {
+            #[inline]
+            fn traverse(&self, source: $data) -> Vec<Self::To> {
+                match $take(source, &self.0) {
+                    Option::Some(x) => self.1.traverse(x),
+                    Option::None => vec![],
+                }
+            }
+        }
+
+        impl<Pm> PrismRef<$data> for optics::$optic<Pm>
+        where
+            Pm: PrismRef<$data>,
+        {
+            #[inline]
+            fn pm_ref<'a>(&self, source: &'a $data) -> Option<&'a Self::To> {
+                $get(source, &self.0).and_then(|x| self.1.pm_ref(x))
+            }
+        }
+
+        impl<Pm> PrismMut<$data> for optics::$optic<Pm>
+        where
+            Pm: PrismMut<$data>,
+        {
+            #[inline]
+            fn pm_mut<'a>(&self, source: &'a mut $data) -> Option<&'a mut Self::To> {
+                $get_mut(source, &self.0).and_then(|x| self.1.pm_mut(x))
+            }
+        }
+
+        impl<Pm> Prism<$data> for optics::$optic<Pm>
+        where
+            Pm: Prism<$data>,
+        {
+            #[inline]
+            fn pm(&self, source: $data) -> Option<Self::To> {
+                $take(source, &self.0).and_then(|x| self.1.pm(x))
+            }
+        }
+
+        impl<Pv> Preview<$data> for optics::$optic<Pv>
+        where
+            Pv: Preview<$data>,
+        {
+            type To = Pv::To;
+
+            #[inline]
+            fn preview(&self, source: &$data) -> Option<Self::To> {
+                $get(source, &self.0).and_then(|x| self.1.preview(x))
+            }
+        }
+
+        impl<St> Setter<$data> for optics::$optic<St>
+        where
+            St: Setter<$data>,
+        {
+            type To = St::To;
+
+            #[inline]
+            fn set(&self, source: &mut $data, value: Self::To) {
+                if let Option::Some(x) = $get_mut(source, &self.0) {
+                    self.1.set(x, value)
+                }
+            }
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {impl_value_child, impl_value_variant};
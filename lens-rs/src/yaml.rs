@@ -0,0 +1,67 @@
+/*!
+* Optics over `serde_yaml::Value`, the same `key`/`index` and type prisms as the other formats.
+* ## Example
+* ```
+* use lens_rs::*;
+* use lens_rs::value::{index, key};
+*
+* let mut config: serde_yaml::Value = serde_yaml::from_str("hosts: [a, b]").unwrap();
+* let second = key("hosts", index(1, optics!(_string)));
+* second.pm_mut(&mut config).unwrap().push('c');
+* assert_eq!(config["hosts"][1].as_str(), Option::Some("bc"));
+* ```
+*/
+use crate::value::{impl_value_child, impl_value_variant};
+use crate::*;
+use serde_yaml::{Mapping, Number, Sequence, Value};
+
+fn key_ref<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Mapping(mapping) => mapping.get(key),
+        _ => Option::None,
+    }
+}
+
+fn key_mut<'a>(value: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Mapping(mapping) => mapping.get_mut(key),
+        _ => Option::None,
+    }
+}
+
+fn key_take(value: Value, key: &str) -> Option<Value> {
+    match value {
+        Value::Mapping(mut mapping) => mapping.remove(key),
+        _ => Option::None,
+    }
+}
+
+fn index_ref<'a>(value: &'a Value, index: &usize) -> Option<&'a Value> {
+    match value {
+        Value::Sequence(vec) => vec.get(*index),
+        _ => Option::None,
+    }
+}
+
+fn index_mut<'a>(value: &'a mut Value, index: &usize) -> Option<&'a mut Value> {
+    match value {
+        Value::Sequence(vec) => vec.get_mut(*index),
+        _ => Option::None,
+    }
+}
+
+fn index_take(value: Value, index: &usize) -> Option<Value> {
+    match value {
+        Value::Sequence(vec) => vec.into_iter().nth(*index),
+        _ => Option::None,
+    }
+}
+
+impl_value_child!(Value, _key, key_ref, key_mut, key_take);
+impl_value_child!(Value, _index, index_ref, index_mut, index_take);
+
+impl_value_variant!(Value, _bool, Value::Bool, bool);
+impl_value_variant!(Value, _number, Value::Number, Number);
+impl_value_variant!(Value, _string, Value::String, String);
+impl_value_variant!(Value, _array, Value::Sequence, Sequence);
+impl_value_variant!(Value, _object, Value::Mapping, Mapping);
@@ -7,90 +7,200 @@ use syn::{
     DeriveInput, ItemEnum, ItemStruct, Token,
 };
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use syn::parse::{Parse, ParseStream, Result};
 
+// the tuple optics `_0`, `_1`, ... provided by lens-rs.
+const MAX_TUPLE_FIELDS: usize = if cfg!(feature = "tuple32") {
+    32
+} else if cfg!(feature = "tuple16") {
+    16
+} else if cfg!(feature = "tuple7") {
+    7
+} else if cfg!(feature = "tuple4") {
+    4
+} else {
+    2
+};
+
 enum OpticMutability {
     Move,
     Ref(Token![ref]),
     Mut(Token![mut]),
+    SetterOnly,
+}
+
+// the arguments of `#[optic(..)]` on a field or variant, e.g. `#[optic(mut, vis = "pub(crate)")]`.
+struct OpticAttr {
+    mutability: OpticMutability,
+    vis: Option<syn::Visibility>,
+    flatten: bool,
 }
 
-impl Parse for OpticMutability {
+impl Parse for OpticAttr {
     fn parse(input: ParseStream) -> Result<Self> {
-        if input.is_empty() {
-            return Ok(Self::Move);
+        let mut attr = OpticAttr {
+            mutability: OpticMutability::Move,
+            vis: None,
+            flatten: false,
+        };
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(Token![mut]) {
+                attr.mutability = OpticMutability::Mut(input.parse()?);
+            } else if lookahead.peek(Token![ref]) {
+                attr.mutability = OpticMutability::Ref(input.parse()?);
+            } else {
+                match input.parse::<syn::Ident>() {
+                    Ok(ident) if ident == "mv" => attr.mutability = OpticMutability::Move,
+                    Ok(ident) if ident == "setter_only" => attr.mutability = OpticMutability::SetterOnly,
+                    Ok(ident) if ident == "flatten" => attr.flatten = true,
+                    Ok(ident) if ident == "vis" => {
+                        input.parse::<Token![=]>()?;
+                        attr.vis = Some(input.parse::<syn::LitStr>()?.parse()?);
+                    }
+                    _ => return Err(input.error("only allow #[optic], #[optic(mv)], #[optic(mut)], #[optic(ref)] or #[optic(setter_only)] here, optionally with `vis = \"..\"` or `flatten`")),
+                }
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
         }
+        Ok(attr)
+    }
+}
 
-        let content;
+// `#[optic]` has no arguments, `#[optic(..)]` has a list of them.
+fn parse_optic_attr(attr: &syn::Attribute) -> Result<OpticAttr> {
+    match &attr.meta {
+        syn::Meta::Path(_) => Ok(OpticAttr {
+            mutability: OpticMutability::Move,
+            vis: None,
+            flatten: false,
+        }),
+        _ => attr.parse_args(),
+    }
+}
 
-        parenthesized!(content in input);
-        let lookahead = content.lookahead1();
-        if lookahead.peek(Token![mut]) {
-            Ok(Self::Mut(content.parse()?))
-        } else if lookahead.peek(Token![ref]) {
-            Ok(Self::Ref(content.parse()?))
-        } else {
-            Err(input.error("only allow #[optic], #[optic(mut)] or #[optic(ref)] here"))
+// the visibility given by `#[optic(vis = "..")]`, `pub` if there's none.
+fn optic_vis(attrs: &[syn::Attribute]) -> syn::Visibility {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+        .and_then(|attr| parse_optic_attr(attr).ok())
+        .and_then(|attr| attr.vis)
+        .unwrap_or_else(|| parse_quote!(pub))
+}
+
+// a trait impl can't be scoped, the marker it's on can: the optic of a field or variant with a restricted `vis`
+// is a marker of the module `{data}_optics` next to the type instead of `lens_rs::optics`.
+fn optic_marker(derive_input: &DeriveInput, optic_name: &syn::Ident, attrs: &[syn::Attribute]) -> proc_macro2::TokenStream {
+    match optic_vis(attrs) {
+        syn::Visibility::Public(_) => quote!(lens_rs::optics::#optic_name),
+        _ => {
+            let module = restricted_module(&derive_input.ident);
+            quote!(#module::#optic_name)
+        }
+    }
+}
+
+fn restricted_module(data_name: &syn::Ident) -> syn::Ident {
+    format_ident!("{}_optics", projection_optic_name(data_name))
+}
+
+// `vis` is relative to the module of the type, the markers are one module deeper.
+fn vis_in_restricted_module(vis: &syn::Visibility) -> syn::Visibility {
+    match vis {
+        syn::Visibility::Inherited => parse_quote!(pub(super)),
+        syn::Visibility::Restricted(restricted) if restricted.path.leading_colon.is_none() => {
+            let path = &restricted.path;
+            let first = &path.segments[0].ident;
+            if first == "self" {
+                let rest = path.segments.iter().skip(1);
+                parse_quote!(pub(in super #(::#rest)*))
+            } else if first == "super" {
+                parse_quote!(pub(in super::#path))
+            } else {
+                vis.clone()
+            }
+        }
+        _ => vis.clone(),
+    }
+}
+
+// the module of the markers of the restricted fields or variants, empty if there are none.
+fn restricted_markers<'a>(
+    derive_input: &DeriveInput,
+    optics: impl Iterator<Item = (syn::Ident, &'a [syn::Attribute])>,
+) -> proc_macro2::TokenStream {
+    let markers = optics
+        .filter_map(|(optic_name, attrs)| match optic_vis(attrs) {
+            syn::Visibility::Public(_) => None,
+            vis => {
+                let vis = vis_in_restricted_module(&vis);
+                Some(quote! {
+                    #[allow(non_camel_case_types)]
+                    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+                    #vis struct #optic_name<Optic>(pub Optic);
+                })
+            }
+        })
+        .collect::<Vec<_>>();
+    if markers.is_empty() {
+        return quote! {};
+    }
+
+    let data_vis = &derive_input.vis;
+    let module = restricted_module(&derive_input.ident);
+    quote! {
+        #data_vis mod #module {
+            #(#markers)*
+        }
+    }
+}
+
+// the umbrella derive, a struct gets the lenses and an enum gets the prisms and the reviews,
+// `#[optic(ref)]`, `#[optic(mut)]` and `#[optic(mv)]` restrict what each field or variant implements.
+#[proc_macro_derive(Optic, attributes(optic))]
+pub fn derive_optic(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    let input = TokenStream::from(derive_input.to_token_stream());
+    match derive_input.data {
+        Data::Struct(_) => derive_lens(input),
+        Data::Enum(_) => {
+            let reviews = proc_macro2::TokenStream::from(derive_review(input.clone()));
+            let prisms = proc_macro2::TokenStream::from(derive_prism(input));
+            TokenStream::from(quote!(#reviews #prisms))
         }
+        Data::Union(_) => panic!("union can't derive the optics"),
     }
 }
 
-// #[proc_macro_derive(Optic, attributes(optic))]
-// pub fn derive_optic(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-//     let derive_input = parse_macro_input!(input as DeriveInput);
-//     let optics = match derive_input.data {
-//         Data::Enum(e) => e
-//             .variants
-//             .iter()
-//             .filter(|var| {
-//                 var
-//                     .attrs
-//                     .iter()
-//                     .any(|attr| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
-//             })
-//             .flat_map(|x| {
-//                 let optic_name = format_ident!("_{}", x.ident);
-//                 let mut table = OPTIC_NAMES.lock().unwrap();
-//                 if table.contains(&optic_name.clone().to_string()) { return quote! {}; }
-//                 table.insert(optic_name.clone().to_string());
-//                 quote! {
-//                     #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-//                     pub struct #optic_name<Optic>(pub Optic);
-//                 }
-//             })
-//             .collect(),
-//         Data::Struct(st) => st
-//             .fields
-//             .iter()
-//             .filter(|var| {
-//                 var
-//                     .attrs
-//                     .iter()
-//                     .any(|attr| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
-//             })
-//             .flat_map(|x| {
-//                 let optic_name = format_ident!("_{}", x.ident.as_ref()?);
-//                 let mut table = OPTIC_NAMES.lock().unwrap();
-//                 if table.contains(&optic_name.clone().to_string()) { return None; }
-//                 table.insert(optic_name.clone().to_string());
-//                 Some(quote! {
-//                     #[derive(Copy, Clone)]
-//                     #[allow(non_camel_case_types)]
-//                     pub struct #optic_name<Optic>(pub Optic);
-//                 })
-//             })
-//             .collect(),
-//         _ => quote! {},
-//     };
-//     TokenStream::from(optics)
-// }
+fn is_non_exhaustive(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path().is_ident(&syn::Ident::new("non_exhaustive", Span::call_site())))
+}
 
+// a `#[non_exhaustive]` variant can't be constructed outside its crate, so it gets no `Review`,
+// its prism still works for matching. a `#[non_exhaustive]` enum only can't be matched exhaustively,
+// its variants are reviewed as usual.
 #[proc_macro_derive(Review, attributes(optic))]
 pub fn derive_review(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
 
+    let message = match &derive_input.data {
+        Data::Struct(_) if is_non_exhaustive(&derive_input.attrs) => Some(
+            "a `#[non_exhaustive]` struct can't be constructed outside its crate, and only an enum can derive `Review`",
+        ),
+        Data::Struct(_) => Some("only an enum can derive `Review`, derive `Lens` for the fields of a struct"),
+        _ => None,
+    };
+    if let Some(message) = message {
+        return syn::Error::new_spanned(&derive_input.ident, message).to_compile_error().into();
+    }
+
     let reviews: proc_macro2::TokenStream = match derive_input.data.clone() {
         Data::Enum(e) => e
             .variants
@@ -99,8 +209,9 @@ pub fn derive_review(input: proc_macro::TokenStream) -> proc_macro::TokenStream
                 var
                     .attrs
                     .iter()
-                    .any(|attr| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
+                    .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
             })
+            .filter(|var| !is_non_exhaustive(&var.attrs))
             .flat_map(|var| {
                 let data = derive_input.clone();
                 let data_name = data.ident;
@@ -114,6 +225,7 @@ pub fn derive_review(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
                 let var_name = &var.ident;
                 let optic_name = format_ident!("{}", var.ident);
+                let marker = optic_marker(&derive_input, &optic_name, &var.attrs);
                 let ty = var
                     .fields
                     .iter()
@@ -132,13 +244,14 @@ pub fn derive_review(input: proc_macro::TokenStream) -> proc_macro::TokenStream
                 //     .collect::<Vec<_>>();
 
                 quote! {
-                    impl<#(#data_gen_param,)* Rv> lens_rs::Review<#data_name #data_gen> for lens_rs::optics::#optic_name<Rv>
+                    impl<#(#data_gen_param,)* Rv> lens_rs::Review<#data_name #data_gen> for #marker<Rv>
                     where
                         Rv: lens_rs::Review<#ty>,
                         #data_gen_where
                     {
                         type From = Rv::From;
 
+                        #[inline]
                         fn review(&self, from: Self::From) -> #data_name #data_gen {
                             // let tuple = self.0.review(from);
                             // <#data_name #data_gen>::#var_name(#(tuple . #fields,)*)
@@ -148,9 +261,85 @@ pub fn derive_review(input: proc_macro::TokenStream) -> proc_macro::TokenStream
                 }
             })
             .collect(),
-        _ => panic!("union and struct can't derive the review"),
+        _ => panic!("union can't derive the review"),
+    };
+    let constructors = review_constructors(&derive_input);
+    TokenStream::from(quote!(#reviews #constructors))
+}
+
+// `Data::construct_some_variant(value)` for every reviewable variant,
+// and reviews through a recursive `Box<Data>` to build nested values.
+fn review_constructors(derive_input: &DeriveInput) -> proc_macro2::TokenStream {
+    let data_name = &derive_input.ident;
+    let data_gen = &derive_input.generics;
+    let (impl_gen, ty_gen, where_clause) = data_gen.split_for_impl();
+    let variants = match &derive_input.data {
+        Data::Enum(e) => e
+            .variants
+            .iter()
+            .filter(|var| {
+                var.attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+            })
+            .filter(|var| !is_non_exhaustive(&var.attrs))
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+
+    let constructors = variants.iter().map(|var| {
+        let var_name = &var.ident;
+        let fn_name = format_ident!("construct_{}", projection_optic_name(var_name));
+        let vis = optic_vis(&var.attrs);
+        let ty = var.fields.iter().take(1).map(|field| field.ty.clone()).collect::<Punctuated<_, Token![,]>>();
+        quote! {
+            #[inline]
+            #vis fn #fn_name(value: #ty) -> Self {
+                #data_name::#var_name(value)
+            }
+        }
+    });
+
+    let boxed = if is_recursive(derive_input) {
+        let data_gen_param = data_gen.params.iter().collect::<Vec<_>>();
+        let data_gen_where = data_gen
+            .where_clause
+            .iter()
+            .flat_map(|x| x.predicates.clone())
+            .collect::<Punctuated<_, Token![,]>>();
+        let data = quote!(#data_name #data_gen);
+        variants
+            .iter()
+            .map(|var| {
+                let optic = optic_marker(derive_input, &var.ident, &var.attrs);
+                quote! {
+                    impl<#(#data_gen_param,)* Rv> lens_rs::Review<Box<#data>> for #optic<Rv>
+                    where
+                        #optic<Rv>: lens_rs::Review<#data>,
+                        #data_gen_where
+                    {
+                        type From = <#optic<Rv> as lens_rs::Review<#data>>::From;
+
+                        #[inline]
+                        fn review(&self, from: Self::From) -> Box<#data> {
+                            Box::new(lens_rs::Review::<#data>::review(self, from))
+                        }
+                    }
+                }
+            })
+            .collect()
+    } else {
+        quote! {}
     };
-    TokenStream::from(reviews)
+
+    quote! {
+        #[allow(dead_code)]
+        impl #impl_gen #data_name #ty_gen #where_clause {
+            #(#constructors)*
+        }
+
+        #boxed
+    }
 }
 
 #[proc_macro_derive(Prism, attributes(optic))]
@@ -165,7 +354,7 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 var
                     .attrs
                     .iter()
-                    .any(|attr| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
+                    .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
             })
             .flat_map(|var| {
                 let data = derive_input.clone();
@@ -180,6 +369,7 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
                 let var_name = &var.ident;
                 let optic_name = format_ident!("{}", var.ident);
+                let marker = optic_marker(&derive_input, &optic_name, &var.attrs);
                 let ty = var
                     .fields
                     .iter()
@@ -190,20 +380,21 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     .attrs
                     .clone()
                     .into_iter()
-                    .find(|attr: &syn::Attribute| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
+                    .find(|attr: &syn::Attribute| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
                     .unwrap();
-                let mutability = syn::parse::<OpticMutability>(TokenStream::from(attr.tokens)).unwrap();
+                let mutability = parse_optic_attr(&attr).unwrap().mutability;
 
 
 
                 let impl_ref = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalRef<#data_name #data_gen> for lens_rs::optics::#optic_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalRef<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::TraversalRef<#ty>,
                         #data_gen_where
                     {
                         type To = Tr::To;
 
+                        #[inline]
                         fn traverse_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen) -> Vec<&'__a98shdai Self::To> {
                             use #data_name::*;
                             match source {
@@ -211,13 +402,23 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                                 _ => vec![],
                             }
                         }
+
+                        #[inline]
+                        fn visit_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen, f: &mut dyn FnMut(&'__a98shdai Self::To) -> bool) -> bool {
+                            use #data_name::*;
+                            match source {
+                                #var_name(x) => self.0.visit_ref(x, f),
+                                _ => true,
+                            }
+                        }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismRef<#data_name #data_gen> for lens_rs::optics::#optic_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismRef<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::PrismRef<#ty>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen) -> Option<&'__a98shdai Self::To> {
                             use #data_name::*;
                             match source {
@@ -226,14 +427,32 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                             }
                         }
                     }
+
+                    impl<#(#data_gen_param,)* Pv> lens_rs::Preview<#data_name #data_gen> for #marker<Pv>
+                    where
+                        Pv: lens_rs::Preview<#ty>,
+                        #data_gen_where
+                    {
+                        type To = Pv::To;
+
+                        #[inline]
+                        fn preview(&self, source: &#data_name #data_gen) -> Option<Self::To> {
+                            use #data_name::*;
+                            match source {
+                                #var_name(x) => self.0.preview(x),
+                                _ => Option::None,
+                            }
+                        }
+                    }
                 };
 
                 let impl_mut = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalMut<#data_name #data_gen> for lens_rs::optics::#optic_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalMut<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::TraversalMut<#ty>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn traverse_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data_name #data_gen) -> Vec<&'__a98shdai mut Self::To> {
                             use #data_name::*;
                             match source {
@@ -243,11 +462,12 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismMut<#data_name #data_gen> for lens_rs::optics::#optic_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismMut<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::PrismMut<#ty>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data_name #data_gen) -> Option<&'__a98shdai mut Self::To> {
                             use #data_name::*;
                             match source {
@@ -256,14 +476,31 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                             }
                         }
                     }
+
+                    impl<#(#data_gen_param,)* St> lens_rs::Setter<#data_name #data_gen> for #marker<St>
+                    where
+                        St: lens_rs::Setter<#ty>,
+                        #data_gen_where
+                    {
+                        type To = St::To;
+
+                        #[inline]
+                        fn set(&self, source: &mut #data_name #data_gen, value: Self::To) {
+                            use #data_name::*;
+                            if let #var_name(x) = source {
+                                self.0.set(x, value)
+                            }
+                        }
+                    }
                 };
 
                 let impl_mv = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::Traversal<#data_name #data_gen> for lens_rs::optics::#optic_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::Traversal<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::Traversal<#ty>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn traverse(&self, source: #data_name #data_gen) -> Vec<Self::To> {
                             use #data_name::*;
                             match source {
@@ -273,11 +510,12 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::Prism<#data_name #data_gen> for lens_rs::optics::#optic_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::Prism<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::Prism<#ty>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm(&self, source: #data_name #data_gen) -> Option<Self::To> {
                             use #data_name::*;
                             match source {
@@ -291,20 +529,766 @@ pub fn derive_prism(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 match mutability {
                     OpticMutability::Ref(_) => vec![impl_ref],
                     OpticMutability::Mut(_) => vec![impl_mut, impl_ref],
-                    OpticMutability::Move   => vec![impl_mv, impl_mut, impl_ref]
+                    OpticMutability::Move   => vec![impl_mv, impl_mut, impl_ref],
+                    OpticMutability::SetterOnly => panic!("only fields of a struct can be #[optic(setter_only)]"),
                 }.into_iter().flat_map(|x| x)
             })
             .collect(),
         _ => panic!("union and struct can't derive the review"),
     };
+    let optic_markers = match &derive_input.data {
+        Data::Enum(e) => e
+            .variants
+            .iter()
+            .filter(|var| {
+                var.attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+            })
+            .map(|var| optic_marker(&derive_input, &var.ident, &var.attrs))
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+    let boxed = boxed_optics(&derive_input, &optic_markers);
+    let markers = match &derive_input.data {
+        Data::Enum(e) => restricted_markers(
+            &derive_input,
+            e.variants
+                .iter()
+                .filter(|var| {
+                    var.attrs
+                        .iter()
+                        .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+                })
+                .map(|var| (var.ident.clone(), &var.attrs[..])),
+        ),
+        _ => quote! {},
+    };
+
+    TokenStream::from(quote!(#prisms #boxed #markers))
+}
+
+struct BoxedSelf<'a> {
+    data_name: &'a syn::Ident,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for BoxedSelf<'_> {
+    fn visit_path_segment(&mut self, segment: &'ast syn::PathSegment) {
+        if segment.ident == "Box" {
+            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                self.found |= args.args.iter().any(|arg| match arg {
+                    syn::GenericArgument::Type(syn::Type::Path(ty)) => matches!(
+                        ty.path.segments.last(),
+                        Some(last) if last.ident == "Self" || last.ident == *self.data_name
+                    ),
+                    _ => false,
+                });
+            }
+        }
+        syn::visit::visit_path_segment(self, segment);
+    }
+}
+
+// does some field hold a `Box<Self>`, e.g. `Option<Box<Node>>`.
+fn is_recursive(derive_input: &DeriveInput) -> bool {
+    let mut visitor = BoxedSelf { data_name: &derive_input.ident, found: false };
+    match &derive_input.data {
+        Data::Struct(st) => st.fields.iter().for_each(|f| visitor.visit_type(&f.ty)),
+        Data::Enum(e) => e
+            .variants
+            .iter()
+            .flat_map(|var| var.fields.iter())
+            .for_each(|f| visitor.visit_type(&f.ty)),
+        Data::Union(_) => {}
+    }
+    visitor.found
+}
+
+// the optics of a recursive type also see through its `Box`,
+// so `optics!(next.Some.next.Some.value)` needs no `_box` between the steps.
+fn boxed_optics(derive_input: &DeriveInput, markers: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+    if !is_recursive(derive_input) {
+        return quote! {};
+    }
+
+    let data_name = &derive_input.ident;
+    let data_gen = &derive_input.generics;
+    let data_gen_param = data_gen.params.iter().collect::<Vec<_>>();
+    let data_gen_where = data_gen
+        .where_clause
+        .iter()
+        .flat_map(|x| x.predicates.clone())
+        .collect::<Punctuated<_, Token![,]>>();
+    let data = quote!(#data_name #data_gen);
+
+    markers
+        .iter()
+        .map(|marker| {
+            let optic = quote!(#marker<Op>);
+            quote! {
+                impl<#(#data_gen_param,)* Op> lens_rs::TraversalRef<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::TraversalRef<#data>,
+                    #data_gen_where
+                {
+                    type To = <#optic as lens_rs::TraversalRef<#data>>::To;
+
+                    #[inline]
+                    fn traverse_ref<'__a98shdai>(&self, source: &'__a98shdai Box<#data>) -> Vec<&'__a98shdai Self::To> {
+                        lens_rs::TraversalRef::<#data>::traverse_ref(self, &**source)
+                    }
+
+                    #[inline]
+                    fn visit_ref<'__a98shdai>(&self, source: &'__a98shdai Box<#data>, f: &mut dyn FnMut(&'__a98shdai Self::To) -> bool) -> bool {
+                        lens_rs::TraversalRef::<#data>::visit_ref(self, &**source, f)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::TraversalMut<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::TraversalMut<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn traverse_mut<'__a98shdai>(&self, source: &'__a98shdai mut Box<#data>) -> Vec<&'__a98shdai mut Self::To> {
+                        lens_rs::TraversalMut::<#data>::traverse_mut(self, &mut **source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::Traversal<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::Traversal<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn traverse(&self, source: Box<#data>) -> Vec<Self::To> {
+                        lens_rs::Traversal::<#data>::traverse(self, *source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::PrismRef<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::PrismRef<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn pm_ref<'__a98shdai>(&self, source: &'__a98shdai Box<#data>) -> Option<&'__a98shdai Self::To> {
+                        lens_rs::PrismRef::<#data>::pm_ref(self, &**source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::PrismMut<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::PrismMut<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn pm_mut<'__a98shdai>(&self, source: &'__a98shdai mut Box<#data>) -> Option<&'__a98shdai mut Self::To> {
+                        lens_rs::PrismMut::<#data>::pm_mut(self, &mut **source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::Prism<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::Prism<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn pm(&self, source: Box<#data>) -> Option<Self::To> {
+                        lens_rs::Prism::<#data>::pm(self, *source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::LensRef<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::LensRef<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn view_ref<'__a98shdai>(&self, source: &'__a98shdai Box<#data>) -> &'__a98shdai Self::To {
+                        lens_rs::LensRef::<#data>::view_ref(self, &**source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::LensMut<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::LensMut<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn view_mut<'__a98shdai>(&self, source: &'__a98shdai mut Box<#data>) -> &'__a98shdai mut Self::To {
+                        lens_rs::LensMut::<#data>::view_mut(self, &mut **source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::Lens<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::Lens<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn view(&self, source: Box<#data>) -> Self::To {
+                        lens_rs::Lens::<#data>::view(self, *source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::Preview<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::Preview<#data>,
+                    #data_gen_where
+                {
+                    type To = <#optic as lens_rs::Preview<#data>>::To;
+
+                    #[inline]
+                    fn preview(&self, source: &Box<#data>) -> Option<Self::To> {
+                        lens_rs::Preview::<#data>::preview(self, &**source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::Getter<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::Getter<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn get(&self, source: &Box<#data>) -> Self::To {
+                        lens_rs::Getter::<#data>::get(self, &**source)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::Setter<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::Setter<#data>,
+                    #data_gen_where
+                {
+                    type To = <#optic as lens_rs::Setter<#data>>::To;
+
+                    #[inline]
+                    fn set(&self, source: &mut Box<#data>, value: Self::To) {
+                        lens_rs::Setter::<#data>::set(self, &mut **source, value)
+                    }
+                }
+
+                impl<#(#data_gen_param,)* Op> lens_rs::Over<Box<#data>> for #optic
+                where
+                    #optic: lens_rs::Over<#data>,
+                    #data_gen_where
+                {
+                    #[inline]
+                    fn over(&self, source: &mut Box<#data>, f: &mut dyn FnMut(&mut Self::To)) {
+                        lens_rs::Over::<#data>::over(self, &mut **source, f)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+// a derive sees only its own type, so the optics of a flattened field's type are found in the sources
+// of the crate being compiled, as the generator finds the optic names.
+struct StructFinder<'a> {
+    name: &'a str,
+    found: Vec<ItemStruct>,
+}
+
+impl<'ast> Visit<'ast> for StructFinder<'_> {
+    fn visit_item_struct(&mut self, item_struct: &'ast ItemStruct) {
+        if item_struct.ident == self.name {
+            self.found.push(item_struct.clone());
+        }
+    }
+}
+
+fn find_structs(dir: &std::path::Path, finder: &mut StructFinder) {
+    let entries = match dir.read_dir() {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    entries.flatten().for_each(|entry| {
+        let path = entry.path();
+        if path.is_dir() {
+            find_structs(&path, finder);
+        } else if path.extension() == Some("rs".as_ref()) {
+            if let Some(file) = fs::read_to_string(&path).ok().and_then(|src| syn::parse_file(&src).ok()) {
+                finder.visit_file(&file);
+            }
+        }
+    });
+}
+
+fn is_flattened(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident(&syn::Ident::new("optic", Span::call_site()))
+            && parse_optic_attr(attr).is_ok_and(|attr| attr.flatten)
+    })
+}
+
+// the names of the optics of the structs named `name`, through their own flattened fields as well,
+// `None` if the sources define no such struct.
+fn struct_optic_names(name: &str, visited: &mut BTreeSet<String>) -> Option<BTreeSet<String>> {
+    if !visited.insert(name.to_string()) {
+        return Some(BTreeSet::new());
+    }
+    let mut finder = StructFinder { name, found: vec![] };
+    if let Some(crate_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
+        let crate_dir = std::path::PathBuf::from(crate_dir);
+        ["src", "examples", "tests"]
+            .iter()
+            .for_each(|dir| find_structs(&crate_dir.join(dir), &mut finder));
+    }
+    if finder.found.is_empty() {
+        return None;
+    }
+    let mut names = BTreeSet::new();
+    finder.found.iter().for_each(|item_struct| {
+        let optic_fields = item_struct.fields.iter().filter(|f| {
+            f.attrs
+                .iter()
+                .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+        });
+        optic_fields.enumerate().for_each(|(i, f)| {
+            names.insert(match &f.ident {
+                Some(ident) => ident.to_string(),
+                None => format!("_{}", i),
+            });
+            if is_flattened(&f.attrs) {
+                names.extend(field_type_name(&f.ty).and_then(|ty| struct_optic_names(&ty, visited)).unwrap_or_default());
+            }
+        });
+    });
+    Some(names)
+}
+
+fn field_type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(type_path) => type_path.path.segments.last().map(|seg| seg.ident.to_string()),
+        _ => None,
+    }
+}
+
+// `#[optic(flatten)]` on a field lets the optics of its type go through the field from the outer type,
+// e.g. `optics!(port)` instead of `optics!(server.port)`, as far as the field's mutability allows.
+fn flattened_optics(derive_input: &DeriveInput, own_optics: &[String]) -> proc_macro2::TokenStream {
+    let fields = match &derive_input.data {
+        Data::Struct(syn::DataStruct { fields, .. }) => fields,
+        _ => return quote! {},
+    };
+
+    let data_name = &derive_input.ident;
+    let data_gen = &derive_input.generics;
+    let data_gen_param = data_gen.params.iter().collect::<Vec<_>>();
+    let data_gen_where = data_gen
+        .where_clause
+        .iter()
+        .flat_map(|x| x.predicates.clone())
+        .collect::<Punctuated<_, Token![,]>>();
+    let data = quote!(#data_name #data_gen);
+
+    // the outer type's own optics win, then the earlier flattened fields.
+    let mut taken = own_optics.iter().cloned().collect::<BTreeSet<_>>();
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| is_flattened(&f.attrs))
+        .flat_map(|(i, f)| {
+            let member = match &f.ident {
+                Some(ident) => quote!(#ident),
+                None => {
+                    let index = syn::Index::from(i);
+                    quote!(#index)
+                }
+            };
+            let to = &f.ty;
+            let mutability = f
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+                .and_then(|attr| parse_optic_attr(attr).ok())
+                .map_or(OpticMutability::Move, |attr| attr.mutability);
+            let names = match field_type_name(to).and_then(|ty| struct_optic_names(&ty, &mut BTreeSet::new())) {
+                Some(names) => names,
+                None => {
+                    let message = "can't find the struct of this flattened field, `#[optic(flatten)]` looks it up by name in the src, examples and tests of this crate";
+                    return vec![syn::Error::new_spanned(to, message).to_compile_error()];
+                }
+            };
+            let names = names
+                .into_iter()
+                .filter(|name| taken.insert(name.clone()))
+                .collect::<Vec<_>>();
+
+            names
+                .into_iter()
+                .map(|name| {
+                    let optic_name = format_ident!("{}", name);
+                    let optic = quote!(lens_rs::optics::#optic_name<Op>);
+                    let impl_ref = quote! {
+                        impl<#(#data_gen_param,)* Op> lens_rs::TraversalRef<#data> for #optic
+                        where
+                            #optic: lens_rs::TraversalRef<#to>,
+                            #data_gen_where
+                        {
+                            type To = <#optic as lens_rs::TraversalRef<#to>>::To;
+
+                            #[inline]
+                            fn traverse_ref<'__a98shdai>(&self, source: &'__a98shdai #data) -> Vec<&'__a98shdai Self::To> {
+                                lens_rs::TraversalRef::<#to>::traverse_ref(self, &source.#member)
+                            }
+
+                            #[inline]
+                            fn visit_ref<'__a98shdai>(&self, source: &'__a98shdai #data, f: &mut dyn FnMut(&'__a98shdai Self::To) -> bool) -> bool {
+                                lens_rs::TraversalRef::<#to>::visit_ref(self, &source.#member, f)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::PrismRef<#data> for #optic
+                        where
+                            #optic: lens_rs::PrismRef<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn pm_ref<'__a98shdai>(&self, source: &'__a98shdai #data) -> Option<&'__a98shdai Self::To> {
+                                lens_rs::PrismRef::<#to>::pm_ref(self, &source.#member)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::LensRef<#data> for #optic
+                        where
+                            #optic: lens_rs::LensRef<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn view_ref<'__a98shdai>(&self, source: &'__a98shdai #data) -> &'__a98shdai Self::To {
+                                lens_rs::LensRef::<#to>::view_ref(self, &source.#member)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::Preview<#data> for #optic
+                        where
+                            #optic: lens_rs::Preview<#to>,
+                            #data_gen_where
+                        {
+                            type To = <#optic as lens_rs::Preview<#to>>::To;
+
+                            #[inline]
+                            fn preview(&self, source: &#data) -> Option<Self::To> {
+                                lens_rs::Preview::<#to>::preview(self, &source.#member)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::Getter<#data> for #optic
+                        where
+                            #optic: lens_rs::Getter<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn get(&self, source: &#data) -> Self::To {
+                                lens_rs::Getter::<#to>::get(self, &source.#member)
+                            }
+                        }
+                    };
+
+                    let impl_mut = quote! {
+                        impl<#(#data_gen_param,)* Op> lens_rs::TraversalMut<#data> for #optic
+                        where
+                            #optic: lens_rs::TraversalMut<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn traverse_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data) -> Vec<&'__a98shdai mut Self::To> {
+                                lens_rs::TraversalMut::<#to>::traverse_mut(self, &mut source.#member)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::PrismMut<#data> for #optic
+                        where
+                            #optic: lens_rs::PrismMut<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn pm_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data) -> Option<&'__a98shdai mut Self::To> {
+                                lens_rs::PrismMut::<#to>::pm_mut(self, &mut source.#member)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::LensMut<#data> for #optic
+                        where
+                            #optic: lens_rs::LensMut<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn view_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data) -> &'__a98shdai mut Self::To {
+                                lens_rs::LensMut::<#to>::view_mut(self, &mut source.#member)
+                            }
+                        }
+                    };
+
+                    let impl_set = quote! {
+                        impl<#(#data_gen_param,)* Op> lens_rs::Setter<#data> for #optic
+                        where
+                            #optic: lens_rs::Setter<#to>,
+                            #data_gen_where
+                        {
+                            type To = <#optic as lens_rs::Setter<#to>>::To;
+
+                            #[inline]
+                            fn set(&self, source: &mut #data, set_to: Self::To) {
+                                lens_rs::Setter::<#to>::set(self, &mut source.#member, set_to)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::Over<#data> for #optic
+                        where
+                            #optic: lens_rs::Over<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn over(&self, source: &mut #data, f: &mut dyn FnMut(&mut Self::To)) {
+                                lens_rs::Over::<#to>::over(self, &mut source.#member, f)
+                            }
+                        }
+                    };
+
+                    let impl_mv = quote! {
+                        impl<#(#data_gen_param,)* Op> lens_rs::Traversal<#data> for #optic
+                        where
+                            #optic: lens_rs::Traversal<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn traverse(&self, source: #data) -> Vec<Self::To> {
+                                lens_rs::Traversal::<#to>::traverse(self, source.#member)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::Prism<#data> for #optic
+                        where
+                            #optic: lens_rs::Prism<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn pm(&self, source: #data) -> Option<Self::To> {
+                                lens_rs::Prism::<#to>::pm(self, source.#member)
+                            }
+                        }
+
+                        impl<#(#data_gen_param,)* Op> lens_rs::Lens<#data> for #optic
+                        where
+                            #optic: lens_rs::Lens<#to>,
+                            #data_gen_where
+                        {
+                            #[inline]
+                            fn view(&self, source: #data) -> Self::To {
+                                lens_rs::Lens::<#to>::view(self, source.#member)
+                            }
+                        }
+                    };
+
+                    match mutability {
+                        OpticMutability::Ref(_) => quote!(#impl_ref),
+                        OpticMutability::Mut(_) => quote!(#impl_set #impl_mut #impl_ref),
+                        OpticMutability::Move => quote!(#impl_mv #impl_set #impl_mut #impl_ref),
+                        OpticMutability::SetterOnly => quote!(#impl_set),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
 
-    TokenStream::from(prisms)
+// the doc comment of a field, one line per `///` line.
+fn doc_comment(attrs: &[syn::Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value: syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(doc), .. }),
+                ..
+            }) => Some(doc.value().trim().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// `OpticDoc<T>` on the optic of every field in `T::OPTICS`, the optic is shared by every type with the field.
+fn optic_docs(derive_input: &DeriveInput, fields: &[(String, &syn::Field)]) -> proc_macro2::TokenStream {
+    let data_name = &derive_input.ident;
+    let mut generics = derive_input.generics.clone();
+    generics.params.push(parse_quote!(__Optic));
+    let (impl_gen, _, _) = generics.split_for_impl();
+    let (_, ty_gen, where_clause) = derive_input.generics.split_for_impl();
+
+    let docs = fields.iter().map(|(optic_name, f)| {
+        let optic_name = format_ident!("{}", optic_name);
+        let ty = &f.ty;
+        let doc = doc_comment(&f.attrs);
+        quote! {
+            impl #impl_gen lens_rs::OpticDoc<#data_name #ty_gen> for lens_rs::optics::#optic_name<__Optic> #where_clause {
+                const DESCRIPTION: &'static str = #doc;
+                const FOCUS_TYPE_NAME: &'static str = stringify!(#ty);
+            }
+        }
+    });
+
+    quote!(#(#docs)*)
+}
+
+// FNV-1a of the names and types of the fields, unlike the std hashers it's the same in every build.
+fn optics_version(data_name: &syn::Ident, fields: &[(String, &syn::Field)]) -> u64 {
+    let signature = fields.iter().fold(data_name.to_string(), |signature, (optic_name, f)| {
+        let ty = &f.ty;
+        format!("{};{}:{}", signature, optic_name, quote!(#ty))
+    });
+    signature
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+// `T::OPTICS`, the type-erased accessors of every `#[optic]` field.
+fn optic_registry(derive_input: &DeriveInput, fields: &[(String, &syn::Field)]) -> proc_macro2::TokenStream {
+    let data_name = &derive_input.ident;
+    let version = optics_version(data_name, fields);
+    let (impl_gen, ty_gen, where_clause) = derive_input.generics.split_for_impl();
+    let mut predicates = where_clause
+        .map(|x| x.predicates.clone())
+        .unwrap_or_default();
+
+    let infos = fields
+        .iter()
+        .map(|(optic_name, f)| {
+            let ty = &f.ty;
+            predicates.push(parse_quote!(#ty: 'static));
+            let member = match &f.ident {
+                Some(ident) => quote!(#ident),
+                None => {
+                    let index = syn::Index::from(optic_name.trim_start_matches('_').parse::<usize>().unwrap());
+                    quote!(#index)
+                }
+            };
+            let attr = f
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+                .unwrap();
+            let get_mut = match parse_optic_attr(attr).unwrap().mutability {
+                OpticMutability::Ref(_) => quote!(::core::option::Option::None),
+                _ => quote!(::core::option::Option::Some(|source: &mut Self| &mut source.#member as &mut dyn std::any::Any)),
+            };
+
+            let description = doc_comment(&f.attrs);
+
+            quote! {
+                lens_rs::OpticInfo {
+                    name: #optic_name,
+                    type_name: stringify!(#ty),
+                    description: #description,
+                    get: |source: &Self| &source.#member as &dyn std::any::Any,
+                    get_mut: #get_mut,
+                    version: #version,
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        impl #impl_gen #data_name #ty_gen where #predicates {
+            pub const OPTICS: &'static [lens_rs::OpticInfo<Self>] = &[#(#infos,)*];
+        }
+    }
+}
+
+// `#[optic(fields)]` on a struct generates `{Struct}Field`, one variant per entry of `OPTICS`.
+fn optic_fields_enum(derive_input: &DeriveInput, fields: &[(String, &syn::Field)]) -> proc_macro2::TokenStream {
+    let wants_fields = derive_input.attrs.iter().any(|attr| {
+        attr.path().is_ident(&syn::Ident::new("optic", Span::call_site()))
+            && matches!(attr.parse_args::<syn::Ident>(), Ok(arg) if arg == "fields")
+    });
+    if !wants_fields {
+        return quote! {};
+    }
+    if !derive_input.generics.params.is_empty() {
+        panic!("generic types can't generate the fields enum");
+    }
+
+    let data_name = &derive_input.ident;
+    let data_vis = &derive_input.vis;
+    let enum_name = format_ident!("{}Field", data_name);
+    let variants = fields
+        .iter()
+        .map(|(optic_name, _)| {
+            let camel = optic_name
+                .split('_')
+                .filter(|word| !word.is_empty())
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars.next().into_iter().flat_map(char::to_uppercase).chain(chars).collect::<String>()
+                })
+                .collect::<String>();
+            if camel.starts_with(|c: char| c.is_ascii_digit()) {
+                format_ident!("Field{}", camel)
+            } else {
+                format_ident!("{}", camel)
+            }
+        })
+        .collect::<Vec<_>>();
+    let optic_names = fields.iter().map(|(optic_name, _)| optic_name);
+
+    quote! {
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+        #data_vis enum #enum_name {
+            #(#variants,)*
+        }
+
+        impl #enum_name {
+            pub const ALL: &'static [#enum_name] = &[#(#enum_name::#variants,)*];
+
+            pub fn name(&self) -> &'static str {
+                self.info().name
+            }
+
+            pub fn info(&self) -> lens_rs::OpticInfo<#data_name> {
+                #data_name::OPTICS[*self as usize]
+            }
+
+            pub fn from_name(name: &str) -> ::core::option::Option<Self> {
+                match name {
+                    #(#optic_names => ::core::option::Option::Some(#enum_name::#variants),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    }
 }
 
 #[proc_macro_derive(Lens, attributes(optic))]
 pub fn derive_lens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
 
+    // the tuple optics of lens-rs stop at `_{MAX_TUPLE_FIELDS - 1}`, a wider tuple struct needs a `tuple*` feature.
+    if let Data::Struct(syn::DataStruct { fields: syn::Fields::Unnamed(fs), .. }) = &derive_input.data {
+        let too_wide = fs
+            .unnamed
+            .iter()
+            .filter(|f| f.attrs.iter().any(|attr| attr.path().is_ident("optic")))
+            .enumerate()
+            .nth(MAX_TUPLE_FIELDS);
+        if let Some((i, f)) = too_wide {
+            let message = match [(4, "tuple4"), (7, "tuple7"), (16, "tuple16"), (32, "tuple32")]
+                .iter()
+                .find(|(width, _)| i < *width)
+            {
+                Some((_, feature)) => {
+                    format!("the optic `_{}` of this field needs the `{}` feature of lens-rs", i, feature)
+                }
+                None => format!("lens-rs has no tuple optic `_{}` for this field, the widest is `_31`", i),
+            };
+            return syn::Error::new_spanned(f, message).to_compile_error().into();
+        }
+    }
+
     let lens: proc_macro2::TokenStream = match derive_input.data.clone() {
         Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fs), .. }) => fs
             .named
@@ -313,7 +1297,7 @@ pub fn derive_lens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 var
                     .attrs
                     .iter()
-                    .any(|attr| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
+                    .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
             })
             .flat_map(|f| {
                 let data = derive_input.clone();
@@ -327,6 +1311,7 @@ pub fn derive_lens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     .collect::<Punctuated<_, Token![,]>>();
 
                 let optics_name = format_ident!("{}", f.ident.as_ref().unwrap());
+                let marker = optic_marker(&derive_input, &optics_name, &f.attrs);
                 let to = &f.ty;
                 let field_name = f.ident.as_ref().unwrap();
 
@@ -334,103 +1319,191 @@ pub fn derive_lens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     .attrs
                     .clone()
                     .into_iter()
-                    .find(|attr: &syn::Attribute| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
+                    .find(|attr: &syn::Attribute| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
                     .unwrap();
-                let mutability = syn::parse::<OpticMutability>(TokenStream::from(attr.tokens)).unwrap();
+                let mutability = parse_optic_attr(&attr).unwrap().mutability;
 
                 let impl_ref = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalRef<#data_name #data_gen> for lens_rs::optics::#optics_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalRef<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::TraversalRef<#to>,
                         #data_gen_where
                     {
                         type To = Tr::To;
 
+                        #[inline]
                         fn traverse_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen) -> Vec<&'__a98shdai Self::To> {
                             self.0.traverse_ref(&source.#field_name)
                         }
+
+                        #[inline]
+                        fn visit_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen, f: &mut dyn FnMut(&'__a98shdai Self::To) -> bool) -> bool {
+                            self.0.visit_ref(&source.#field_name, f)
+                        }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismRef<#data_name #data_gen> for lens_rs::optics::#optics_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismRef<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::PrismRef<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen) -> Option<&'__a98shdai Self::To> {
                             self.0.pm_ref(&source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Ls> lens_rs::LensRef<#data_name #data_gen> for lens_rs::optics::#optics_name<Ls>
+                    impl<#(#data_gen_param,)* Ls> lens_rs::LensRef<#data_name #data_gen> for #marker<Ls>
                     where
                         Ls: lens_rs::LensRef<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn view_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen) -> &'__a98shdai Self::To {
                             self.0.view_ref(&source.#field_name)
                         }
                     }
+
+                    impl<#(#data_gen_param,)* Ls> lens_rs::LensAsyncRef<#data_name #data_gen> for #marker<Ls>
+                    where
+                        Ls: lens_rs::LensAsyncRef<#to>,
+                        #data_gen_where
+                    {
+                        type To = Ls::To;
+
+                        #[inline]
+                        fn view_async_ref<'__a98shdai>(&'__a98shdai self, source: &'__a98shdai #data_name #data_gen) -> lens_rs::BoxFuture<'__a98shdai, &'__a98shdai Self::To> {
+                            self.0.view_async_ref(&source.#field_name)
+                        }
+                    }
+
+                    impl<#(#data_gen_param,)* Pv> lens_rs::Preview<#data_name #data_gen> for #marker<Pv>
+                    where
+                        Pv: lens_rs::Preview<#to>,
+                        #data_gen_where
+                    {
+                        type To = Pv::To;
+
+                        #[inline]
+                        fn preview(&self, source: &#data_name #data_gen) -> Option<Self::To> {
+                            self.0.preview(&source.#field_name)
+                        }
+                    }
+
+                    impl<#(#data_gen_param,)* Gt> lens_rs::Getter<#data_name #data_gen> for #marker<Gt>
+                    where
+                        Gt: lens_rs::Getter<#to>,
+                        #data_gen_where
+                    {
+                        #[inline]
+                        fn get(&self, source: &#data_name #data_gen) -> Self::To {
+                            self.0.get(&source.#field_name)
+                        }
+                    }
                 };
 
                 let impl_mut = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalMut<#data_name #data_gen> for lens_rs::optics::#optics_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalMut<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::TraversalMut<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn traverse_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data_name #data_gen) -> Vec<&'__a98shdai mut Self::To> {
                             self.0.traverse_mut(&mut source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismMut<#data_name #data_gen> for lens_rs::optics::#optics_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismMut<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::PrismMut<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data_name #data_gen) -> Option<&'__a98shdai mut Self::To> {
                             self.0.pm_mut(&mut source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Ls> lens_rs::LensMut<#data_name #data_gen> for lens_rs::optics::#optics_name<Ls>
+                    impl<#(#data_gen_param,)* Ls> lens_rs::LensMut<#data_name #data_gen> for #marker<Ls>
                     where
                         Ls: LensMut<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn view_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data_name #data_gen) -> &'__a98shdai mut Self::To {
                             self.0.view_mut(&mut source.#field_name)
                         }
                     }
 
+                    impl<#(#data_gen_param,)* Ls> lens_rs::LensAsyncMut<#data_name #data_gen> for #marker<Ls>
+                    where
+                        Ls: lens_rs::LensAsyncMut<#to>,
+                        #data_gen_where
+                    {
+                        #[inline]
+                        fn view_async_mut<'__a98shdai>(&'__a98shdai self, source: &'__a98shdai mut #data_name #data_gen) -> lens_rs::BoxFuture<'__a98shdai, &'__a98shdai mut Self::To> {
+                            self.0.view_async_mut(&mut source.#field_name)
+                        }
+                    }
+
+                };
+
+                let impl_set = quote! {
+                    impl<#(#data_gen_param,)* St> lens_rs::Setter<#data_name #data_gen> for #marker<St>
+                    where
+                        St: lens_rs::Setter<#to>,
+                        #data_gen_where
+                    {
+                        type To = St::To;
+
+                        #[inline]
+                        fn set(&self, source: &mut #data_name #data_gen, value: Self::To) {
+                            self.0.set(&mut source.#field_name, value)
+                        }
+                    }
+
+                    impl<#(#data_gen_param,)* Ov> lens_rs::Over<#data_name #data_gen> for #marker<Ov>
+                    where
+                        Ov: lens_rs::Over<#to>,
+                        #data_gen_where
+                    {
+                        #[inline]
+                        fn over(&self, source: &mut #data_name #data_gen, f: &mut dyn FnMut(&mut Self::To)) {
+                            self.0.over(&mut source.#field_name, f)
+                        }
+                    }
                 };
 
                 let impl_mv = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::Traversal<#data_name #data_gen> for lens_rs::optics::#optics_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::Traversal<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::Traversal<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn traverse(&self, source: #data_name #data_gen) -> Vec<Self::To> {
                             self.0.traverse(source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::Prism<#data_name #data_gen> for lens_rs::optics::#optics_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::Prism<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::Prism<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm(&self, source: #data_name #data_gen) -> Option<Self::To> {
                             self.0.pm(source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Ls> lens_rs::Lens<#data_name #data_gen> for lens_rs::optics::#optics_name<Ls>
+                    impl<#(#data_gen_param,)* Ls> lens_rs::Lens<#data_name #data_gen> for #marker<Ls>
                     where
                         Ls: lens_rs::Lens<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn view(&self, source: #data_name #data_gen) -> Self::To {
                             self.0.view(source.#field_name)
                         }
@@ -439,19 +1512,19 @@ pub fn derive_lens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
                 match mutability {
                     OpticMutability::Ref(_) => vec![impl_ref],
-                    OpticMutability::Mut(_) => vec![impl_mut, impl_ref],
-                    OpticMutability::Move   => vec![impl_mv, impl_mut, impl_ref]
+                    OpticMutability::Mut(_) => vec![impl_set, impl_mut, impl_ref],
+                    OpticMutability::Move   => vec![impl_mv, impl_set, impl_mut, impl_ref],
+                    OpticMutability::SetterOnly => vec![impl_set],
                 }.into_iter().flat_map(|x| x)
             }).collect(),
         Data::Struct(syn::DataStruct { fields: syn::Fields::Unnamed(fs), .. }) => fs
             .unnamed
             .iter()
-            .take(7)
             .filter(|var| {
                 var
                     .attrs
                     .iter()
-                    .any(|attr| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
+                    .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
             })
             .enumerate()
             .flat_map(|(i, f)| {
@@ -466,6 +1539,7 @@ pub fn derive_lens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     .collect::<Punctuated<_, Token![,]>>();
 
                 let optics_name = format_ident!("_{}", i);
+                let marker = optic_marker(&derive_input, &optics_name, &f.attrs);
                 let to = &f.ty;
                 let field_name = syn::Index::from(i);
 
@@ -473,103 +1547,191 @@ pub fn derive_lens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     .attrs
                     .clone()
                     .into_iter()
-                    .find(|attr: &syn::Attribute| attr.path.is_ident(&syn::Ident::new("optic", Span::call_site())))
+                    .find(|attr: &syn::Attribute| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
                     .unwrap();
-                let mutability = syn::parse::<OpticMutability>(TokenStream::from(attr.tokens)).unwrap();
+                let mutability = parse_optic_attr(&attr).unwrap().mutability;
 
                 let impl_ref = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalRef<#data_name #data_gen> for lens_rs::optics::#optics_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalRef<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::TraversalRef<#to>,
                         #data_gen_where
                     {
                         type To = Tr::To;
 
+                        #[inline]
                         fn traverse_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen) -> Vec<&'__a98shdai Self::To> {
                             self.0.traverse_ref(&source.#field_name)
                         }
+
+                        #[inline]
+                        fn visit_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen, f: &mut dyn FnMut(&'__a98shdai Self::To) -> bool) -> bool {
+                            self.0.visit_ref(&source.#field_name, f)
+                        }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismRef<#data_name #data_gen> for lens_rs::optics::#optics_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismRef<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::PrismRef<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen) -> Option<&'__a98shdai Self::To> {
                             self.0.pm_ref(&source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Ls> lens_rs::LensRef<#data_name #data_gen> for lens_rs::optics::#optics_name<Ls>
+                    impl<#(#data_gen_param,)* Ls> lens_rs::LensRef<#data_name #data_gen> for #marker<Ls>
                     where
                         Ls: lens_rs::LensRef<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn view_ref<'__a98shdai>(&self, source: &'__a98shdai #data_name #data_gen) -> &'__a98shdai Self::To {
                             self.0.view_ref(&source.#field_name)
                         }
                     }
+
+                    impl<#(#data_gen_param,)* Ls> lens_rs::LensAsyncRef<#data_name #data_gen> for #marker<Ls>
+                    where
+                        Ls: lens_rs::LensAsyncRef<#to>,
+                        #data_gen_where
+                    {
+                        type To = Ls::To;
+
+                        #[inline]
+                        fn view_async_ref<'__a98shdai>(&'__a98shdai self, source: &'__a98shdai #data_name #data_gen) -> lens_rs::BoxFuture<'__a98shdai, &'__a98shdai Self::To> {
+                            self.0.view_async_ref(&source.#field_name)
+                        }
+                    }
+
+                    impl<#(#data_gen_param,)* Pv> lens_rs::Preview<#data_name #data_gen> for #marker<Pv>
+                    where
+                        Pv: lens_rs::Preview<#to>,
+                        #data_gen_where
+                    {
+                        type To = Pv::To;
+
+                        #[inline]
+                        fn preview(&self, source: &#data_name #data_gen) -> Option<Self::To> {
+                            self.0.preview(&source.#field_name)
+                        }
+                    }
+
+                    impl<#(#data_gen_param,)* Gt> lens_rs::Getter<#data_name #data_gen> for #marker<Gt>
+                    where
+                        Gt: lens_rs::Getter<#to>,
+                        #data_gen_where
+                    {
+                        #[inline]
+                        fn get(&self, source: &#data_name #data_gen) -> Self::To {
+                            self.0.get(&source.#field_name)
+                        }
+                    }
                 };
 
                 let impl_mut = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalMut<#data_name #data_gen> for lens_rs::optics::#optics_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::TraversalMut<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::TraversalMut<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn traverse_mut<'a>(&self, source: &'a mut #data_name #data_gen) -> Vec<&'a mut Self::To> {
                             self.0.traverse_mut(&mut source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismMut<#data_name #data_gen> for lens_rs::optics::#optics_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::PrismMut<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::PrismMut<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data_name #data_gen) -> Option<&'__a98shdai mut Self::To> {
                             self.0.pm_mut(&mut source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Ls> lens_rs::LensMut<#data_name #data_gen> for lens_rs::optics::#optics_name<Ls>
+                    impl<#(#data_gen_param,)* Ls> lens_rs::LensMut<#data_name #data_gen> for #marker<Ls>
                     where
                         Ls: lens_rs::LensMut<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn view_mut<'__a98shdai>(&self, source: &'__a98shdai mut #data_name #data_gen) -> &'__a98shdai mut Self::To {
                             self.0.view_mut(&mut source.#field_name)
                         }
                     }
 
+                    impl<#(#data_gen_param,)* Ls> lens_rs::LensAsyncMut<#data_name #data_gen> for #marker<Ls>
+                    where
+                        Ls: lens_rs::LensAsyncMut<#to>,
+                        #data_gen_where
+                    {
+                        #[inline]
+                        fn view_async_mut<'__a98shdai>(&'__a98shdai self, source: &'__a98shdai mut #data_name #data_gen) -> lens_rs::BoxFuture<'__a98shdai, &'__a98shdai mut Self::To> {
+                            self.0.view_async_mut(&mut source.#field_name)
+                        }
+                    }
+
+                };
+
+                let impl_set = quote! {
+                    impl<#(#data_gen_param,)* St> lens_rs::Setter<#data_name #data_gen> for #marker<St>
+                    where
+                        St: lens_rs::Setter<#to>,
+                        #data_gen_where
+                    {
+                        type To = St::To;
+
+                        #[inline]
+                        fn set(&self, source: &mut #data_name #data_gen, value: Self::To) {
+                            self.0.set(&mut source.#field_name, value)
+                        }
+                    }
+
+                    impl<#(#data_gen_param,)* Ov> lens_rs::Over<#data_name #data_gen> for #marker<Ov>
+                    where
+                        Ov: lens_rs::Over<#to>,
+                        #data_gen_where
+                    {
+                        #[inline]
+                        fn over(&self, source: &mut #data_name #data_gen, f: &mut dyn FnMut(&mut Self::To)) {
+                            self.0.over(&mut source.#field_name, f)
+                        }
+                    }
                 };
 
                 let impl_mv = quote! {
-                    impl<#(#data_gen_param,)* Tr> lens_rs::Traversal<#data_name #data_gen> for lens_rs::optics::#optics_name<Tr>
+                    impl<#(#data_gen_param,)* Tr> lens_rs::Traversal<#data_name #data_gen> for #marker<Tr>
                     where
                         Tr: lens_rs::Traversal<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn traverse(&self, source: #data_name #data_gen) -> Vec<Self::To> {
                             self.0.traverse(source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Pm> lens_rs::Prism<#data_name #data_gen> for lens_rs::optics::#optics_name<Pm>
+                    impl<#(#data_gen_param,)* Pm> lens_rs::Prism<#data_name #data_gen> for #marker<Pm>
                     where
                         Pm: lens_rs::Prism<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn pm(&self, source: #data_name #data_gen) -> Option<Self::To> {
                             self.0.pm(source.#field_name)
                         }
                     }
 
-                    impl<#(#data_gen_param,)* Ls> lens_rs::Lens<#data_name #data_gen> for lens_rs::optics::#optics_name<Ls>
+                    impl<#(#data_gen_param,)* Ls> lens_rs::Lens<#data_name #data_gen> for #marker<Ls>
                     where
                         Ls: lens_rs::Lens<#to>,
                         #data_gen_where
                     {
+                        #[inline]
                         fn view(&self, source: #data_name #data_gen) -> Self::To {
                             self.0.view(source.#field_name)
                         }
@@ -578,30 +1740,294 @@ pub fn derive_lens(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
                 match mutability {
                     OpticMutability::Ref(_) => vec![impl_ref],
-                    OpticMutability::Mut(_) => vec![impl_mut, impl_ref],
-                    OpticMutability::Move   => vec![impl_mv, impl_mut, impl_ref]
+                    OpticMutability::Mut(_) => vec![impl_set, impl_mut, impl_ref],
+                    OpticMutability::Move   => vec![impl_mv, impl_set, impl_mut, impl_ref],
+                    OpticMutability::SetterOnly => vec![impl_set],
                 }.into_iter().flat_map(|x| x)
             }).collect(),
         _ => panic!("union and enum can't derive the lens"),
     };
 
-    TokenStream::from(lens)
+    let optic_fields = match &derive_input.data {
+        Data::Struct(syn::DataStruct { fields, .. }) => fields
+            .iter()
+            .filter(|f| {
+                f.attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+            })
+            .enumerate()
+            // a write-only field mustn't be readable through the registry
+            .filter(|(_, f)| {
+                !f.attrs.iter().any(|attr| {
+                    attr.path().is_ident(&syn::Ident::new("optic", Span::call_site()))
+                        && matches!(
+                            parse_optic_attr(attr).map(|attr| attr.mutability),
+                            Ok(OpticMutability::SetterOnly)
+                        )
+                })
+            })
+            // nor may a field with a restricted `vis` be reached through the public registry
+            .filter(|(_, f)| matches!(optic_vis(&f.attrs), syn::Visibility::Public(_)))
+            .map(|(i, f)| match &f.ident {
+                Some(ident) => (ident.to_string(), f),
+                None => (format!("_{}", i), f),
+            })
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+    let registry = optic_registry(&derive_input, &optic_fields);
+    let docs = optic_docs(&derive_input, &optic_fields);
+    let fields_enum = optic_fields_enum(&derive_input, &optic_fields);
+    let optic_markers = optic_fields
+        .iter()
+        .map(|(optic_name, _)| {
+            let optic_name = format_ident!("{}", optic_name);
+            quote!(lens_rs::optics::#optic_name)
+        })
+        .collect::<Vec<_>>();
+    let boxed = boxed_optics(&derive_input, &optic_markers);
+    let own_optics = match &derive_input.data {
+        Data::Struct(syn::DataStruct { fields, .. }) => fields
+            .iter()
+            .filter(|f| {
+                f.attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+            })
+            .enumerate()
+            .map(|(i, f)| match &f.ident {
+                Some(ident) => ident.to_string(),
+                None => format!("_{}", i),
+            })
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+    let flattened = flattened_optics(&derive_input, &own_optics);
+
+    let markers = match &derive_input.data {
+        Data::Struct(syn::DataStruct { fields, .. }) => restricted_markers(
+            &derive_input,
+            fields
+                .iter()
+                .filter(|f| {
+                    f.attrs
+                        .iter()
+                        .any(|attr| attr.path().is_ident(&syn::Ident::new("optic", Span::call_site())))
+                })
+                .enumerate()
+                .map(|(i, f)| match &f.ident {
+                    Some(ident) => (ident.clone(), &f.attrs[..]),
+                    None => (format_ident!("_{}", i), &f.attrs[..]),
+                }),
+        ),
+        _ => quote! {},
+    };
+
+    TokenStream::from(quote!(#lens #registry #docs #fields_enum #boxed #flattened #markers))
+}
+
+struct Projection {
+    name: syn::Ident,
+    fields: Punctuated<syn::Ident, Token![,]>,
+}
+
+impl Parse for Projection {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        Ok(Self {
+            name,
+            fields: content.parse_terminated(syn::Ident::parse, Token![,])?,
+        })
+    }
+}
+
+// `Name(field, ...)`, or `derive(Trait, ...)` for the traits every projection derives.
+enum ProjectArg {
+    Projection(Projection),
+    Derive(Punctuated<syn::Path, Token![,]>),
+}
+
+impl Parse for ProjectArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.fork().parse::<syn::Ident>()? == "derive" {
+            input.parse::<syn::Ident>()?;
+            let content;
+            parenthesized!(content in input);
+            Ok(ProjectArg::Derive(content.parse_terminated(syn::Path::parse, Token![,])?))
+        } else {
+            input.parse().map(ProjectArg::Projection)
+        }
+    }
+}
+
+fn parse_project_args(attrs: &[syn::Attribute]) -> Vec<ProjectArg> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(&syn::Ident::new("project", Span::call_site())))
+        .flat_map(|attr| {
+            attr.parse_args_with(Punctuated::<ProjectArg, Token![,]>::parse_terminated)
+                .expect("only allow #[project(Name(field, ...), ..., derive(Trait, ...))] here")
+        })
+        .collect()
+}
+
+fn parse_projections(attrs: &[syn::Attribute]) -> Vec<Projection> {
+    parse_project_args(attrs)
+        .into_iter()
+        .filter_map(|arg| match arg {
+            ProjectArg::Projection(projection) => Some(projection),
+            ProjectArg::Derive(_) => None,
+        })
+        .collect()
 }
 
-struct OpticCollector<'a>(&'a mut OpticMap);
+fn projection_derives(attrs: &[syn::Attribute]) -> Vec<syn::Path> {
+    parse_project_args(attrs)
+        .into_iter()
+        .flat_map(|arg| match arg {
+            ProjectArg::Derive(derives) => derives.into_iter().collect(),
+            ProjectArg::Projection(_) => vec![],
+        })
+        .collect()
+}
+
+// `UserView` is reached by the optic `user_view`.
+fn projection_optic_name(name: &syn::Ident) -> String {
+    name.to_string()
+        .chars()
+        .enumerate()
+        .fold(String::new(), |mut snake, (i, c)| {
+            if c.is_uppercase() {
+                if i > 0 {
+                    snake.push('_');
+                }
+                snake.extend(c.to_lowercase());
+            } else {
+                snake.push(c);
+            }
+            snake
+        })
+}
+
+#[proc_macro_derive(Project, attributes(project))]
+pub fn derive_project(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    let data_name = &derive_input.ident;
+    let data_vis = &derive_input.vis;
+
+    if !derive_input.generics.params.is_empty() {
+        panic!("generic types can't derive the projection");
+    }
+
+    let named = match &derive_input.data {
+        Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fs), .. }) => &fs.named,
+        _ => panic!("union, enum and tuple struct can't derive the projection"),
+    };
+
+    let derives = projection_derives(&derive_input.attrs);
+    let derives = (!derives.is_empty()).then(|| quote!(#[derive(#(#derives),*)]));
+    let projections: proc_macro2::TokenStream = parse_projections(&derive_input.attrs)
+        .into_iter()
+        .flat_map(|projection| {
+            let proj_name = &projection.name;
+            let optic_name = format_ident!("{}", projection_optic_name(proj_name));
+            let fields = projection
+                .fields
+                .iter()
+                .map(|ident| {
+                    named
+                        .iter()
+                        .find(|f| f.ident.as_ref() == Some(ident))
+                        .unwrap_or_else(|| panic!("`{}` has no field `{}`", data_name, ident))
+                })
+                .collect::<Vec<_>>();
+            let field_vis = fields.iter().map(|f| &f.vis);
+            let field_ty = fields.iter().map(|f| &f.ty);
+            let field_name = projection.fields.iter().collect::<Vec<_>>();
+
+            quote! {
+                #derives
+                #data_vis struct #proj_name {
+                    #(#field_vis #field_name: #field_ty,)*
+                }
+
+                impl From<&#data_name> for #proj_name {
+                    fn from(source: &#data_name) -> Self {
+                        Self {
+                            #(#field_name: source.#field_name.clone(),)*
+                        }
+                    }
+                }
+
+                impl<Pv> lens_rs::Preview<#data_name> for lens_rs::optics::#optic_name<Pv>
+                where
+                    Pv: lens_rs::Preview<#proj_name>,
+                {
+                    type To = Pv::To;
+
+                    #[inline]
+                    fn preview(&self, source: &#data_name) -> Option<Self::To> {
+                        self.0.preview(&#proj_name::from(source))
+                    }
+                }
+
+                impl<Gt> lens_rs::Getter<#data_name> for lens_rs::optics::#optic_name<Gt>
+                where
+                    Gt: lens_rs::Getter<#proj_name>,
+                {
+                    #[inline]
+                    fn get(&self, source: &#data_name) -> Self::To {
+                        self.0.get(&#proj_name::from(source))
+                    }
+                }
+
+                impl<St> lens_rs::Setter<#data_name> for lens_rs::optics::#optic_name<St>
+                where
+                    St: lens_rs::Setter<#proj_name>,
+                {
+                    type To = St::To;
+
+                    #[inline]
+                    fn set(&self, source: &mut #data_name, value: Self::To) {
+                        let mut projection = #proj_name::from(&*source);
+                        self.0.set(&mut projection, value);
+                        #(source.#field_name = projection.#field_name;)*
+                    }
+                }
+            }
+        })
+        .collect();
+
+    TokenStream::from(projections)
+}
+
+struct OpticCollector<'a> {
+    optics: &'a mut OpticMap,
+    // the field names of every anonymous struct, sorted.
+    structx: &'a mut BTreeSet<Vec<String>>,
+    file: &'a str,
+}
 
 impl<'a> OpticCollector<'a> {
-    fn collect_optic_fields<'f>(&mut self, fields: impl Iterator<Item = &'f syn::Field>) {
+    fn insert(&mut self, optic_name: String, item: String) {
+        self.optics
+            .entry(optic_name)
+            .or_default()
+            .insert(format!("{} in {}", item, self.file));
+    }
+
+    fn collect_optic_fields<'f>(&mut self, item: &syn::Ident, fields: impl Iterator<Item = &'f syn::Field>) {
         fields.for_each(|field| {
             if field.attrs.iter().any(|attr| {
-                attr.path
+                attr.path()
                     .is_ident(&syn::Ident::new("optic", Span::call_site()))
             }) {
-                field
-                    .ident
-                    .as_ref()
-                    .map(|ident| format!("{}", ident.to_string()))
-                    .map(|optic_name| self.0.insert(optic_name));
+                if let Some(ident) = &field.ident {
+                    self.insert(ident.to_string(), format!("struct {}", item));
+                }
             }
         });
     }
@@ -609,12 +2035,17 @@ impl<'a> OpticCollector<'a> {
 
 impl<'a> Visit<'_> for OpticCollector<'a> {
     fn visit_item_struct(&mut self, item_struct: &ItemStruct) {
+        parse_projections(&item_struct.attrs)
+            .iter()
+            .for_each(|projection| {
+                self.insert(projection_optic_name(&projection.name), format!("struct {}", item_struct.ident));
+            });
         match &item_struct.fields {
             syn::Fields::Named(fields_named) => {
-                self.collect_optic_fields(fields_named.named.iter())
+                self.collect_optic_fields(&item_struct.ident, fields_named.named.iter())
             }
             syn::Fields::Unnamed(fields_unnamed) => {
-                self.collect_optic_fields(fields_unnamed.unnamed.iter())
+                self.collect_optic_fields(&item_struct.ident, fields_unnamed.unnamed.iter())
             }
             syn::Fields::Unit => (),
         }
@@ -623,22 +2054,86 @@ impl<'a> Visit<'_> for OpticCollector<'a> {
     fn visit_item_enum(&mut self, item_enum: &ItemEnum) {
         item_enum.variants.iter().for_each(|variant| {
             if variant.attrs.iter().any(|attr| {
-                attr.path
+                attr.path()
                     .is_ident(&syn::Ident::new("optic", Span::call_site()))
             }) {
-                self.0.insert(format!("{}", variant.ident));
+                self.insert(format!("{}", variant.ident), format!("enum {}", item_enum.ident));
             }
         })
     }
+
+    // `structx!{ a: 1, b }` and `Structx!{ a: i32, b: String }` name their fields at the start of each segment.
+    fn visit_macro(&mut self, mac: &syn::Macro) {
+        let is_type = match mac.path.segments.last() {
+            Some(seg) if seg.ident == "structx" => false,
+            Some(seg) if seg.ident == "Structx" => true,
+            _ => return,
+        };
+
+        let mut fields = structx_fields(mac.tokens.clone(), is_type);
+        fields
+            .iter()
+            .for_each(|field| self.insert(field.clone(), "structx!".to_string()));
+        fields.sort();
+        fields.dedup();
+        if !fields.is_empty() {
+            self.structx.insert(fields);
+        }
+    }
 }
 
-type OpticMap = HashSet<String>;
+// a field is a name followed by `:` or, in the shorthand of `structx!`, by `,` or nothing.
+// The commas of the generics of a type are skipped, e.g. in `HashMap<K, V>`, but a value may compare with `<`,
+// so the angle brackets are only counted in `Structx!`.
+fn structx_fields(tokens: proc_macro2::TokenStream, is_type: bool) -> Vec<String> {
+    use proc_macro2::{Spacing, TokenTree};
+
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut fields = vec![];
+    let mut depth = 0usize;
+    let mut segment_start = true;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Ident(ident) if segment_start => {
+                let is_field = match tokens.get(i + 1) {
+                    None => true,
+                    Some(TokenTree::Punct(p)) => {
+                        p.as_char() == ',' || (p.as_char() == ':' && p.spacing() == Spacing::Alone)
+                    }
+                    _ => false,
+                };
+                if is_field {
+                    fields.push(ident.to_string());
+                }
+            }
+            TokenTree::Punct(p) if is_type && p.as_char() == '<' => depth += 1,
+            // not the `>` of an `->`
+            TokenTree::Punct(p) if is_type && p.as_char() == '>' => {
+                let is_arrow = i > 0
+                    && matches!(&tokens[i - 1], TokenTree::Punct(q) if q.as_char() == '-' && q.spacing() == Spacing::Joint);
+                if !is_arrow {
+                    depth = depth.saturating_sub(1);
+                }
+            }
+            _ => (),
+        }
+        segment_start = depth == 0 && matches!(token, TokenTree::Punct(p) if p.as_char() == ',');
+    }
+    fields
+}
+
+// the optic names, each with where it's found, ordered so the generated optics are stable.
+type OpticMap = BTreeMap<String, BTreeSet<String>>;
+
+// optics already defined in `lens_rs::optics`, a variant or field named after them reuses them.
+const BUILTIN_OPTICS: &[&str] = &["Ok", "Err", "Some", "None"];
 
 #[doc(hidden)]
 #[proc_macro]
 pub fn scan_optics_from_source_files(input: TokenStream) -> TokenStream {
     let mut iter = input.into_iter();
     let mut optcis_map = OpticMap::new();
+    let mut structx = BTreeSet::new();
 
     loop {
         let token_tree = iter.next();
@@ -650,40 +2145,86 @@ pub fn scan_optics_from_source_files(input: TokenStream) -> TokenStream {
                     String::from_utf8(fs::read(std::path::Path::new(file_name)).unwrap()).unwrap();
                 let syntax = syn::parse_file(&contents)
                     .expect(".rs files should contain valid Rust source code.");
-                OpticCollector(&mut optcis_map).visit_file(&syntax);
-
-                if let Some(token_tree) = iter.next() {
-                    if let TokenTree::Punct(punct) = token_tree {
-                        if punct.to_string() != "," {
-                            panic!(
-                                "scan_optics_from_source_files!(): expect `,`, got `{}`",
-                                punct.to_string()
-                            );
-                        }
-                    }
-                } else {
-                    break;
-                }
+                OpticCollector { optics: &mut optcis_map, structx: &mut structx, file: file_name }
+                    .visit_file(&syntax);
+            }
+            // optics named in the metadata of a crate, e.g. for the generated code no file has.
+            Some(TokenTree::Ident(ident)) => {
+                optcis_map
+                    .entry(ident.to_string())
+                    .or_default()
+                    .insert("the crate metadata".to_string());
             }
             None => break,
             _ => panic!(
-                "scan_optics_from_source_files!(): expect string literal, got `{:?}`",
+                "scan_optics_from_source_files!(): expect string literal or identifier, got `{:?}`",
                 token_tree
             ),
         }
+
+        if let Some(token_tree) = iter.next() {
+            if let TokenTree::Punct(punct) = token_tree {
+                if punct.to_string() != "," {
+                    panic!(
+                        "scan_optics_from_source_files!(): expect `,`, got `{}`",
+                        punct.to_string()
+                    );
+                }
+            }
+        } else {
+            break;
+        }
     }
 
     let mut struct_items = Vec::<ItemStruct>::with_capacity(optcis_map.len());
+    let mut optic_idents = Vec::<syn::Ident>::with_capacity(optcis_map.len());
 
-    for optic_name in optcis_map {
+    for (optic_name, found_in) in optcis_map {
+        if BUILTIN_OPTICS.contains(&optic_name.as_str()) {
+            continue;
+        }
         let optic_ident = syn::Ident::new(&optic_name, Span::call_site());
+        let provenance = format!(
+            "The optic of {}.",
+            found_in.into_iter().collect::<Vec<_>>().join(", ")
+        );
+        optic_idents.push(optic_ident.clone());
         struct_items.push(parse_quote! {
-
-               #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+               #[doc = #provenance]
+               #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
                #[allow(non_camel_case_types)]
                pub struct #optic_ident<Optic>(pub Optic);
         });
     }
 
-    quote!( #( #struct_items )* ).into()
+    // the anonymous structs are defined by structx, one generic struct per set of field names.
+    let structx_impls = structx.iter().map(|fields| {
+        let params = (0..fields.len()).map(|i| format_ident!("__T{}", i)).collect::<Vec<_>>();
+        let names = fields
+            .iter()
+            .map(|field| syn::Ident::new(field, Span::call_site()))
+            .collect::<Vec<_>>();
+        let impls = names.iter().zip(&params).map(|(name, param)| {
+            quote!(impl_field!(<#(#params),*> ::structx::Structx!{ #(#names: #params),* }, #name, #name, #param);)
+        });
+        quote!(#(#impls)*)
+    });
+    let structx_impls = (!structx.is_empty()).then(|| {
+        quote! {
+            #[cfg(feature = "structx")]
+            mod impl_structx {
+                use crate::*;
+                use crate::optics::*;
+
+                #( #structx_impls )*
+            }
+        }
+    });
+
+    quote!(
+        #( #struct_items )*
+        impl_through_ref!(#( #optic_idents ),*);
+        impl_compose!(#( #optic_idents ),*);
+        #structx_impls
+    ).into()
 }